@@ -12,7 +12,7 @@ use std::sync::{Arc, Mutex};
 use tokio::sync::mpsc;
 use tracing::{error, info, warn};
 
-use super::{AudioBackend, AudioBackendConfig, AudioBackendFactory, DeviceInfo};
+use super::{AudioBackend, AudioBackendConfig, AudioBackendFactory, CaptureWarmup, DeviceInfo};
 
 /// cpal-based audio capture backend.
 pub struct CpalBackend {
@@ -23,6 +23,8 @@ pub struct CpalBackend {
     last_audio_timestamp: Arc<AtomicU64>,
     /// Count of dropped samples due to channel backpressure
     samples_dropped: Arc<AtomicU64>,
+    /// Re-armed on every start, since play() resumes a paused device
+    warmup: CaptureWarmup,
 }
 
 impl CpalBackend {
@@ -106,6 +108,8 @@ impl CpalBackend {
 
         let stream_id = device.name().unwrap_or_else(|_| "unknown".to_string());
         let threshold = config.silence_threshold;
+        let warmup = CaptureWarmup::new(config.warmup_ms, config.sample_rate);
+        let callback_warmup = warmup.clone();
 
         // Clone for error callback
         let error_stream_id = stream_id.clone();
@@ -115,6 +119,11 @@ impl CpalBackend {
         let stream = device.build_input_stream(
             &stream_config,
             move |data: &[f32], _: &cpal::InputCallbackInfo| {
+                let data = callback_warmup.skip(data);
+                if data.is_empty() {
+                    return;
+                }
+
                 // Pre-filter obviously silent chunks
                 let rms: f32 =
                     (data.iter().map(|&s| s * s).sum::<f32>() / data.len() as f32).sqrt();
@@ -182,6 +191,7 @@ impl CpalBackend {
             errored_streams,
             last_audio_timestamp,
             samples_dropped,
+            warmup,
         })
     }
 
@@ -208,6 +218,7 @@ impl CpalBackend {
 
 impl AudioBackend for CpalBackend {
     fn start(&self) -> Result<()> {
+        self.warmup.arm();
         for stream in &self.streams {
             stream.play()?;
         }
@@ -4,6 +4,7 @@
 //! allowing different implementations (cpal, pipewire-rs) to be used interchangeably.
 
 pub mod cpal_backend;
+mod warmup;
 
 #[cfg(feature = "pipewire")]
 pub mod pipewire_backend;
@@ -13,6 +14,8 @@ use serde::Deserialize;
 use tokio::sync::mpsc;
 use tracing::{debug, info, warn};
 
+pub use warmup::CaptureWarmup;
+
 /// Configuration for creating an audio backend.
 #[derive(Clone)]
 pub struct AudioBackendConfig {
@@ -22,6 +25,12 @@ pub struct AudioBackendConfig {
     pub sample_rate: u32,
     /// RMS threshold below which audio is considered silence.
     pub silence_threshold: f32,
+    /// First delay before reconnecting to a source that disappeared and came back (ms).
+    pub reconnect_backoff_min_ms: u64,
+    /// Upper bound for the doubling reconnect delay (ms).
+    pub reconnect_backoff_max_ms: u64,
+    /// Audio discarded right after a stream starts, to skip pops and DC settling (ms).
+    pub warmup_ms: u64,
 }
 
 /// Information about an available audio input device.
@@ -252,8 +261,11 @@ fn list_devices_pactl() -> Result<Vec<DeviceInfo>> {
     Ok(devices)
 }
 
-/// Fallback: list devices via the backend-specific enumeration.
-fn list_devices_backend(backend_type: BackendType) -> Result<Vec<DeviceInfo>> {
+/// List devices via the backend-specific enumeration only (no `pactl`).
+///
+/// Names are the ones that backend matches `audio_device` against. Also the
+/// fallback for [`list_devices`].
+pub fn list_devices_backend(backend_type: BackendType) -> Result<Vec<DeviceInfo>> {
     match backend_type {
         BackendType::Auto | BackendType::Cpal => cpal_backend::CpalBackend::list_devices(),
         #[cfg(feature = "pipewire")]
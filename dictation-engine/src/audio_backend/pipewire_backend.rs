@@ -12,10 +12,11 @@ use std::rc::Rc;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
 use std::thread;
+use std::time::{Duration, Instant};
 use tokio::sync::mpsc;
 use tracing::{debug, error, info, warn};
 
-use super::{AudioBackend, AudioBackendConfig, AudioBackendFactory, DeviceInfo};
+use super::{AudioBackend, AudioBackendConfig, AudioBackendFactory, CaptureWarmup, DeviceInfo};
 
 /// Commands sent to the PipeWire thread.
 enum PwCommand {
@@ -41,6 +42,131 @@ struct AudioSourceInfo {
     media_class: String,
 }
 
+/// Connection state of a targeted capture stream.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ConnectionState {
+    /// Stream is attached to the target node
+    Connected,
+    /// Target node disappeared; waiting for a node with the same name
+    Lost,
+    /// Target node is back; reconnect is scheduled for the backoff deadline
+    Pending,
+}
+
+/// Exponential delay between reconnect attempts.
+#[derive(Debug)]
+struct ReconnectBackoff {
+    min: Duration,
+    max: Duration,
+    attempts: u32,
+}
+
+impl ReconnectBackoff {
+    fn new(min: Duration, max: Duration) -> Self {
+        Self { min, max: max.max(min), attempts: 0 }
+    }
+
+    /// Delay before the next attempt; doubles on every call until `reset`
+    fn next_delay(&mut self) -> Duration {
+        let factor = 1u32.checked_shl(self.attempts).unwrap_or(u32::MAX);
+        self.attempts = self.attempts.saturating_add(1);
+        self.min.saturating_mul(factor).min(self.max)
+    }
+
+    fn reset(&mut self) {
+        self.attempts = 0;
+    }
+}
+
+/// Tracks the target node of a capture stream across removal and re-adding.
+///
+/// Suspend/resume and USB resets briefly remove the source node and bring it
+/// back under the same `node.name` with a new serial. Reconnects are delayed by
+/// the backoff, which only resets once a connection has stayed up for the
+/// maximum delay, so a flapping device isn't hammered.
+#[derive(Debug)]
+struct StreamConnection {
+    /// `node.name` of the target source
+    node_name: String,
+    /// Registry id of the target node while it exists
+    node_id: Option<u32>,
+    state: ConnectionState,
+    /// Serial of the reappeared node and when to reconnect to it
+    pending: Option<(u32, Instant)>,
+    connected_at: Instant,
+    backoff: ReconnectBackoff,
+}
+
+impl StreamConnection {
+    fn new(node_name: String, backoff: ReconnectBackoff, now: Instant) -> Self {
+        Self {
+            node_name,
+            node_id: None,
+            state: ConnectionState::Connected,
+            pending: None,
+            connected_at: now,
+            backoff,
+        }
+    }
+
+    /// A source node appeared in the registry
+    fn node_added(&mut self, id: u32, name: &str, serial: u32, now: Instant) {
+        if name != self.node_name {
+            return;
+        }
+        self.node_id = Some(id);
+        if self.state == ConnectionState::Connected {
+            return;
+        }
+
+        let delay = self.backoff.next_delay();
+        info!(
+            "PipeWire: source '{}' is back (serial {}), reconnecting in {:?}",
+            self.node_name, serial, delay
+        );
+        self.state = ConnectionState::Pending;
+        self.pending = Some((serial, now + delay));
+    }
+
+    /// A registry object was removed; returns true if it was the target node
+    fn node_removed(&mut self, id: u32) -> bool {
+        if self.node_id != Some(id) {
+            return false;
+        }
+        self.node_id = None;
+        self.pending = None;
+        self.state = ConnectionState::Lost;
+        true
+    }
+
+    /// Serial to reconnect to, once the scheduled attempt is due
+    fn due(&mut self, now: Instant) -> Option<u32> {
+        if self.state == ConnectionState::Connected && now.duration_since(self.connected_at) >= self.backoff.max {
+            self.backoff.reset();
+        }
+
+        match self.pending {
+            Some((serial, at)) if now >= at => {
+                self.pending = None;
+                Some(serial)
+            }
+            _ => None,
+        }
+    }
+
+    fn connected(&mut self, now: Instant) {
+        self.state = ConnectionState::Connected;
+        self.connected_at = now;
+    }
+
+    /// Reconnecting failed; try the same node again after a longer delay
+    fn connect_failed(&mut self, serial: u32, now: Instant) -> Duration {
+        let delay = self.backoff.next_delay();
+        self.pending = Some((serial, now + delay));
+        delay
+    }
+}
+
 /// PipeWire native audio backend.
 ///
 /// Uses a dedicated thread for the PipeWire MainLoop since it cannot
@@ -84,6 +210,11 @@ impl AudioBackendFactory for PipewireBackend {
 
         let sample_rate = config.sample_rate;
         let silence_threshold = config.silence_threshold;
+        let warmup = CaptureWarmup::new(config.warmup_ms, sample_rate);
+        let reconnect_backoff = (
+            Duration::from_millis(config.reconnect_backoff_min_ms),
+            Duration::from_millis(config.reconnect_backoff_max_ms),
+        );
         let is_running = Arc::new(AtomicBool::new(false));
         let is_running_clone = is_running.clone();
 
@@ -105,14 +236,14 @@ impl AudioBackendFactory for PipewireBackend {
 
         // Resolve device name to PipeWire target serial
         let device_name = config.device_name.clone();
-        let target_serial = match &device_name {
+        let target = match &device_name {
             Some(name) if name != "default" => {
                 match enumerate_audio_sources() {
                     Ok(sources) => {
                         let found = sources.iter().find(|s| s.name == *name);
                         if let Some(source) = found {
                             info!("Resolved device '{}' to PipeWire serial {}", name, source.object_serial);
-                            Some(source.object_serial)
+                            Some((source.object_serial, source.name.clone()))
                         } else {
                             warn!("Device '{}' not found in PipeWire sources, using default", name);
                             None
@@ -137,7 +268,9 @@ impl AudioBackendFactory for PipewireBackend {
                     sample_rate,
                     silence_threshold,
                     is_running_clone,
-                    target_serial,
+                    warmup,
+                    target,
+                    reconnect_backoff,
                 ) {
                     error!("PipeWire thread error: {e}");
                 }
@@ -323,13 +456,18 @@ fn build_audio_format_pod(sample_rate: u32) -> Result<Vec<u8>> {
 }
 
 /// Run the PipeWire MainLoop with single-stream capture.
+///
+/// A stream targeting a specific source (`target` = serial and node name) is
+/// re-created when that node disappears and comes back, see `StreamConnection`.
 fn run_pipewire_thread(
     control_rx: std::sync::mpsc::Receiver<PwCommand>,
     audio_tx: crossbeam_channel::Sender<Vec<i16>>,
     sample_rate: u32,
     silence_threshold: f32,
     is_running: Arc<AtomicBool>,
-    target_serial: Option<u32>,
+    warmup: CaptureWarmup,
+    target: Option<(u32, String)>,
+    reconnect_backoff: (Duration, Duration),
 ) -> Result<()> {
     let mainloop = pw::main_loop::MainLoop::new(None)
         .context("Failed to create PipeWire MainLoop")?;
@@ -346,20 +484,63 @@ fn run_pipewire_thread(
 
     let samples_dropped = Arc::new(AtomicU64::new(0));
 
+    let target_serial = target.as_ref().map(|(serial, _)| *serial);
     let stream_name = if target_serial.is_some() { "targeted" } else { "default" };
-    let (stream, _listener) = create_capture_stream(
+    let stream = create_capture_stream(
         &core,
         target_serial,
         stream_name,
         &format_buffer,
         silence_threshold,
-        audio_tx,
+        audio_tx.clone(),
         samples_dropped.clone(),
         is_running.clone(),
+        warmup.clone(),
     )?;
+    let stream_slot = Rc::new(RefCell::new(Some(stream)));
 
     info!("Created PipeWire capture stream (target_serial: {:?})", target_serial);
 
+    // The default stream follows the default source on its own; only a targeted
+    // stream needs to watch for its node going away and coming back
+    let connection = target.map(|(_, name)| {
+        let (min, max) = reconnect_backoff;
+        Rc::new(RefCell::new(StreamConnection::new(name, ReconnectBackoff::new(min, max), Instant::now())))
+    });
+
+    let registry = core.get_registry().context("Failed to get PipeWire Registry")?;
+    let _registry_listener = connection.clone().map(|connection| {
+        let removed = connection.clone();
+        registry
+            .add_listener_local()
+            .global(move |global| {
+                if global.type_ != pw::types::ObjectType::Node {
+                    return;
+                }
+                let Some(props) = &global.props else {
+                    return;
+                };
+                if props.get("media.class") != Some("Audio/Source") {
+                    return;
+                }
+                let Some(name) = props.get("node.name") else {
+                    return;
+                };
+                let serial = props
+                    .get("object.serial")
+                    .and_then(|s| s.parse::<u32>().ok())
+                    .unwrap_or(global.id);
+                connection.borrow_mut().node_added(global.id, name, serial, Instant::now());
+            })
+            .global_remove(move |id| {
+                let mut connection = removed.borrow_mut();
+                if connection.node_removed(id) {
+                    warn!("PipeWire: source '{}' disappeared, waiting for it to return", connection.node_name);
+                }
+            })
+            .register()
+    });
+
     // Run mainloop with command polling
     let loop_clone = mainloop.loop_();
     let control_rx = std::sync::Arc::new(std::sync::Mutex::new(control_rx));
@@ -367,6 +548,37 @@ fn run_pipewire_thread(
     let mainloop_weak = mainloop.downgrade();
 
     let _timer = loop_clone.add_timer(move |_| {
+        if let Some(connection) = &connection {
+            let now = Instant::now();
+            let due = connection.borrow_mut().due(now);
+            if let Some(serial) = due {
+                // Drop the dead stream before connecting its replacement
+                stream_slot.borrow_mut().take();
+                match create_capture_stream(
+                    &core,
+                    Some(serial),
+                    "targeted",
+                    &format_buffer,
+                    silence_threshold,
+                    audio_tx.clone(),
+                    samples_dropped.clone(),
+                    is_running_for_timer.clone(),
+                    warmup.clone(),
+                ) {
+                    Ok(stream) => {
+                        *stream_slot.borrow_mut() = Some(stream);
+                        warmup.arm();
+                        connection.borrow_mut().connected(now);
+                        info!("PipeWire: reconnected capture stream (target_serial: {})", serial);
+                    }
+                    Err(e) => {
+                        let delay = connection.borrow_mut().connect_failed(serial, now);
+                        warn!("PipeWire: reconnect failed: {e}, retrying in {:?}", delay);
+                    }
+                }
+            }
+        }
+
         let rx = match control_rx.lock() {
             Ok(rx) => rx,
             Err(e) => {
@@ -377,6 +589,9 @@ fn run_pipewire_thread(
         while let Ok(cmd) = rx.try_recv() {
             match cmd {
                 PwCommand::Start => {
+                    // Buffers queued while stopped are stale, and the source may
+                    // have just resumed from suspend
+                    warmup.arm();
                     is_running_for_timer.store(true, Ordering::Relaxed);
                     debug!("PipeWire: recording started");
                 }
@@ -401,9 +616,6 @@ fn run_pipewire_thread(
         Some(std::time::Duration::from_millis(10)),
     );
 
-    // Keep stream alive
-    let _stream = stream;
-
     mainloop.run();
 
     info!("PipeWire thread exiting");
@@ -420,6 +632,7 @@ fn create_capture_stream(
     audio_tx: crossbeam_channel::Sender<Vec<i16>>,
     samples_dropped: Arc<AtomicU64>,
     is_running: Arc<AtomicBool>,
+    warmup: CaptureWarmup,
 ) -> Result<(pw::stream::Stream, pw::stream::StreamListener<()>)> {
     let mut props = pw::properties::properties! {
         *pw::keys::MEDIA_TYPE => "Audio",
@@ -473,6 +686,11 @@ fn create_capture_stream(
                                 )
                             };
 
+                            let f32_samples = warmup.skip(f32_samples);
+                            if f32_samples.is_empty() {
+                                return;
+                            }
+
                             // Pre-filter silence
                             let rms: f32 = (f32_samples.iter().map(|&s| s * s).sum::<f32>()
                                 / f32_samples.len() as f32)
@@ -511,3 +729,66 @@ fn create_capture_stream(
 
     Ok((stream, listener))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn connection(now: Instant) -> StreamConnection {
+        let backoff = ReconnectBackoff::new(Duration::from_millis(500), Duration::from_secs(4));
+        let mut connection = StreamConnection::new("alsa_input.usb-mic".to_string(), backoff, now);
+        connection.node_added(40, "alsa_input.usb-mic", 100, now);
+        connection
+    }
+
+    #[test]
+    fn test_backoff_doubles_and_caps() {
+        let mut backoff = ReconnectBackoff::new(Duration::from_millis(500), Duration::from_secs(4));
+        let delays: Vec<u64> = (0..6).map(|_| backoff.next_delay().as_millis() as u64).collect();
+        assert_eq!(delays, vec![500, 1000, 2000, 4000, 4000, 4000]);
+        backoff.reset();
+        assert_eq!(backoff.next_delay(), Duration::from_millis(500));
+    }
+
+    #[test]
+    fn test_reconnects_when_same_node_returns() {
+        let start = Instant::now();
+        let mut conn = connection(start);
+
+        assert!(!conn.node_removed(7));
+        assert!(conn.node_removed(40));
+        assert_eq!(conn.state, ConnectionState::Lost);
+
+        conn.node_added(41, "alsa_input.other", 200, start);
+        assert_eq!(conn.state, ConnectionState::Lost);
+
+        conn.node_added(42, "alsa_input.usb-mic", 101, start);
+        assert_eq!(conn.due(start), None);
+        assert_eq!(conn.due(start + Duration::from_millis(500)), Some(101));
+        assert_eq!(conn.due(start + Duration::from_millis(600)), None);
+    }
+
+    #[test]
+    fn test_flapping_node_backs_off() {
+        let start = Instant::now();
+        let mut conn = connection(start);
+        let mut now = start;
+
+        for expected_ms in [500, 1000, 2000] {
+            conn.node_removed(conn.node_id.unwrap());
+            conn.node_added(50, "alsa_input.usb-mic", 100, now);
+            let (_, at) = conn.pending.unwrap();
+            assert_eq!(at - now, Duration::from_millis(expected_ms));
+            now = at;
+            assert!(conn.due(now).is_some());
+            conn.connected(now);
+        }
+
+        // A connection that stays up for the max delay resets the backoff
+        conn.due(now + Duration::from_secs(4));
+        conn.node_removed(50);
+        conn.node_added(51, "alsa_input.usb-mic", 100, now);
+        let (_, at) = conn.pending.unwrap();
+        assert_eq!(at - now, Duration::from_millis(500));
+    }
+}
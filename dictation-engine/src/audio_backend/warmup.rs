@@ -0,0 +1,74 @@
+//! Discarding the first samples after a capture stream starts.
+//!
+//! Freshly started streams often open with a pop, click or DC offset that is
+//! still settling. Dropping the first few milliseconds keeps that out of the
+//! engine, where it tends to garble the first word.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// Per-stream warmup counter shared with the capture callback
+#[derive(Clone)]
+pub struct CaptureWarmup {
+    samples: usize,
+    remaining: Arc<AtomicUsize>,
+}
+
+impl CaptureWarmup {
+    /// Disarmed until `arm` is called; `warmup_ms` of 0 disables it
+    pub fn new(warmup_ms: u64, sample_rate: u32) -> Self {
+        let samples = (warmup_ms * sample_rate as u64 / 1000) as usize;
+        Self { samples, remaining: Arc::new(AtomicUsize::new(0)) }
+    }
+
+    /// Start discarding again, called whenever the stream (re)starts
+    pub fn arm(&self) {
+        self.remaining.store(self.samples, Ordering::Relaxed);
+    }
+
+    /// The part of `data` past the warmup, consuming it from the counter
+    pub fn skip<'a, T>(&self, data: &'a [T]) -> &'a [T] {
+        let remaining = self.remaining.load(Ordering::Relaxed);
+        if remaining == 0 {
+            return data;
+        }
+        let discard = remaining.min(data.len());
+        self.remaining.store(remaining - discard, Ordering::Relaxed);
+        &data[discard..]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_discards_across_callbacks() {
+        // 50ms at 16kHz = 800 samples
+        let warmup = CaptureWarmup::new(50, 16000);
+        warmup.arm();
+        let chunk = [1i16; 512];
+        assert!(warmup.skip(&chunk).is_empty());
+        assert_eq!(warmup.skip(&chunk).len(), 224);
+        assert_eq!(warmup.skip(&chunk).len(), 512);
+    }
+
+    #[test]
+    fn test_rearm_on_restart() {
+        let warmup = CaptureWarmup::new(10, 16000);
+        let chunk = [0.0f32; 200];
+        // Not armed yet
+        assert_eq!(warmup.skip(&chunk).len(), 200);
+        warmup.arm();
+        assert_eq!(warmup.skip(&chunk).len(), 40);
+        warmup.arm();
+        assert_eq!(warmup.skip(&chunk).len(), 40);
+    }
+
+    #[test]
+    fn test_zero_disables() {
+        let warmup = CaptureWarmup::new(0, 16000);
+        warmup.arm();
+        assert_eq!(warmup.skip(&[1i16, 2, 3]), &[1, 2, 3]);
+    }
+}
@@ -0,0 +1,197 @@
+//! Session audio buffer with a bounded in-memory size.
+//!
+//! Long continuous sessions would otherwise grow the buffer without limit
+//! (~115MB/hour at 16kHz). Once the in-memory portion exceeds its limit, the
+//! oldest samples are either spilled to a temp file (still transcribed by the
+//! final pass) or dropped with a warning.
+
+use anyhow::Result;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use tracing::warn;
+
+/// Audio buffer that keeps at most `max_samples` in memory.
+pub struct BoundedAudioBuffer {
+    samples: Vec<i16>,
+    /// In-memory limit in samples (0 = unbounded)
+    max_samples: usize,
+    /// Raw little-endian i16 PCM file for spilled samples (None = drop oldest)
+    spill_path: Option<PathBuf>,
+    spilled: usize,
+    dropped: usize,
+    /// Samples pushed since the last clear, including spilled and dropped ones
+    total: usize,
+}
+
+impl BoundedAudioBuffer {
+    /// Buffer with no memory limit
+    pub fn unbounded() -> Self {
+        Self::new(0, None)
+    }
+
+    pub fn new(max_samples: usize, spill_path: Option<PathBuf>) -> Self {
+        Self {
+            samples: Vec::new(),
+            max_samples,
+            spill_path,
+            spilled: 0,
+            dropped: 0,
+            total: 0,
+        }
+    }
+
+    /// Append samples, spilling or dropping the oldest ones past the limit.
+    pub fn push(&mut self, samples: &[i16]) {
+        self.samples.extend_from_slice(samples);
+        self.total += samples.len();
+
+        if self.max_samples == 0 || self.samples.len() <= self.max_samples {
+            return;
+        }
+
+        // Evict an extra block so we don't touch the disk on every push
+        let block = (self.max_samples / 10).max(1);
+        let evict = (self.samples.len() - self.max_samples + block).min(self.samples.len());
+
+        if let Some(ref path) = self.spill_path {
+            match append_samples(path, &self.samples[..evict]) {
+                Ok(()) => {
+                    self.spilled += evict;
+                    self.samples.drain(..evict);
+                    return;
+                }
+                Err(e) => {
+                    warn!("Failed to spill audio to {:?}: {}, dropping oldest samples instead", path, e);
+                }
+            }
+        }
+
+        if self.dropped == 0 {
+            warn!(
+                "Audio buffer exceeded {} samples, dropping oldest audio (enable audio_buffer_spill to keep it)",
+                self.max_samples
+            );
+        }
+        self.dropped += evict;
+        self.samples.drain(..evict);
+    }
+
+    /// Samples currently held in memory (the most recent audio)
+    pub fn recent(&self) -> &[i16] {
+        &self.samples
+    }
+
+    /// Transcribable length: spilled plus in-memory samples
+    pub fn len(&self) -> usize {
+        self.spilled + self.samples.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Samples pushed since the last clear. Keeps growing after the limit is hit,
+    /// so callers can detect new audio even when old audio is being evicted.
+    pub fn total_len(&self) -> usize {
+        self.total
+    }
+
+    /// Full transcribable audio: spilled samples (read back from disk) followed
+    /// by the in-memory samples.
+    pub fn to_vec(&self) -> Result<Vec<i16>> {
+        let mut out = Vec::with_capacity(self.len());
+
+        if self.spilled > 0 {
+            if let Some(ref path) = self.spill_path {
+                let bytes = fs::read(path)?;
+                out.extend(bytes.chunks_exact(2).map(|b| i16::from_le_bytes([b[0], b[1]])));
+            }
+        }
+
+        out.extend_from_slice(&self.samples);
+        Ok(out)
+    }
+
+    /// Drop all audio and remove the spill file
+    pub fn clear(&mut self) {
+        self.samples.clear();
+        self.remove_spill_file();
+        self.spilled = 0;
+        self.dropped = 0;
+        self.total = 0;
+    }
+
+    fn remove_spill_file(&self) {
+        if self.spilled > 0 {
+            if let Some(ref path) = self.spill_path {
+                let _ = fs::remove_file(path);
+            }
+        }
+    }
+}
+
+impl Drop for BoundedAudioBuffer {
+    fn drop(&mut self) {
+        self.remove_spill_file();
+    }
+}
+
+fn append_samples(path: &PathBuf, samples: &[i16]) -> Result<()> {
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    let bytes: Vec<u8> = samples.iter().flat_map(|s| s.to_le_bytes()).collect();
+    file.write_all(&bytes)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ramp(start: i16, len: usize) -> Vec<i16> {
+        (0..len).map(|i| start.wrapping_add(i as i16)).collect()
+    }
+
+    #[test]
+    fn test_unbounded_keeps_everything() {
+        let mut buffer = BoundedAudioBuffer::unbounded();
+        buffer.push(&ramp(0, 5000));
+        assert_eq!(buffer.len(), 5000);
+        assert_eq!(buffer.recent().len(), 5000);
+    }
+
+    #[test]
+    fn test_drop_oldest_past_limit() {
+        let mut buffer = BoundedAudioBuffer::new(1000, None);
+        for i in 0..30 {
+            buffer.push(&ramp(i * 100, 100));
+        }
+
+        assert!(buffer.recent().len() <= 1000);
+        assert_eq!(buffer.len(), buffer.recent().len());
+        assert_eq!(buffer.total_len(), 3000);
+        // Newest sample is retained
+        assert_eq!(*buffer.recent().last().unwrap(), 2999);
+    }
+
+    #[test]
+    fn test_spill_preserves_all_audio() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("spill.raw");
+        let mut buffer = BoundedAudioBuffer::new(1000, Some(path.clone()));
+
+        let all = ramp(0, 3000);
+        for chunk in all.chunks(100) {
+            buffer.push(chunk);
+        }
+
+        assert!(buffer.recent().len() <= 1000);
+        assert!(path.exists());
+        assert_eq!(buffer.len(), 3000);
+        assert_eq!(buffer.to_vec().unwrap(), all);
+
+        buffer.clear();
+        assert!(!path.exists());
+        assert!(buffer.is_empty());
+    }
+}
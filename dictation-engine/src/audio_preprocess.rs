@@ -0,0 +1,218 @@
+//! Optional external audio preprocessing (noise suppression, VAD gating).
+//!
+//! When `audio_preprocess_command` is set, session audio is piped through the
+//! command as raw 16kHz mono i16 little-endian PCM on stdin and read back from
+//! its stdout before it reaches the engine and VAD. The command must emit one
+//! output sample per input sample (e.g. an RNNoise filter).
+//!
+//! Output arrives with whatever delay the command buffers, so the live preview
+//! lags by that much. If the command fails or stops producing output, the
+//! audio still in flight and everything after it is passed through raw.
+
+use anyhow::{Context, Result};
+use std::collections::VecDeque;
+use std::io::{Read, Write};
+use std::process::{Child, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender, TryRecvError};
+use std::thread;
+use std::time::{Duration, Instant};
+use tracing::{debug, warn};
+
+/// Streaming pipe through an external command, one instance per session.
+pub struct AudioPreprocessor {
+    child: Child,
+    input_tx: Option<Sender<Vec<i16>>>,
+    output_rx: Receiver<Vec<i16>>,
+    /// Raw samples sent but not yet returned, replayed if the command fails
+    in_flight: VecDeque<i16>,
+    /// Fall back to raw audio once this many samples are in flight
+    max_lag_samples: usize,
+    failed: bool,
+}
+
+impl AudioPreprocessor {
+    /// Spawn `command` through `sh -c`.
+    pub fn spawn(command: &str, max_lag_samples: usize) -> Result<Self> {
+        let mut child = Command::new("sh")
+            .arg("-c")
+            .arg(command)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()
+            .with_context(|| format!("Failed to spawn audio preprocess command '{}'", command))?;
+
+        let mut stdin = child.stdin.take().context("Preprocess command has no stdin")?;
+        let mut stdout = child.stdout.take().context("Preprocess command has no stdout")?;
+
+        let (input_tx, input_rx) = mpsc::channel::<Vec<i16>>();
+        let (output_tx, output_rx) = mpsc::channel::<Vec<i16>>();
+
+        // Writer: exits (closing stdin) when the sender is dropped or the pipe breaks
+        thread::spawn(move || {
+            for samples in input_rx {
+                let bytes: Vec<u8> = samples.iter().flat_map(|s| s.to_le_bytes()).collect();
+                if let Err(e) = stdin.write_all(&bytes) {
+                    debug!("Audio preprocess stdin closed: {}", e);
+                    break;
+                }
+            }
+        });
+
+        // Reader: exits on EOF, which disconnects the output channel
+        thread::spawn(move || {
+            let mut buf = [0u8; 4096];
+            let mut carry: Option<u8> = None;
+            loop {
+                let n = match stdout.read(&mut buf) {
+                    Ok(0) | Err(_) => break,
+                    Ok(n) => n,
+                };
+
+                let mut bytes = Vec::with_capacity(n + 1);
+                bytes.extend(carry.take());
+                bytes.extend_from_slice(&buf[..n]);
+                if bytes.len() % 2 == 1 {
+                    carry = bytes.pop();
+                }
+
+                let samples = bytes.chunks_exact(2).map(|b| i16::from_le_bytes([b[0], b[1]])).collect();
+                if output_tx.send(samples).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Ok(Self {
+            child,
+            input_tx: Some(input_tx),
+            output_rx,
+            in_flight: VecDeque::new(),
+            max_lag_samples,
+            failed: false,
+        })
+    }
+
+    /// Feed captured samples and return whatever processed audio is ready.
+    pub fn process(&mut self, input: &[i16]) -> Vec<i16> {
+        if self.failed {
+            return input.to_vec();
+        }
+
+        self.in_flight.extend(input.iter().copied());
+        let sent = self.input_tx.as_ref().is_some_and(|tx| tx.send(input.to_vec()).is_ok());
+
+        let mut output = Vec::new();
+        loop {
+            match self.output_rx.try_recv() {
+                Ok(samples) => self.accept(samples, &mut output),
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    self.fail("command exited", &mut output);
+                    return output;
+                }
+            }
+        }
+
+        if !sent {
+            self.fail("stdin closed", &mut output);
+        } else if self.in_flight.len() > self.max_lag_samples {
+            self.fail("command is not producing output", &mut output);
+        }
+        output
+    }
+
+    /// Close the command's stdin and collect its remaining output.
+    ///
+    /// Any audio not returned within `timeout` is appended raw.
+    pub fn finish(&mut self, timeout: Duration) -> Vec<i16> {
+        let mut output = Vec::new();
+        if self.failed {
+            return output;
+        }
+
+        self.input_tx = None;
+        let deadline = Instant::now() + timeout;
+
+        while !self.in_flight.is_empty() {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            match self.output_rx.recv_timeout(remaining) {
+                Ok(samples) => self.accept(samples, &mut output),
+                Err(RecvTimeoutError::Timeout) => {
+                    self.fail("timed out flushing", &mut output);
+                    break;
+                }
+                Err(RecvTimeoutError::Disconnected) => {
+                    self.fail("command exited before flushing", &mut output);
+                    break;
+                }
+            }
+        }
+
+        output
+    }
+
+    fn accept(&mut self, samples: Vec<i16>, output: &mut Vec<i16>) {
+        let acknowledged = samples.len().min(self.in_flight.len());
+        self.in_flight.drain(..acknowledged);
+        output.extend(samples);
+    }
+
+    /// Switch to raw passthrough, replaying audio the command never returned
+    fn fail(&mut self, reason: &str, output: &mut Vec<i16>) {
+        if !self.in_flight.is_empty() {
+            warn!(
+                "Audio preprocess command failed ({}), using raw audio for the rest of the session",
+                reason
+            );
+        }
+        self.failed = true;
+        self.input_tx = None;
+        output.extend(self.in_flight.drain(..));
+    }
+}
+
+impl Drop for AudioPreprocessor {
+    fn drop(&mut self) {
+        self.input_tx = None;
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ramp(len: usize) -> Vec<i16> {
+        (0..len).map(|i| i as i16).collect()
+    }
+
+    fn run(command: &str, max_lag: usize, input: &[i16]) -> Vec<i16> {
+        let mut pre = AudioPreprocessor::spawn(command, max_lag).unwrap();
+        let mut output = Vec::new();
+        for chunk in input.chunks(160) {
+            output.extend(pre.process(chunk));
+        }
+        output.extend(pre.finish(Duration::from_secs(2)));
+        output
+    }
+
+    #[test]
+    fn test_identity_command_round_trips() {
+        let input = ramp(4000);
+        assert_eq!(run("cat", 16000, &input), input);
+    }
+
+    #[test]
+    fn test_failing_command_falls_back_to_raw() {
+        let input = ramp(4000);
+        assert_eq!(run("exit 1", 16000, &input), input);
+    }
+
+    #[test]
+    fn test_silent_command_falls_back_after_lag_limit() {
+        let input = ramp(4000);
+        assert_eq!(run("cat > /dev/null", 1000, &input), input);
+    }
+}
@@ -3,7 +3,10 @@
 //! Provides reusable chunking and merging logic for transcription engines
 //! that have context length limits.
 
+use crate::pause_split::{self, PauseSplit};
 use parakeet_rs::TimedToken;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
 use tracing::debug;
 
 /// Configuration for audio chunking
@@ -15,6 +18,10 @@ pub struct ChunkConfig {
     pub overlap_seconds: u32,
     /// Sample rate in Hz
     pub sample_rate: u32,
+    /// Chunks transcribed at once (1 = sequential)
+    pub parallelism: usize,
+    /// Break the merged text at long pauses between words (None = off)
+    pub pause_split: Option<PauseSplit>,
 }
 
 impl Default for ChunkConfig {
@@ -23,6 +30,8 @@ impl Default for ChunkConfig {
             max_chunk_seconds: 30,
             overlap_seconds: 2,
             sample_rate: 16000,
+            parallelism: 1,
+            pause_split: None,
         }
     }
 }
@@ -34,9 +43,17 @@ impl ChunkConfig {
             max_chunk_seconds,
             overlap_seconds,
             sample_rate,
+            parallelism: 1,
+            pause_split: None,
         }
     }
 
+    /// Transcribe up to `parallelism` chunks concurrently (at least 1)
+    pub fn with_parallelism(mut self, parallelism: usize) -> Self {
+        self.parallelism = parallelism.max(1);
+        self
+    }
+
     /// Maximum samples per chunk
     pub fn max_chunk_samples(&self) -> usize {
         (self.max_chunk_seconds * self.sample_rate) as usize
@@ -51,8 +68,54 @@ impl ChunkConfig {
     pub fn needs_chunking(&self, samples: &[i16]) -> bool {
         samples.len() > self.max_chunk_samples()
     }
+
+    /// Window searched around each fixed boundary for a silence point (1 second)
+    fn silence_search_window(&self) -> usize {
+        self.sample_rate as usize
+    }
+
+    /// Frame size used for silence energy measurement (25ms)
+    fn silence_frame_size(&self) -> usize {
+        (self.sample_rate as usize / 40).max(1)
+    }
 }
 
+/// Apply `f` to every item on up to `parallelism` threads, returning results in input order
+fn map_ordered<T, R, F>(items: &[T], parallelism: usize, f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    let workers = parallelism.min(items.len());
+    if workers <= 1 {
+        return items.iter().map(f).collect();
+    }
+
+    let next = AtomicUsize::new(0);
+    let slots: Mutex<Vec<Option<R>>> = Mutex::new((0..items.len()).map(|_| None).collect());
+    std::thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                let Some(item) = items.get(i) else { break };
+                let result = f(item);
+                slots.lock().unwrap_or_else(|e| e.into_inner())[i] = Some(result);
+            });
+        }
+    });
+
+    slots
+        .into_inner()
+        .unwrap_or_else(|e| e.into_inner())
+        .into_iter()
+        .map(|slot| slot.expect("every chunk index is processed once"))
+        .collect()
+}
+
+/// RMS (i16 scale) below which a frame counts as silence for chunk splitting (~-36 dBFS)
+const SILENCE_SPLIT_RMS: f64 = 500.0;
+
 /// Iterator over audio chunks with overlap
 #[allow(dead_code)]
 pub struct AudioChunks<'a> {
@@ -86,12 +149,32 @@ impl<'a> Iterator for AudioChunks<'a> {
         let max_samples = self.config.max_chunk_samples();
         let overlap = self.config.overlap_samples();
 
-        let chunk_end = (self.offset + max_samples).min(self.samples.len());
+        let fixed_end = (self.offset + max_samples).min(self.samples.len());
+
+        // Snap interior boundaries to a nearby silence point so words aren't cut in half.
+        // Falls back to the fixed-size boundary when the window has no silence.
+        let chunk_end = if fixed_end < self.samples.len() {
+            find_silence_split(
+                self.samples,
+                fixed_end,
+                self.config.silence_search_window(),
+                self.config.silence_frame_size(),
+            )
+            .filter(|&pos| pos > self.offset)
+            .unwrap_or(fixed_end)
+        } else {
+            fixed_end
+        };
+
         let chunk = &self.samples[self.offset..chunk_end];
         let chunk_num = self.chunk_num;
 
-        // Advance for next iteration
-        self.offset += max_samples - overlap;
+        // Advance for next iteration (always make forward progress)
+        self.offset = if chunk_end >= self.samples.len() {
+            self.samples.len()
+        } else {
+            chunk_end.saturating_sub(overlap).max(self.offset + 1)
+        };
         self.chunk_num += 1;
 
         Some((chunk_num, chunk))
@@ -110,30 +193,59 @@ pub struct TimestampedChunkResult {
 /// Scans for the lowest-energy frame to avoid splitting audio mid-word.
 /// Returns the best split position (start of the quietest frame).
 fn find_silence_boundary(samples: &[i16], target_pos: usize, search_window: usize, frame_size: usize) -> usize {
+    match find_quietest_frame(samples, target_pos, search_window, frame_size) {
+        Some((best_pos, best_energy)) => {
+            debug!("find_silence_boundary: target={}, best={}, energy={:.1}", target_pos, best_pos, best_energy);
+            best_pos
+        }
+        None => target_pos.min(samples.len()),
+    }
+}
+
+/// Like `find_silence_boundary`, but only returns a position if the quietest
+/// frame is actually silent (RMS below `SILENCE_SPLIT_RMS`).
+fn find_silence_split(samples: &[i16], target_pos: usize, search_window: usize, frame_size: usize) -> Option<usize> {
+    let (best_pos, best_energy) = find_quietest_frame(samples, target_pos, search_window, frame_size)?;
+    if best_energy.sqrt() <= SILENCE_SPLIT_RMS {
+        debug!("find_silence_split: target={}, snapped to {}", target_pos, best_pos);
+        Some(best_pos)
+    } else {
+        debug!("find_silence_split: no silence near {}, using fixed boundary", target_pos);
+        None
+    }
+}
+
+/// Scan frames within the search window and return the lowest mean-square-energy
+/// frame start. Ties prefer the frame closest to `target_pos`.
+fn find_quietest_frame(samples: &[i16], target_pos: usize, search_window: usize, frame_size: usize) -> Option<(usize, f64)> {
     let search_start = target_pos.saturating_sub(search_window / 2);
     let search_end = (target_pos + search_window / 2).min(samples.len());
 
-    if search_start >= search_end || search_end - search_start < frame_size {
-        return target_pos.min(samples.len());
+    if frame_size == 0 || search_start >= search_end || search_end - search_start < frame_size {
+        return None;
     }
 
-    let mut best_pos = target_pos;
-    let mut best_energy = f64::MAX;
+    let mut best: Option<(usize, f64)> = None;
 
     let mut pos = search_start;
     while pos + frame_size <= search_end {
         let frame = &samples[pos..pos + frame_size];
         let energy: f64 = frame.iter().map(|&s| (s as f64).powi(2)).sum::<f64>() / frame_size as f64;
 
-        if energy < best_energy {
-            best_energy = energy;
-            best_pos = pos;
+        let better = match best {
+            None => true,
+            Some((best_pos, best_energy)) => {
+                energy < best_energy
+                    || (energy == best_energy && pos.abs_diff(target_pos) < best_pos.abs_diff(target_pos))
+            }
+        };
+        if better {
+            best = Some((pos, energy));
         }
         pos += frame_size;
     }
 
-    debug!("find_silence_boundary: target={}, best={}, energy={:.1}", target_pos, best_pos, best_energy);
-    best_pos
+    best
 }
 
 /// Generate VAD-aware chunk boundaries that prefer silence points over fixed splits
@@ -173,12 +285,17 @@ fn chunk_boundaries_vad(samples: &[i16], config: &ChunkConfig) -> Vec<(usize, us
 /// Merge timestamped chunk results using word time positions instead of text matching.
 ///
 /// For overlapping regions, keeps chunk N's words (more left context) and appends
-/// chunk N+1's words that start after the overlap zone.
-fn merge_chunks_timestamped(chunks: &[TimestampedChunkResult], overlap_seconds: f32) -> String {
+/// chunk N+1's words that start after the overlap zone. With `pause_split` the
+/// words are joined with breaks at long pauses.
+fn merge_chunks_timestamped(
+    chunks: &[TimestampedChunkResult],
+    overlap_seconds: f32,
+    pause_split: Option<PauseSplit>,
+) -> String {
     if chunks.is_empty() {
         return String::new();
     }
-    if chunks.len() == 1 {
+    if chunks.len() == 1 && (pause_split.is_none() || chunks[0].words.is_empty()) {
         return chunks[0].text.clone();
     }
 
@@ -220,53 +337,47 @@ fn merge_chunks_timestamped(chunks: &[TimestampedChunkResult], overlap_seconds:
         }
     }
 
-    // Build text from collected words
-    let mut result = String::new();
-    for (i, word) in all_words.iter().enumerate() {
-        let is_standalone_punct = word.text.len() == 1
-            && word.text.chars().all(|c| matches!(c, '.' | ',' | '!' | '?' | ';' | ':'));
-        if i > 0 && !is_standalone_punct {
-            result.push(' ');
-        }
-        result.push_str(&word.text);
-    }
-
-    result
+    pause_split::join_words(&all_words, pause_split)
 }
 
 /// Process long audio in chunks using timestamped transcription for accurate merging.
 ///
 /// Uses VAD-aware boundaries and word timestamps to merge chunks without
-/// the fragility of text-based overlap matching.
+/// the fragility of text-based overlap matching. With `config.parallelism` > 1
+/// chunks are transcribed concurrently, so `transcribe_fn` must be safe to call
+/// from several threads at once.
 pub fn transcribe_chunked_with_timestamps<F>(
     samples: &[i16],
     config: &ChunkConfig,
     transcribe_fn: F,
 ) -> anyhow::Result<String>
 where
-    F: Fn(&[i16]) -> anyhow::Result<TimestampedChunkResult>,
+    F: Fn(&[i16]) -> anyhow::Result<TimestampedChunkResult> + Sync,
 {
     let duration_secs = samples.len() as f32 / config.sample_rate as f32;
     tracing::info!(
-        "transcribe_chunked_with_timestamps: chunking {:.1}s audio into ~{}s segments",
+        "transcribe_chunked_with_timestamps: chunking {:.1}s audio into ~{}s segments ({} at a time)",
         duration_secs,
-        config.max_chunk_seconds
+        config.max_chunk_seconds,
+        config.parallelism
     );
 
     let boundaries = chunk_boundaries_vad(samples, config);
+    let outputs = map_ordered(&boundaries, config.parallelism, |&(start, end)| {
+        transcribe_fn(&samples[start..end])
+    });
     let mut results: Vec<TimestampedChunkResult> = Vec::new();
 
-    for (chunk_num, &(start, end)) in boundaries.iter().enumerate() {
-        let chunk = &samples[start..end];
+    for (chunk_num, (&(start, end), output)) in boundaries.iter().zip(outputs).enumerate() {
         let chunk_start_sec = start as f32 / config.sample_rate as f32;
         let chunk_end_sec = end as f32 / config.sample_rate as f32;
 
         debug!(
             "transcribe_chunked_ts: chunk {} ({:.1}s - {:.1}s, {} samples)",
-            chunk_num, chunk_start_sec, chunk_end_sec, chunk.len()
+            chunk_num, chunk_start_sec, chunk_end_sec, end - start
         );
 
-        match transcribe_fn(chunk) {
+        match output {
             Ok(mut result) => {
                 // Offset timestamps to absolute positions
                 for word in &mut result.words {
@@ -286,7 +397,7 @@ where
     }
 
     let overlap_secs = config.overlap_seconds as f32;
-    let merged = merge_chunks_timestamped(&results, overlap_secs);
+    let merged = merge_chunks_timestamped(&results, overlap_secs, config.pause_split);
     tracing::info!(
         "transcribe_chunked_with_timestamps: merged {} chunks into {} chars",
         results.len(),
@@ -389,7 +500,7 @@ pub fn transcribe_chunked<F>(
     transcribe_fn: F,
 ) -> anyhow::Result<String>
 where
-    F: Fn(&[i16]) -> anyhow::Result<String>,
+    F: Fn(&[i16]) -> anyhow::Result<String> + Sync,
 {
     // Short audio: transcribe directly
     if !config.needs_chunking(samples) {
@@ -404,9 +515,11 @@ where
         config.max_chunk_seconds
     );
 
+    let chunks: Vec<(usize, &[i16])> = AudioChunks::new(samples, config.clone()).collect();
+    let outputs = map_ordered(&chunks, config.parallelism, |&(_, chunk)| transcribe_fn(chunk));
     let mut results: Vec<String> = Vec::new();
 
-    for (chunk_num, chunk) in AudioChunks::new(samples, config.clone()) {
+    for (&(chunk_num, chunk), output) in chunks.iter().zip(outputs) {
         let chunk_start = chunk_num as f32 * (config.max_chunk_seconds - config.overlap_seconds) as f32;
         let chunk_end = chunk_start + chunk.len() as f32 / config.sample_rate as f32;
 
@@ -418,7 +531,7 @@ where
             chunk.len()
         );
 
-        match transcribe_fn(chunk) {
+        match output {
             Ok(text) => {
                 if !text.is_empty() {
                     debug!("transcribe_chunked: chunk {} -> '{}'", chunk_num, text);
@@ -655,7 +768,7 @@ mod tests {
                 TimedToken { text: ".".to_string(), start: 1.0, end: 1.1 },
             ],
         }];
-        let merged = merge_chunks_timestamped(&chunks, 2.0);
+        let merged = merge_chunks_timestamped(&chunks, 2.0, None);
         assert_eq!(merged, "Hello world.");
     }
 
@@ -680,7 +793,7 @@ mod tests {
                 ],
             },
         ];
-        let merged = merge_chunks_timestamped(&chunks, 2.0);
+        let merged = merge_chunks_timestamped(&chunks, 2.0, None);
         assert_eq!(merged, "Hello world foo bar baz");
     }
 
@@ -700,7 +813,7 @@ mod tests {
                 ],
             },
         ];
-        let merged = merge_chunks_timestamped(&chunks, 0.0);
+        let merged = merge_chunks_timestamped(&chunks, 0.0, None);
         assert_eq!(merged, "Hello world");
     }
 
@@ -732,6 +845,62 @@ mod tests {
         assert_eq!(boundary, 0);
     }
 
+    /// Synthetic "speech" (loud square wave) with silent gaps at the given sample ranges
+    fn speech_with_gaps(len: usize, gaps: &[(usize, usize)]) -> Vec<i16> {
+        let mut samples: Vec<i16> = (0..len).map(|i| if (i / 20) % 2 == 0 { 8000 } else { -8000 }).collect();
+        for &(start, end) in gaps {
+            for s in &mut samples[start..end] {
+                *s = 0;
+            }
+        }
+        samples
+    }
+
+    #[test]
+    fn test_chunk_iterator_snaps_to_silence_gaps() {
+        // 5s of speech with 100ms gaps near (but not at) the 2s fixed boundaries
+        let gaps = [(28800, 30400), (60800, 62400)];
+        let samples = speech_with_gaps(80000, &gaps);
+        let config = ChunkConfig::new(2, 0, 16000);
+
+        let chunks: Vec<_> = AudioChunks::new(&samples, config).collect();
+        assert_eq!(chunks.len(), 3);
+
+        // Each interior boundary should land inside a silence gap
+        let mut end = 0;
+        for (i, (_, chunk)) in chunks.iter().enumerate().take(chunks.len() - 1) {
+            end += chunk.len();
+            let (gap_start, gap_end) = gaps[i];
+            assert!(end >= gap_start && end < gap_end, "boundary {} at {} not in gap", i, end);
+        }
+
+        // All audio is covered
+        let total: usize = chunks.iter().map(|(_, c)| c.len()).sum();
+        assert_eq!(total, samples.len());
+    }
+
+    #[test]
+    fn test_chunk_iterator_falls_back_without_silence() {
+        // Continuous speech: no silence in the search window, keep fixed-size chunks
+        let samples = speech_with_gaps(48000, &[]);
+        let config = ChunkConfig::new(1, 0, 16000);
+
+        let chunks: Vec<_> = AudioChunks::new(&samples, config).collect();
+        assert_eq!(chunks.len(), 3);
+        for (_, chunk) in &chunks {
+            assert_eq!(chunk.len(), 16000);
+        }
+    }
+
+    #[test]
+    fn test_find_silence_split_requires_silence() {
+        let samples = speech_with_gaps(16000, &[(7600, 8400)]);
+        assert!(find_silence_split(&samples, 8000, 4000, 400).is_some());
+
+        let loud = speech_with_gaps(16000, &[]);
+        assert_eq!(find_silence_split(&loud, 8000, 4000, 400), None);
+    }
+
     #[test]
     fn test_chunk_boundaries_vad_short_audio() {
         let config = ChunkConfig::new(30, 2, 16000);
@@ -779,4 +948,62 @@ mod tests {
         assert_eq!(max, i16::MAX);
         assert!(rms > 30000.0);
     }
+
+    #[test]
+    fn test_map_ordered_preserves_order() {
+        let items: Vec<u64> = (0..20).collect();
+        let out = map_ordered(&items, 4, |&i| {
+            // Later items finish first
+            std::thread::sleep(std::time::Duration::from_millis(20 - i));
+            i * 10
+        });
+        assert_eq!(out, (0..20).map(|i| i * 10).collect::<Vec<_>>());
+        assert_eq!(map_ordered(&items, 1, |&i| i), items);
+        assert!(map_ordered(&[] as &[u64], 4, |&i| i).is_empty());
+    }
+
+    /// 1 kHz-ish loud samples whose value encodes the absolute second they belong to
+    fn numbered_seconds(secs: usize, rate: usize) -> Vec<i16> {
+        (0..secs * rate).map(|i| 1000 + (i / rate) as i16).collect()
+    }
+
+    /// Fake recognizer: one word per second of audio, named after that second
+    fn words_for_chunk(chunk: &[i16], rate: usize) -> TimestampedChunkResult {
+        let mut words = Vec::new();
+        for (n, second) in chunk.chunks(rate).enumerate() {
+            words.push(TimedToken {
+                text: format!("w{}", second[0] - 1000),
+                start: n as f32,
+                end: n as f32 + 0.9,
+            });
+        }
+        let text = words.iter().map(|w| w.text.as_str()).collect::<Vec<_>>().join(" ");
+        TimestampedChunkResult { text, words }
+    }
+
+    #[test]
+    fn test_parallel_timestamped_matches_sequential() {
+        let rate = 1000;
+        let samples = numbered_seconds(25, rate);
+        let sequential = ChunkConfig::new(4, 1, rate as u32);
+        let parallel = sequential.clone().with_parallelism(4);
+
+        let expected = transcribe_chunked_with_timestamps(&samples, &sequential, |c| Ok(words_for_chunk(c, rate))).unwrap();
+        let actual = transcribe_chunked_with_timestamps(&samples, &parallel, |c| Ok(words_for_chunk(c, rate))).unwrap();
+        assert!(!expected.is_empty());
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_parallel_text_chunks_match_sequential() {
+        let samples = numbered_seconds(10, 1000);
+        let sequential = ChunkConfig::new(2, 0, 1000);
+        let parallel = sequential.clone().with_parallelism(3);
+        let transcribe = |c: &[i16]| Ok(format!("s{}", c[0] - 1000));
+
+        let expected = transcribe_chunked(&samples, &sequential, transcribe).unwrap();
+        let actual = transcribe_chunked(&samples, &parallel, transcribe).unwrap();
+        assert_eq!(actual, expected);
+        assert_eq!(expected, "s0 s2 s4 s6 s8");
+    }
 }
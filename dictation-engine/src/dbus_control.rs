@@ -1,11 +1,18 @@
 use anyhow::Result;
 use zbus::interface;
+use zbus::object_server::SignalEmitter;
 use std::sync::Arc;
 use std::sync::atomic::Ordering;
-use tokio::sync::{Mutex, watch};
-use tracing::info;
+use std::time::Duration;
+use tokio::sync::{broadcast, Mutex, RwLock, watch};
+use tracing::{info, warn};
 
-use crate::HealthState;
+use crate::debug_audio::{self, SessionDiagnostics};
+use crate::window_detect::AppFilter;
+use crate::{GuiControl, HealthState};
+
+/// Object path the control interface is served at
+const OBJECT_PATH: &str = "/com/voicedictation/Control";
 
 /// Daemon state enum shared between lib.rs and dbus_control.rs
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -30,17 +37,72 @@ pub struct VoiceDictationService {
     command_sender: Arc<Mutex<tokio::sync::mpsc::Sender<DaemonCommand>>>,
     state_receiver: watch::Receiver<DaemonState>,
     health_state: Arc<HealthState>,
+    last_transcription: Arc<RwLock<Option<String>>>,
+    session_id: Arc<RwLock<Option<String>>>,
+    last_diagnostics: Arc<RwLock<Option<SessionDiagnostics>>>,
+    /// app_blocklist/app_allowlist, checked before a start is accepted
+    app_filter: AppFilter,
+    gui_control_tx: broadcast::Sender<GuiControl>,
 }
 
 /// Commands that can be sent from D-Bus to the daemon
 #[derive(Debug, Clone)]
 pub enum DaemonCommand {
     StartRecording,
+    /// Cancel or confirm, depending on `stop_behavior`
     StopRecording,
     Confirm,
+    /// Always discard the current recording
+    Cancel,
     Shutdown,
     /// Switch audio input device. None = system default, Some(name) = specific device.
     SwitchDevice(Option<String>),
+    /// Re-type the last finalized transcription into the focused window
+    RepeatLast,
+}
+
+/// What `StopRecording` does with the current session
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StopBehavior {
+    /// Discard the transcription
+    #[default]
+    Cancel,
+    /// Finalize and type it, like Confirm
+    Confirm,
+}
+
+impl StopBehavior {
+    /// Parse from config string
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "cancel" | "discard" => Some(StopBehavior::Cancel),
+            "confirm" | "finalize" => Some(StopBehavior::Confirm),
+            _ => None,
+        }
+    }
+
+    /// The command a StopRecording stands for under this behavior
+    pub fn resolve(self, command: DaemonCommand) -> DaemonCommand {
+        match (self, command) {
+            (StopBehavior::Cancel, DaemonCommand::StopRecording) => DaemonCommand::Cancel,
+            (StopBehavior::Confirm, DaemonCommand::StopRecording) => DaemonCommand::Confirm,
+            (_, other) => other,
+        }
+    }
+}
+
+/// Double-tap gesture: a second start/toggle press within `window` of the one
+/// that began recording cancels instead of confirming. A zero window disables it.
+///
+/// `voice-dictation toggle` sends Confirm while recording, so both Confirm and
+/// StartRecording count as the second tap.
+pub fn resolve_double_tap(command: DaemonCommand, since_start: Duration, window: Duration) -> DaemonCommand {
+    match command {
+        DaemonCommand::StartRecording | DaemonCommand::Confirm if !window.is_zero() && since_start <= window => {
+            DaemonCommand::Cancel
+        }
+        other => other,
+    }
 }
 
 /// Response from status query
@@ -52,16 +114,26 @@ pub struct StatusInfo {
 
 #[interface(name = "com.voicedictation.Control")]
 impl VoiceDictationService {
-    /// Start a new recording session
+    /// Start a new recording session; refused when the focused app is blocked
     async fn start_recording(&self) -> zbus::fdo::Result<()> {
         info!("D-Bus: StartRecording called");
+        if self.app_filter.is_active() && *self.state_receiver.borrow() == DaemonState::Idle {
+            let target = crate::window_target::WindowTarget::capture().await;
+            let class = target.as_ref().map(|wt| wt.class());
+            if !self.app_filter.allows(class) {
+                let class = class.unwrap_or("unknown");
+                info!("Dictation disabled for focused app '{}', not starting", class);
+                crate::show_refused_start(&self.gui_control_tx).await;
+                return Err(zbus::fdo::Error::Failed(format!("Dictation is disabled for the focused app '{}'", class)));
+            }
+        }
         let sender = self.command_sender.lock().await;
         sender.send(DaemonCommand::StartRecording).await
             .map_err(|e| zbus::fdo::Error::Failed(format!("Failed to send command: {}", e)))?;
         Ok(())
     }
 
-    /// Stop the current recording session (cancel)
+    /// Stop the current recording session (cancels, or confirms with stop_behavior = "confirm")
     async fn stop_recording(&self) -> zbus::fdo::Result<()> {
         info!("D-Bus: StopRecording called");
         let sender = self.command_sender.lock().await;
@@ -70,15 +142,40 @@ impl VoiceDictationService {
         Ok(())
     }
 
+    /// Discard the current recording regardless of stop_behavior
+    async fn cancel(&self) -> zbus::fdo::Result<()> {
+        info!("D-Bus: Cancel called");
+        let sender = self.command_sender.lock().await;
+        sender.send(DaemonCommand::Cancel).await
+            .map_err(|e| zbus::fdo::Error::Failed(format!("Failed to send command: {}", e)))?;
+        Ok(())
+    }
+
     /// Confirm and finalize the current transcription
     async fn confirm(&self) -> zbus::fdo::Result<()> {
         info!("D-Bus: Confirm called");
         let sender = self.command_sender.lock().await;
+        let queued = sender.max_capacity() - sender.capacity();
+        if !confirm_expected(*self.state_receiver.borrow(), queued) {
+            return Err(zbus::fdo::Error::Failed("Nothing to confirm: not recording".to_string()));
+        }
         sender.send(DaemonCommand::Confirm).await
             .map_err(|e| zbus::fdo::Error::Failed(format!("Failed to send command: {}", e)))?;
         Ok(())
     }
 
+    /// Re-type the last finalized transcription without recording again
+    async fn repeat_last(&self) -> zbus::fdo::Result<()> {
+        info!("D-Bus: RepeatLast called");
+        if self.last_transcription.read().await.is_none() {
+            return Err(zbus::fdo::Error::Failed("No previous transcription to repeat".to_string()));
+        }
+        let sender = self.command_sender.lock().await;
+        sender.send(DaemonCommand::RepeatLast).await
+            .map_err(|e| zbus::fdo::Error::Failed(format!("Failed to send command: {}", e)))?;
+        Ok(())
+    }
+
     /// Get current daemon status
     async fn status(&self) -> zbus::fdo::Result<(String, bool)> {
         info!("D-Bus: Status called");
@@ -87,6 +184,35 @@ impl VoiceDictationService {
         Ok((state.to_string(), session_active))
     }
 
+    /// Live state machine state: "idle", "recording" or "processing"
+    async fn get_state(&self) -> zbus::fdo::Result<String> {
+        Ok(self.state_receiver.borrow().to_string())
+    }
+
+    /// Final processed text of each finished session; empty when nothing was
+    /// transcribed or the session was cancelled
+    #[zbus(signal)]
+    async fn transcription_complete(emitter: &SignalEmitter<'_>, text: &str) -> zbus::Result<()>;
+
+    /// ID of the active recording session (empty when idle), matches the `session` log field
+    async fn session_id(&self) -> zbus::fdo::Result<String> {
+        Ok(self.session_id.read().await.clone().unwrap_or_default())
+    }
+
+    /// Write a diagnostic bundle (audio, raw and processed text, device, format) of the
+    /// last finished session and return its directory
+    async fn dump_diagnostics(&self) -> zbus::fdo::Result<String> {
+        info!("D-Bus: DumpDiagnostics called");
+        let Some(diagnostics) = self.last_diagnostics.read().await.clone() else {
+            return Err(zbus::fdo::Error::Failed("No finished session to diagnose yet".to_string()));
+        };
+        let dir = tokio::task::spawn_blocking(move || debug_audio::write_diagnostics(diagnostics))
+            .await
+            .map_err(|e| zbus::fdo::Error::Failed(format!("Diagnostics task failed: {}", e)))?
+            .map_err(|e| zbus::fdo::Error::Failed(format!("Failed to write diagnostics: {}", e)))?;
+        Ok(dir.display().to_string())
+    }
+
     /// Get health status of all subsystems
     async fn health_check(&self) -> zbus::fdo::Result<(String, String, String)> {
         info!("D-Bus: HealthCheck called");
@@ -127,10 +253,26 @@ impl VoiceDictationService {
     }
 }
 
+/// Broadcast the `TranscriptionComplete` signal, logging instead of failing
+pub async fn emit_transcription_complete(connection: &zbus::Connection, text: &str) {
+    let result = match SignalEmitter::new(connection, OBJECT_PATH) {
+        Ok(emitter) => VoiceDictationService::transcription_complete(&emitter, text).await,
+        Err(e) => Err(e),
+    };
+    if let Err(e) = result {
+        warn!("Failed to emit TranscriptionComplete: {}", e);
+    }
+}
+
 /// Create and register D-Bus service
 pub async fn create_dbus_service(
     state_receiver: watch::Receiver<DaemonState>,
     health_state: Arc<HealthState>,
+    last_transcription: Arc<RwLock<Option<String>>>,
+    session_id: Arc<RwLock<Option<String>>>,
+    last_diagnostics: Arc<RwLock<Option<SessionDiagnostics>>>,
+    app_filter: AppFilter,
+    gui_control_tx: broadcast::Sender<GuiControl>,
 ) -> Result<(
     zbus::Connection,
     Arc<Mutex<tokio::sync::mpsc::Sender<DaemonCommand>>>,
@@ -143,11 +285,16 @@ pub async fn create_dbus_service(
         command_sender: Arc::clone(&command_sender),
         state_receiver,
         health_state,
+        last_transcription,
+        session_id,
+        last_diagnostics,
+        app_filter,
+        gui_control_tx,
     };
 
     let connection = zbus::connection::Builder::session()?
         .name("com.voicedictation.Daemon")?
-        .serve_at("/com/voicedictation/Control", service)?
+        .serve_at(OBJECT_PATH, service)?
         .build()
         .await?;
 
@@ -155,3 +302,50 @@ pub async fn create_dbus_service(
 
     Ok((connection, command_sender, command_rx))
 }
+
+/// Whether a Confirm can apply to anything: a session is active, or queued commands
+/// (e.g. a StartRecording sent just before) may start one first
+fn confirm_expected(state: DaemonState, queued_commands: usize) -> bool {
+    state != DaemonState::Idle || queued_commands > 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stop_behavior_from_str() {
+        assert_eq!(StopBehavior::from_str("Confirm"), Some(StopBehavior::Confirm));
+        assert_eq!(StopBehavior::from_str("cancel"), Some(StopBehavior::Cancel));
+        assert_eq!(StopBehavior::from_str("pause"), None);
+    }
+
+    #[test]
+    fn test_stop_behavior_resolves_only_stop() {
+        assert!(matches!(StopBehavior::Cancel.resolve(DaemonCommand::StopRecording), DaemonCommand::Cancel));
+        assert!(matches!(StopBehavior::Confirm.resolve(DaemonCommand::StopRecording), DaemonCommand::Confirm));
+        assert!(matches!(StopBehavior::Confirm.resolve(DaemonCommand::Cancel), DaemonCommand::Cancel));
+        assert!(matches!(StopBehavior::Cancel.resolve(DaemonCommand::Confirm), DaemonCommand::Confirm));
+    }
+
+    #[test]
+    fn test_double_tap_cancels_within_window() {
+        let window = Duration::from_millis(400);
+        let quick = Duration::from_millis(250);
+        let slow = Duration::from_millis(900);
+        assert!(matches!(resolve_double_tap(DaemonCommand::Confirm, quick, window), DaemonCommand::Cancel));
+        assert!(matches!(resolve_double_tap(DaemonCommand::StartRecording, quick, window), DaemonCommand::Cancel));
+        assert!(matches!(resolve_double_tap(DaemonCommand::Confirm, slow, window), DaemonCommand::Confirm));
+        assert!(matches!(resolve_double_tap(DaemonCommand::Shutdown, quick, window), DaemonCommand::Shutdown));
+        // Disabled
+        assert!(matches!(resolve_double_tap(DaemonCommand::Confirm, quick, Duration::ZERO), DaemonCommand::Confirm));
+    }
+
+    #[test]
+    fn test_confirm_rejected_only_when_idle_with_nothing_queued() {
+        assert!(!confirm_expected(DaemonState::Idle, 0));
+        assert!(confirm_expected(DaemonState::Idle, 1));
+        assert!(confirm_expected(DaemonState::Recording, 0));
+        assert!(confirm_expected(DaemonState::Processing, 0));
+    }
+}
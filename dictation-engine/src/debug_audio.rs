@@ -1,20 +1,40 @@
 //! Debug audio preservation
 //!
-//! Saves audio recordings with metadata when debug mode is enabled.
+//! Saves audio recordings with metadata when debug mode is enabled, and writes
+//! on-demand diagnostic bundles of the last session.
 
 use anyhow::Result;
 use chrono::{DateTime, Utc};
 use hound::{SampleFormat, WavSpec, WavWriter};
 use serde::Serialize;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::sync::RwLock;
 use tracing::{debug, info, warn};
 
+use crate::engine::TranscriptionEngine;
+
 /// Debug directory for audio files
 const DEBUG_DIR: &str = "/tmp/voice-dictation-debug";
 
-/// Maximum number of debug files to keep
-const MAX_DEBUG_FILES: usize = 50;
+/// Default number of debug recordings to keep
+pub const MAX_DEBUG_FILES: usize = 50;
+
+/// Rotation policy for saved session recordings
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Retention {
+    /// Most recordings kept
+    pub max_files: usize,
+    /// Most total WAV bytes kept (0 = no limit)
+    pub max_bytes: u64,
+}
+
+impl Default for Retention {
+    fn default() -> Self {
+        Self { max_files: MAX_DEBUG_FILES, max_bytes: 0 }
+    }
+}
 
 /// Metadata for a debug audio recording
 #[derive(Debug, Serialize)]
@@ -32,6 +52,102 @@ pub struct AudioMetadata {
     pub same_model_used: bool,
 }
 
+/// Most session audio kept for a diagnostic bundle (the tail is kept)
+pub const MAX_DIAGNOSTIC_AUDIO_SECS: usize = 120;
+
+/// Snapshot of the last finished session for `write_diagnostics`
+#[derive(Clone, Serialize)]
+pub struct SessionDiagnostics {
+    pub timestamp: DateTime<Utc>,
+    pub session_id: String,
+    /// Engine still holding the session audio, which is only copied out when a
+    /// bundle is written. None once the next session or an idle unload clears it.
+    #[serde(skip)]
+    pub engine: Option<Arc<dyn TranscriptionEngine>>,
+    /// Whether `audio.wav` was written alongside the report
+    pub audio_included: bool,
+    /// Seconds of audio dropped from the start to respect `MAX_DIAGNOSTIC_AUDIO_SECS`
+    pub audio_truncated_secs: f32,
+    pub sample_rate: u32,
+    pub capture_sample_rate: u32,
+    pub sample_format: String,
+    pub audio_backend: String,
+    pub audio_device: String,
+    /// Live preview text before the final pass
+    pub preview_text: String,
+    /// Final pass output before post-processing
+    pub accurate_text: String,
+    /// After the post-processing pipeline
+    pub processed_text: String,
+    /// After app sanitization (what was typed)
+    pub typed_text: String,
+}
+
+impl SessionDiagnostics {
+    /// Keep at most `MAX_DIAGNOSTIC_AUDIO_SECS` of `audio`, dropping the oldest samples
+    pub fn limit_audio(mut audio: Vec<i16>, sample_rate: u32) -> (Vec<i16>, f32) {
+        let max = MAX_DIAGNOSTIC_AUDIO_SECS * sample_rate as usize;
+        if audio.len() <= max {
+            return (audio, 0.0);
+        }
+        let excess = audio.len() - max;
+        audio.drain(..excess);
+        (audio, excess as f32 / sample_rate as f32)
+    }
+}
+
+/// Stop holding the engine for the last session's audio, once the engine is
+/// about to be cleared for other audio or released to free memory
+pub async fn release_session_audio(last: &RwLock<Option<SessionDiagnostics>>) {
+    if let Some(diagnostics) = last.write().await.as_mut() {
+        diagnostics.engine = None;
+    }
+}
+
+/// Write `diagnostics` as `audio.wav` and `report.json` into a new timestamped
+/// directory under the debug directory, returning the directory.
+///
+/// The audio is left out when the engine no longer holds the session.
+pub fn write_diagnostics(mut diagnostics: SessionDiagnostics) -> Result<PathBuf> {
+    let dir = PathBuf::from(DEBUG_DIR).join(format!(
+        "diag_{}",
+        Utc::now().format("%Y%m%d_%H%M%S%.3f")
+    ));
+    fs::create_dir_all(&dir)?;
+
+    match diagnostics.engine.take() {
+        Some(engine) => {
+            let (audio, truncated_secs) =
+                SessionDiagnostics::limit_audio(engine.get_audio_buffer(), diagnostics.sample_rate);
+            write_wav(&dir.join("audio.wav"), &audio, diagnostics.sample_rate)?;
+            diagnostics.audio_included = true;
+            diagnostics.audio_truncated_secs = truncated_secs;
+        }
+        None => warn!("Session audio was already cleared, writing the report without it"),
+    }
+    fs::write(dir.join("report.json"), serde_json::to_string_pretty(&diagnostics)?)?;
+
+    info!("Diagnostics written to {}", dir.display());
+    Ok(dir)
+}
+
+/// Write mono 16-bit samples to a WAV file
+fn write_wav(path: &Path, samples: &[i16], sample_rate: u32) -> Result<()> {
+    let spec = WavSpec {
+        channels: 1,
+        sample_rate,
+        bits_per_sample: 16,
+        sample_format: SampleFormat::Int,
+    };
+
+    let mut writer = WavWriter::create(path, spec)?;
+    for &sample in samples {
+        writer.write_sample(sample)?;
+    }
+    writer.finalize()?;
+    Ok(())
+}
+
 /// Check if debug audio is enabled via environment or config
 pub fn is_debug_audio_enabled() -> bool {
     // Check RUST_LOG for debug level
@@ -47,11 +163,13 @@ pub fn is_debug_audio_enabled() -> bool {
         .unwrap_or(false)
 }
 
-/// Save audio buffer and metadata to debug directory
+/// Save audio buffer and metadata to debug directory, then prune older
+/// recordings beyond `retention`
 pub fn save_debug_audio(
     audio_buffer: &[i16],
     sample_rate: u32,
     metadata: AudioMetadata,
+    retention: Retention,
 ) -> Result<PathBuf> {
     // Ensure debug directory exists
     let debug_dir = PathBuf::from(DEBUG_DIR);
@@ -65,18 +183,7 @@ pub fn save_debug_audio(
     let json_path = debug_dir.join(format!("{}.json", base_name));
 
     // Write WAV file
-    let spec = WavSpec {
-        channels: 1,
-        sample_rate,
-        bits_per_sample: 16,
-        sample_format: SampleFormat::Int,
-    };
-
-    let mut writer = WavWriter::create(&wav_path, spec)?;
-    for &sample in audio_buffer {
-        writer.write_sample(sample)?;
-    }
-    writer.finalize()?;
+    write_wav(&wav_path, audio_buffer, sample_rate)?;
 
     // Write metadata JSON
     let json_content = serde_json::to_string_pretty(&metadata)?;
@@ -89,51 +196,126 @@ pub fn save_debug_audio(
         audio_buffer.len()
     );
 
-    // Cleanup old files
-    cleanup_old_files(&debug_dir)?;
+    let removed = prune_recordings(&debug_dir, retention)?;
+    if removed > 0 {
+        debug!("Pruned {} old debug recordings", removed);
+    }
 
     Ok(wav_path)
 }
 
-/// Remove old debug files, keeping only the most recent MAX_DEBUG_FILES
-fn cleanup_old_files(debug_dir: &PathBuf) -> Result<()> {
-    let mut wav_files: Vec<_> = fs::read_dir(debug_dir)?
+/// Remove the oldest recordings (WAV and its JSON) beyond `retention`.
+///
+/// The newest recording is always kept, even if it alone exceeds the size cap.
+/// Returns the number of recordings removed.
+fn prune_recordings(debug_dir: &Path, retention: Retention) -> Result<usize> {
+    let mut recordings: Vec<_> = fs::read_dir(debug_dir)?
         .filter_map(|e| e.ok())
-        .filter(|e| {
-            e.path()
-                .extension()
-                .map(|ext| ext == "wav")
-                .unwrap_or(false)
+        .filter(|e| e.path().extension().map(|ext| ext == "wav").unwrap_or(false))
+        .filter_map(|e| {
+            let metadata = e.metadata().ok()?;
+            Some((metadata.modified().ok(), e.path(), metadata.len()))
         })
         .collect();
 
-    if wav_files.len() <= MAX_DEBUG_FILES {
-        return Ok(());
-    }
-
-    // Sort by modification time (oldest first)
-    wav_files.sort_by(|a, b| {
-        let a_time = a.metadata().and_then(|m| m.modified()).ok();
-        let b_time = b.metadata().and_then(|m| m.modified()).ok();
-        a_time.cmp(&b_time)
-    });
+    // Oldest first; the timestamped names break ties between equal mtimes
+    recordings.sort();
 
-    // Remove oldest files
-    let to_remove = wav_files.len() - MAX_DEBUG_FILES;
-    for entry in wav_files.into_iter().take(to_remove) {
-        let wav_path = entry.path();
-        let json_path = wav_path.with_extension("json");
+    let mut remaining = recordings.len();
+    let mut total_bytes: u64 = recordings.iter().map(|(_, _, size)| size).sum();
+    let mut removed = 0;
+    for (_, wav_path, size) in recordings {
+        let over_count = remaining > retention.max_files;
+        let over_size = retention.max_bytes > 0 && total_bytes > retention.max_bytes;
+        if remaining <= 1 || !(over_count || over_size) {
+            break;
+        }
 
         if let Err(e) = fs::remove_file(&wav_path) {
             warn!("Failed to remove old debug WAV: {}", e);
-        } else {
-            debug!("Removed old debug file: {}", wav_path.display());
+            continue;
+        }
+        debug!("Removed old debug file: {}", wav_path.display());
+        let _ = fs::remove_file(wav_path.with_extension("json"));
+
+        remaining -= 1;
+        total_bytes -= size;
+        removed += 1;
+    }
+
+    Ok(removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_limit_audio_keeps_short_sessions() {
+        let (audio, dropped) = SessionDiagnostics::limit_audio(vec![1; 16000], 16000);
+        assert_eq!(audio.len(), 16000);
+        assert_eq!(dropped, 0.0);
+    }
+
+    fn write_recording(dir: &Path, name: &str, bytes: usize) {
+        fs::write(dir.join(format!("{}.wav", name)), vec![0u8; bytes]).unwrap();
+        fs::write(dir.join(format!("{}.json", name)), "{}").unwrap();
+    }
+
+    fn wav_names(dir: &Path) -> Vec<String> {
+        let mut names: Vec<String> = fs::read_dir(dir)
+            .unwrap()
+            .filter_map(|e| e.ok())
+            .map(|e| e.file_name().to_string_lossy().into_owned())
+            .filter(|name| name.ends_with(".wav"))
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn test_prune_keeps_newest_by_count() {
+        let dir = tempfile::tempdir().unwrap();
+        for i in 1..=5 {
+            write_recording(dir.path(), &format!("recording_{}", i), 10);
         }
 
-        if json_path.exists() {
-            let _ = fs::remove_file(&json_path);
+        let removed = prune_recordings(dir.path(), Retention { max_files: 3, max_bytes: 0 }).unwrap();
+        assert_eq!(removed, 2);
+        assert_eq!(wav_names(dir.path()), vec!["recording_3.wav", "recording_4.wav", "recording_5.wav"]);
+        // Metadata goes with its recording
+        assert!(!dir.path().join("recording_1.json").exists());
+        assert!(dir.path().join("recording_3.json").exists());
+    }
+
+    #[test]
+    fn test_prune_by_total_size() {
+        let dir = tempfile::tempdir().unwrap();
+        for i in 1..=4 {
+            write_recording(dir.path(), &format!("recording_{}", i), 100);
         }
+
+        prune_recordings(dir.path(), Retention { max_files: 10, max_bytes: 250 }).unwrap();
+        assert_eq!(wav_names(dir.path()), vec!["recording_3.wav", "recording_4.wav"]);
     }
 
-    Ok(())
+    #[test]
+    fn test_prune_always_keeps_latest() {
+        let dir = tempfile::tempdir().unwrap();
+        write_recording(dir.path(), "recording_1", 1000);
+
+        prune_recordings(dir.path(), Retention { max_files: 10, max_bytes: 10 }).unwrap();
+        assert_eq!(wav_names(dir.path()), vec!["recording_1.wav"]);
+    }
+
+    #[test]
+    fn test_limit_audio_keeps_tail() {
+        let rate = 100;
+        let mut audio = vec![0i16; 10 * rate as usize];
+        audio.extend(vec![7i16; MAX_DIAGNOSTIC_AUDIO_SECS * rate as usize]);
+        let (limited, dropped) = SessionDiagnostics::limit_audio(audio, rate);
+        assert_eq!(limited.len(), MAX_DIAGNOSTIC_AUDIO_SECS * rate as usize);
+        assert!(limited.iter().all(|&s| s == 7));
+        assert_eq!(dropped, 10.0);
+    }
 }
@@ -38,6 +38,30 @@ pub trait TranscriptionEngine: Send + Sync {
     /// * Final transcription from the preview/fast model
     fn get_final_result(&self) -> Result<String>;
 
+    /// Get the final transcription result with the model's confidence in it.
+    ///
+    /// Confidence ranges 0.0-1.0 (e.g. the mean token probability). Engines
+    /// that don't score their output return None, which is never discarded
+    /// as low-confidence.
+    fn get_final_result_with_confidence(&self) -> Result<(String, Option<f32>)> {
+        Ok((self.get_final_result()?, None))
+    }
+
+    /// Ask a running final pass to stop early; its result is no longer wanted.
+    ///
+    /// Engines that transcribe in chunks stop before the next chunk and return
+    /// an error. Cleared by `reset` and `clear_buffer`. Engines that can't
+    /// stop ignore it (the default).
+    fn cancel_final_pass(&self) {}
+
+    /// Whether the cached preview text was transcribed from the whole session.
+    ///
+    /// False when the preview only covers recent audio (a preview window), so
+    /// the cached text must not stand in for the final result.
+    fn cached_text_covers_session(&self) -> bool {
+        true
+    }
+
     /// Get the cached preview text without re-transcribing.
     ///
     /// In single-model mode, the preview already has a recent full-buffer
@@ -61,4 +85,64 @@ pub trait TranscriptionEngine: Send + Sync {
     /// Clears the audio buffer and any accumulated transcription state.
     /// Called between recording sessions when reusing the same engine.
     fn reset(&self);
+
+    /// Clear the session audio, cached text and transcription progress without
+    /// touching the loaded model, so the engine can be reused for the next session.
+    ///
+    /// Unlike `reset`, failures (e.g. a poisoned lock) are reported so the caller
+    /// can recreate the engine instead of recording into stale state.
+    fn clear_buffer(&self) -> Result<()> {
+        self.reset();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    /// Engine that "recognizes" one word per 100 buffered samples
+    #[derive(Default)]
+    struct CountingEngine {
+        audio: Mutex<Vec<i16>>,
+    }
+
+    impl TranscriptionEngine for CountingEngine {
+        fn process_audio(&self, samples: &[i16]) -> Result<()> {
+            self.audio.lock().unwrap().extend_from_slice(samples);
+            Ok(())
+        }
+
+        fn get_current_text(&self) -> Result<String> {
+            Ok(vec!["word"; self.audio.lock().unwrap().len() / 100].join(" "))
+        }
+
+        fn get_final_result(&self) -> Result<String> {
+            self.get_current_text()
+        }
+
+        fn get_cached_text(&self) -> String {
+            self.get_current_text().unwrap_or_default()
+        }
+
+        fn get_audio_buffer(&self) -> Vec<i16> {
+            self.audio.lock().unwrap().clone()
+        }
+
+        fn reset(&self) {
+            self.audio.lock().unwrap().clear();
+        }
+    }
+
+    #[test]
+    fn test_clear_buffer_empties_session() {
+        let engine = CountingEngine::default();
+        engine.process_audio(&[0; 300]).unwrap();
+        assert_eq!(engine.get_current_text().unwrap(), "word word word");
+
+        engine.clear_buffer().unwrap();
+        assert_eq!(engine.get_current_text().unwrap(), "");
+        assert!(engine.get_audio_buffer().is_empty());
+    }
 }
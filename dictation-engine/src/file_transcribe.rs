@@ -0,0 +1,147 @@
+//! Transcribing a recorded WAV file without the daemon.
+//!
+//! Loads the configured model and post-processing pipeline the same way the
+//! daemon's final pass does, so a saved recording can be replayed through
+//! them while tuning settings. There is no focused window, so app profiles,
+//! sanitization and spoken submit/toggle commands don't apply.
+
+use crate::parakeet_engine::PARAKEET_SAMPLE_RATE;
+use crate::post_processing::{self, AcronymProcessor, CommandVocabulary, Pipeline, ProcessorKind, UnicodeForm};
+use crate::resample::resample;
+use crate::user_dictionary::UserDictionary;
+use crate::window_detect::AppFilter;
+use anyhow::{bail, Context, Result};
+use hound::{SampleFormat, WavReader};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tracing::warn;
+
+/// Engine output and the text the daemon would type
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileTranscript {
+    pub raw: String,
+    pub processed: String,
+}
+
+/// Transcribe `path` with the configured model and post-processing
+pub fn transcribe_wav(path: &Path) -> Result<FileTranscript> {
+    let samples = read_wav(path)?;
+
+    let config = crate::load_config().unwrap_or_else(|e| {
+        warn!("Failed to load config: {}, using defaults", e);
+        crate::default_config()
+    });
+    let daemon = &config.daemon;
+
+    let model_spec = crate::model_spec_from_config(daemon)?;
+    if !model_spec.is_available() {
+        bail!(
+            "Model '{}' not found at {:?}. Run `voice-dictation download-model` first.",
+            daemon.model,
+            model_spec.model_path()
+        );
+    }
+    let engine = model_spec.create_engine(PARAKEET_SAMPLE_RATE, daemon.max_audio_buffer_seconds, None)?;
+    engine.process_audio(&samples)?;
+    let raw = engine.get_final_result()?;
+
+    let dictionary_files: Vec<PathBuf> =
+        daemon.dictionary_files.iter().map(|path| PathBuf::from(shellexpand::tilde(path).as_ref())).collect();
+    let user_dict = Arc::new(
+        UserDictionary::new().unwrap_or_else(|_| UserDictionary::empty()).with_extra_files(dictionary_files),
+    );
+    let markdown_filter = AppFilter::new(&[], &daemon.markdown_apps);
+    let pipeline = Pipeline::from_order_with_context(
+        &post_processing::resolve_order(&daemon.post_processing_order),
+        |kind| match kind {
+            ProcessorKind::Markdown => daemon.processor_enabled(kind) && markdown_filter.allows(None),
+            _ => daemon.processor_enabled(kind),
+        },
+        Some(user_dict),
+        None,
+        Some(Arc::new(CommandVocabulary::new(&daemon.punctuation_commands))),
+        Some(AcronymProcessor::from_user_config()),
+    );
+    let unicode_form = UnicodeForm::from_str(&daemon.unicode_normalization).unwrap_or(UnicodeForm::Nfc);
+    let processed = unicode_form.apply(&pipeline.process(&raw)?);
+
+    Ok(FileTranscript { raw, processed })
+}
+
+/// Read a 16-bit PCM or 32-bit float WAV as mono samples at the engine rate
+fn read_wav(path: &Path) -> Result<Vec<i16>> {
+    let mut reader = WavReader::open(path).with_context(|| format!("Failed to open WAV file {}", path.display()))?;
+    let spec = reader.spec();
+
+    let interleaved: Vec<i16> = match (spec.sample_format, spec.bits_per_sample) {
+        (SampleFormat::Int, 16) => reader.samples::<i16>().collect::<Result<_, _>>()?,
+        (SampleFormat::Float, 32) => reader
+            .samples::<f32>()
+            .map(|s| s.map(|s| (s.clamp(-1.0, 1.0) * i16::MAX as f32) as i16))
+            .collect::<Result<_, _>>()?,
+        (format, bits) => bail!(
+            "Unsupported WAV format in {}: {}-bit {}. Convert it to 16-bit PCM, e.g. \
+             `ffmpeg -i input.wav -c:a pcm_s16le output.wav`",
+            path.display(),
+            bits,
+            if format == SampleFormat::Float { "float" } else { "integer" }
+        ),
+    };
+
+    let channels = spec.channels.max(1) as usize;
+    let mono: Vec<i16> = interleaved
+        .chunks(channels)
+        .map(|frame| (frame.iter().map(|&s| s as i32).sum::<i32>() / frame.len() as i32) as i16)
+        .collect();
+
+    Ok(resample(&mono, spec.sample_rate, PARAKEET_SAMPLE_RATE))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hound::{WavSpec, WavWriter};
+
+    fn write_wav(path: &Path, spec: WavSpec, frames: usize) {
+        let mut writer = WavWriter::create(path, spec).unwrap();
+        for i in 0..frames * spec.channels as usize {
+            match spec.sample_format {
+                SampleFormat::Float => writer.write_sample(if i % 2 == 0 { 0.5f32 } else { -0.5 }).unwrap(),
+                SampleFormat::Int => writer.write_sample(if i % 2 == 0 { 1000i32 } else { 3000 }).unwrap(),
+            }
+        }
+        writer.finalize().unwrap();
+    }
+
+    #[test]
+    fn test_stereo_downmixed_and_resampled() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("stereo.wav");
+        let spec = WavSpec { channels: 2, sample_rate: 48000, bits_per_sample: 16, sample_format: SampleFormat::Int };
+        write_wav(&path, spec, 48000);
+
+        let samples = read_wav(&path).unwrap();
+        assert!((samples.len() as i64 - 16000).abs() <= 1, "{}", samples.len());
+        // Left 1000 and right 3000 average to 2000
+        assert!(samples.iter().all(|&s| s == 2000));
+    }
+
+    #[test]
+    fn test_float_wav_scaled() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("float.wav");
+        let spec = WavSpec { channels: 1, sample_rate: 16000, bits_per_sample: 32, sample_format: SampleFormat::Float };
+        write_wav(&path, spec, 4);
+        assert_eq!(read_wav(&path).unwrap(), [16383, -16383, 16383, -16383]);
+    }
+
+    #[test]
+    fn test_unsupported_format_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("24bit.wav");
+        let spec = WavSpec { channels: 1, sample_rate: 16000, bits_per_sample: 24, sample_format: SampleFormat::Int };
+        write_wav(&path, spec, 4);
+        let err = read_wav(&path).unwrap_err().to_string();
+        assert!(err.contains("24-bit integer"), "{}", err);
+    }
+}
@@ -0,0 +1,286 @@
+//! Ordered fallback chain for the final transcription pass.
+//!
+//! Each stage is tried in turn and the first non-empty result is used, so a
+//! failing or empty final pass doesn't lose the dictation. Stages that need a
+//! new engine create it only when reached.
+
+use anyhow::Result;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tracing::{info, warn};
+
+use crate::engine::TranscriptionEngine;
+
+/// One stage of the final-pass fallback chain
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FinalPassStage {
+    /// Full-buffer pass on the session engine
+    Final,
+    /// Fresh engine instance fed the session audio (recovers from a bad engine state)
+    Reload,
+    /// Cached live-preview text
+    Preview,
+}
+
+impl FinalPassStage {
+    /// Parse from config string
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "final" | "session" => Some(FinalPassStage::Final),
+            "reload" | "fresh" => Some(FinalPassStage::Reload),
+            "preview" | "cached" => Some(FinalPassStage::Preview),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            FinalPassStage::Final => "final",
+            FinalPassStage::Reload => "reload",
+            FinalPassStage::Preview => "preview",
+        }
+    }
+}
+
+/// Default chain: the final pass, falling back to the preview text
+pub const DEFAULT_CHAIN: &[FinalPassStage] = &[FinalPassStage::Final, FinalPassStage::Preview];
+
+/// Parse the configured chain, skipping unknown or repeated stages.
+///
+/// An empty (or entirely invalid) list uses `DEFAULT_CHAIN`.
+pub fn parse_chain(names: &[String]) -> Vec<FinalPassStage> {
+    let mut chain = Vec::new();
+    for name in names {
+        match FinalPassStage::from_str(name) {
+            Some(stage) if !chain.contains(&stage) => chain.push(stage),
+            Some(_) => warn!("Final pass stage '{}' listed twice, ignoring repeat", name),
+            None => warn!("Unknown final pass stage '{}', ignoring", name),
+        }
+    }
+    if chain.is_empty() {
+        chain = DEFAULT_CHAIN.to_vec();
+    }
+    chain
+}
+
+/// `chain` with the Reload stage moved to the front, for when the final pass
+/// should run on a different model than the session engine
+pub fn reload_first(chain: &[FinalPassStage]) -> Vec<FinalPassStage> {
+    std::iter::once(FinalPassStage::Reload)
+        .chain(chain.iter().copied().filter(|&stage| stage != FinalPassStage::Reload))
+        .collect()
+}
+
+/// Run `chain` against the session engine and return the first non-empty result
+/// with the stage that produced it and the engine's confidence in it.
+///
+/// `create_engine` is only called if the Reload stage is reached. Returns None
+/// when every stage failed or came back empty, or once `cancelled` is set
+/// (checked before each stage, so an abandoned pass never loads another model).
+pub fn run_chain(
+    chain: &[FinalPassStage],
+    session_engine: &dyn TranscriptionEngine,
+    create_engine: impl FnOnce() -> Result<Arc<dyn TranscriptionEngine>>,
+    cancelled: &AtomicBool,
+) -> Option<(String, FinalPassStage, Option<f32>)> {
+    let mut create_engine = Some(create_engine);
+
+    for &stage in chain {
+        if cancelled.load(Ordering::Relaxed) {
+            info!("Final pass cancelled before the '{}' stage", stage.name());
+            return None;
+        }
+        let result = match stage {
+            FinalPassStage::Final => session_engine.get_final_result_with_confidence(),
+            FinalPassStage::Preview if !session_engine.cached_text_covers_session() => {
+                info!("Preview text covers only recent audio, skipping the preview stage");
+                continue;
+            }
+            FinalPassStage::Preview => Ok((session_engine.get_cached_text(), None)),
+            FinalPassStage::Reload => match create_engine.take() {
+                Some(create) => create().and_then(|engine| {
+                    engine.process_audio(&session_engine.get_audio_buffer())?;
+                    engine.get_final_result_with_confidence()
+                }),
+                None => continue,
+            },
+        };
+
+        match result {
+            Ok((text, confidence)) if !text.trim().is_empty() => {
+                if stage == FinalPassStage::Preview && stage != chain[0] {
+                    warn!("Final pass produced no text, using the live preview result instead");
+                } else {
+                    info!("Final text produced by '{}' stage", stage.name());
+                }
+                return Some((text, stage, confidence));
+            }
+            Ok(_) => info!("Final pass stage '{}' returned no text, trying next", stage.name()),
+            Err(e) => warn!("Final pass stage '{}' failed: {}, trying next", stage.name(), e),
+        }
+    }
+    None
+}
+
+/// Whether a result scored below `min_confidence` (0 = off) and should be
+/// discarded. Results without a score always pass.
+pub fn below_confidence(confidence: Option<f32>, min_confidence: f32) -> bool {
+    min_confidence > 0.0 && confidence.is_some_and(|confidence| confidence < min_confidence)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::anyhow;
+    use std::sync::Mutex;
+
+    struct MockEngine {
+        final_result: Option<&'static str>,
+        cached: &'static str,
+        confidence: Option<f32>,
+        windowed: bool,
+        audio: Mutex<Vec<i16>>,
+    }
+
+    impl MockEngine {
+        fn new(final_result: Option<&'static str>, cached: &'static str) -> Self {
+            Self { final_result, cached, confidence: None, windowed: false, audio: Mutex::new(vec![1, 2, 3]) }
+        }
+
+        fn scored(final_result: &'static str, confidence: f32) -> Self {
+            Self { confidence: Some(confidence), ..Self::new(Some(final_result), "") }
+        }
+    }
+
+    impl TranscriptionEngine for MockEngine {
+        fn process_audio(&self, samples: &[i16]) -> Result<()> {
+            self.audio.lock().unwrap().extend_from_slice(samples);
+            Ok(())
+        }
+        fn get_current_text(&self) -> Result<String> {
+            Ok(self.cached.to_string())
+        }
+        fn get_final_result(&self) -> Result<String> {
+            self.final_result.map(str::to_string).ok_or_else(|| anyhow!("inference failed"))
+        }
+        fn get_final_result_with_confidence(&self) -> Result<(String, Option<f32>)> {
+            Ok((self.get_final_result()?, self.confidence))
+        }
+        fn cached_text_covers_session(&self) -> bool {
+            !self.windowed
+        }
+        fn get_cached_text(&self) -> String {
+            self.cached.to_string()
+        }
+        fn get_audio_buffer(&self) -> Vec<i16> {
+            self.audio.lock().unwrap().clone()
+        }
+        fn reset(&self) {
+            self.audio.lock().unwrap().clear();
+        }
+    }
+
+    static RUNNING: AtomicBool = AtomicBool::new(false);
+
+    fn no_engine() -> Result<Arc<dyn TranscriptionEngine>> {
+        panic!("engine created for a chain that didn't reach the reload stage")
+    }
+
+    #[test]
+    fn test_parse_chain() {
+        let names = vec!["Final".to_string(), "bogus".to_string(), "reload".to_string(), "final".to_string()];
+        assert_eq!(parse_chain(&names), vec![FinalPassStage::Final, FinalPassStage::Reload]);
+        assert_eq!(parse_chain(&[]), DEFAULT_CHAIN.to_vec());
+        assert_eq!(parse_chain(&["bogus".to_string()]), DEFAULT_CHAIN.to_vec());
+    }
+
+    #[test]
+    fn test_reload_first() {
+        let chain = [FinalPassStage::Final, FinalPassStage::Reload, FinalPassStage::Preview];
+        assert_eq!(
+            reload_first(&chain),
+            vec![FinalPassStage::Reload, FinalPassStage::Final, FinalPassStage::Preview]
+        );
+        assert_eq!(reload_first(&[FinalPassStage::Preview]), vec![FinalPassStage::Reload, FinalPassStage::Preview]);
+    }
+
+    #[test]
+    fn test_first_stage_wins_without_loading() {
+        let engine = MockEngine::new(Some("hello world"), "hello");
+        let chain = [FinalPassStage::Final, FinalPassStage::Reload];
+        assert_eq!(run_chain(&chain, &engine, no_engine, &RUNNING), Some(("hello world".to_string(), FinalPassStage::Final, None)));
+    }
+
+    #[test]
+    fn test_error_falls_through_to_preview() {
+        let engine = MockEngine::new(None, "hello");
+        assert_eq!(run_chain(DEFAULT_CHAIN, &engine, no_engine, &RUNNING), Some(("hello".to_string(), FinalPassStage::Preview, None)));
+    }
+
+    #[test]
+    fn test_empty_result_falls_through() {
+        let engine = MockEngine::new(Some("  "), "hello");
+        assert_eq!(run_chain(DEFAULT_CHAIN, &engine, no_engine, &RUNNING), Some(("hello".to_string(), FinalPassStage::Preview, None)));
+    }
+
+    #[test]
+    fn test_reload_feeds_session_audio() {
+        let engine = MockEngine::new(None, "");
+        let created = AtomicBool::new(false);
+        let chain = [FinalPassStage::Final, FinalPassStage::Reload];
+        let result = run_chain(&chain, &engine, || {
+            created.store(true, Ordering::Relaxed);
+            let fresh = MockEngine::new(Some("recovered"), "");
+            fresh.reset();
+            Ok(Arc::new(fresh) as Arc<dyn TranscriptionEngine>)
+        }, &RUNNING);
+        assert!(created.load(Ordering::Relaxed));
+        assert_eq!(result, Some(("recovered".to_string(), FinalPassStage::Reload, None)));
+    }
+
+    #[test]
+    fn test_all_stages_empty() {
+        let engine = MockEngine::new(Some(""), "");
+        let chain = [FinalPassStage::Final, FinalPassStage::Reload, FinalPassStage::Preview];
+        let result = run_chain(&chain, &engine, || Err(anyhow!("model missing")), &RUNNING);
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn test_low_confidence_discarded() {
+        // A cough transcribed as a stray word scores low
+        let engine = MockEngine::scored("uh", 0.2);
+        let (_, _, confidence) = run_chain(DEFAULT_CHAIN, &engine, no_engine, &RUNNING).unwrap();
+        assert_eq!(confidence, Some(0.2));
+        assert!(below_confidence(confidence, 0.5));
+        assert!(!below_confidence(confidence, 0.0));
+
+        let engine = MockEngine::scored("hello world", 0.9);
+        let (_, _, confidence) = run_chain(DEFAULT_CHAIN, &engine, no_engine, &RUNNING).unwrap();
+        assert!(!below_confidence(confidence, 0.5));
+    }
+
+    #[test]
+    fn test_unscored_result_kept() {
+        let engine = MockEngine::new(None, "hello");
+        let (_, stage, confidence) = run_chain(DEFAULT_CHAIN, &engine, no_engine, &RUNNING).unwrap();
+        assert_eq!(stage, FinalPassStage::Preview);
+        assert!(!below_confidence(confidence, 0.9));
+    }
+
+    #[test]
+    fn test_windowed_preview_never_final() {
+        // The preview only transcribed the last seconds of a long session
+        let engine = MockEngine { windowed: true, ..MockEngine::new(None, "end of the sentence") };
+        assert_eq!(run_chain(DEFAULT_CHAIN, &engine, no_engine, &RUNNING), None);
+    }
+
+    #[test]
+    fn test_cancelled_pass_skips_reload() {
+        // engine_timeout_ms gave up on the pass: no further stage runs, so no model is loaded
+        let engine = MockEngine::new(None, "hello");
+        let cancelled = AtomicBool::new(true);
+        let chain = [FinalPassStage::Final, FinalPassStage::Reload, FinalPassStage::Preview];
+        assert_eq!(run_chain(&chain, &engine, no_engine, &cancelled), None);
+    }
+}
@@ -0,0 +1,167 @@
+//! Input gain for quiet microphones.
+//!
+//! Session audio is multiplied by `input_gain` before it reaches the spectrum,
+//! engine and VAD. Samples that would overflow are clamped, and sustained
+//! clipping is reported so an overly high gain is noticed in the logs.
+//!
+//! Optional auto gain tracks a slow-moving peak of the input and boosts it
+//! toward a common reference level, so a quiet mic reaches the engine at the
+//! same level as a loud one without hand-tuning `input_gain`.
+
+use tracing::warn;
+
+/// Fraction of clipped samples in a window above which a warning is logged
+const CLIP_WARN_RATIO: f32 = 0.01;
+
+/// Level auto gain brings the tracked peak to (about -6 dBFS)
+const AUTO_GAIN_TARGET: f32 = 16384.0;
+
+/// Largest boost auto gain applies, so silence and room noise aren't pumped up
+const AUTO_GAIN_MAX: f32 = 8.0;
+
+/// Time for the tracked peak to fall to ~37% once the input gets quieter
+const AUTO_GAIN_RELEASE_SECS: f32 = 2.0;
+
+/// Slow-moving peak estimate: jumps up immediately, decays over seconds
+struct PeakTracker {
+    peak: f32,
+    /// Per-sample decay factor
+    release: f32,
+}
+
+impl PeakTracker {
+    fn new(sample_rate: u32) -> Self {
+        let release = (-1.0 / (AUTO_GAIN_RELEASE_SECS * sample_rate as f32)).exp();
+        Self { peak: 0.0, release }
+    }
+
+    fn update(&mut self, sample: f32) -> f32 {
+        self.peak = sample.abs().max(self.peak * self.release);
+        self.peak
+    }
+
+    /// Boost (1.0 to `AUTO_GAIN_MAX`) that brings `peak` to the target level
+    fn gain(peak: f32) -> f32 {
+        if peak <= 0.0 {
+            return AUTO_GAIN_MAX;
+        }
+        (AUTO_GAIN_TARGET / peak).clamp(1.0, AUTO_GAIN_MAX)
+    }
+}
+
+/// Per-session gain stage
+pub struct InputGain {
+    gain: f32,
+    /// Peak estimate for auto gain (None = fixed gain only)
+    auto: Option<PeakTracker>,
+    /// Samples per clipping statistics window
+    window_samples: usize,
+    seen: usize,
+    clipped: usize,
+}
+
+impl InputGain {
+    /// `window_samples` sets how much audio clipping is averaged over before
+    /// deciding whether to warn (e.g. 5s at the session rate).
+    pub fn new(gain: f32, window_samples: usize) -> Self {
+        Self { gain, auto: None, window_samples: window_samples.max(1), seen: 0, clipped: 0 }
+    }
+
+    /// Also normalize toward a reference level (after the fixed gain) when `enabled`
+    pub fn with_auto_gain(mut self, enabled: bool, sample_rate: u32) -> Self {
+        self.auto = enabled.then(|| PeakTracker::new(sample_rate));
+        self
+    }
+
+    /// True when the gain leaves samples unchanged
+    pub fn is_unity(&self) -> bool {
+        self.gain == 1.0 && self.auto.is_none()
+    }
+
+    /// Scale `samples` in place, clamping to the i16 range
+    pub fn apply(&mut self, samples: &mut [i16]) {
+        if self.is_unity() {
+            return;
+        }
+
+        for sample in samples.iter_mut() {
+            let mut scaled = *sample as f32 * self.gain;
+            if let Some(tracker) = self.auto.as_mut() {
+                scaled *= PeakTracker::gain(tracker.update(scaled));
+            }
+            let scaled = scaled.round();
+            if scaled > i16::MAX as f32 || scaled < i16::MIN as f32 {
+                self.clipped += 1;
+            }
+            *sample = scaled.clamp(i16::MIN as f32, i16::MAX as f32) as i16;
+        }
+
+        self.seen += samples.len();
+        if self.seen >= self.window_samples {
+            let ratio = self.clipped as f32 / self.seen as f32;
+            if ratio > CLIP_WARN_RATIO {
+                warn!(
+                    "input_gain {} is clipping {:.1}% of samples, consider lowering it",
+                    self.gain,
+                    ratio * 100.0
+                );
+            }
+            self.seen = 0;
+            self.clipped = 0;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_double_gain_clamps() {
+        let mut gain = InputGain::new(2.0, 16000);
+        let mut samples = [0, 1000, -1000, 20000, -20000, i16::MAX, i16::MIN];
+        gain.apply(&mut samples);
+        assert_eq!(samples, [0, 2000, -2000, i16::MAX, i16::MIN, i16::MAX, i16::MIN]);
+    }
+
+    #[test]
+    fn test_unity_gain_passthrough() {
+        let mut gain = InputGain::new(1.0, 16000);
+        let mut samples = [1, -2, i16::MAX];
+        gain.apply(&mut samples);
+        assert_eq!(samples, [1, -2, i16::MAX]);
+    }
+
+    #[test]
+    fn test_auto_gain_brings_quiet_and_loud_input_together() {
+        let tone = |amplitude: f32| -> Vec<i16> {
+            (0..16000).map(|i| (amplitude * (i as f32 * 0.05).sin()) as i16).collect()
+        };
+        let peak = |samples: &[i16]| samples[8000..].iter().map(|s| s.unsigned_abs()).max().unwrap() as f32;
+
+        let mut quiet = tone(3000.0);
+        InputGain::new(1.0, 16000).with_auto_gain(true, 16000).apply(&mut quiet);
+        let mut loud = tone(20000.0);
+        InputGain::new(1.0, 16000).with_auto_gain(true, 16000).apply(&mut loud);
+
+        // The quiet input is raised to the reference level; loud input is never cut
+        assert!((peak(&quiet) - AUTO_GAIN_TARGET).abs() < 200.0, "{}", peak(&quiet));
+        assert!((peak(&loud) - 20000.0).abs() < 10.0);
+    }
+
+    #[test]
+    fn test_auto_gain_boost_is_capped() {
+        let mut hiss = [10i16; 100];
+        InputGain::new(1.0, 16000).with_auto_gain(true, 16000).apply(&mut hiss);
+        assert_eq!(hiss[99], 10 * AUTO_GAIN_MAX as i16);
+    }
+
+    #[test]
+    fn test_clip_statistics_reset_per_window() {
+        let mut gain = InputGain::new(4.0, 4);
+        gain.apply(&mut [20000, 20000, 0]);
+        assert_eq!(gain.clipped, 2);
+        gain.apply(&mut [0]);
+        assert_eq!((gain.seen, gain.clipped), (0, 0));
+    }
+}
@@ -1,22 +1,179 @@
-// Keyboard text injection via wtype
+// Keyboard text injection via wtype (Wayland) or xdotool (XWayland windows)
 
 use anyhow::Result;
+use std::process::Stdio;
 use std::time::Duration;
-use tracing::debug;
+use tracing::{debug, warn};
 
-pub struct KeyboardInjector;
+/// Where the final text goes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputMode {
+    /// Type into the focused window only
+    Type,
+    /// Only put the text on the clipboard, for pasting manually
+    Clipboard,
+    /// Copy to the clipboard as a backup, then type
+    #[default]
+    Both,
+}
+
+impl OutputMode {
+    /// Parse from config string
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "type" | "keyboard" => Some(OutputMode::Type),
+            "clipboard" | "no-type" | "copy" => Some(OutputMode::Clipboard),
+            "both" => Some(OutputMode::Both),
+            _ => None,
+        }
+    }
+
+    /// Whether the text is typed into the focused window
+    pub fn types(self) -> bool {
+        matches!(self, OutputMode::Type | OutputMode::Both)
+    }
+
+    /// Whether the text is copied to the clipboard
+    pub fn copies(self) -> bool {
+        matches!(self, OutputMode::Clipboard | OutputMode::Both)
+    }
+}
+
+/// Tool used to synthesize keystrokes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum InjectionMethod {
+    /// wtype for native Wayland windows, xdotool for XWayland windows
+    #[default]
+    Auto,
+    /// Wayland virtual-keyboard protocol via wtype
+    Wtype,
+    /// X11 XTEST via xdotool (reaches XWayland windows only)
+    Xdotool,
+}
+
+impl InjectionMethod {
+    /// Parse from config string
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "auto" => Some(InjectionMethod::Auto),
+            "wtype" | "wayland" => Some(InjectionMethod::Wtype),
+            "xdotool" | "x11" | "xwayland" => Some(InjectionMethod::Xdotool),
+            _ => None,
+        }
+    }
+
+    /// Resolve `Auto` for the focused window
+    pub fn resolve(self, is_xwayland: bool) -> Self {
+        match self {
+            InjectionMethod::Auto if is_xwayland => InjectionMethod::Xdotool,
+            InjectionMethod::Auto => InjectionMethod::Wtype,
+            other => other,
+        }
+    }
+
+    fn program(self) -> &'static str {
+        match self {
+            InjectionMethod::Xdotool => "xdotool",
+            InjectionMethod::Auto | InjectionMethod::Wtype => "wtype",
+        }
+    }
+
+    fn type_command(self, text: &str) -> tokio::process::Command {
+        let mut command = tokio::process::Command::new(self.program());
+        match self {
+            InjectionMethod::Xdotool => command.args(["type", "--clearmodifiers", "--"]).arg(text),
+            InjectionMethod::Auto | InjectionMethod::Wtype => command.arg(text),
+        };
+        command
+    }
+
+    fn key_command(self, key: &str) -> tokio::process::Command {
+        let mut command = tokio::process::Command::new(self.program());
+        match self {
+            InjectionMethod::Xdotool => command.args(["key", "--clearmodifiers", key]),
+            InjectionMethod::Auto | InjectionMethod::Wtype => command.args(["-k", key]),
+        };
+        command
+    }
+}
+
+pub struct KeyboardInjector {
+    output_mode: OutputMode,
+}
 
 impl KeyboardInjector {
     pub fn new() -> Self {
-        Self
+        Self::with_output_mode(OutputMode::default())
+    }
+
+    pub fn with_output_mode(output_mode: OutputMode) -> Self {
+        Self { output_mode }
+    }
+
+    pub fn output_mode(&self) -> OutputMode {
+        self.output_mode
+    }
+
+    /// Put `text` on the Wayland clipboard via wl-copy, or the clipboard
+    /// protocol directly when wl-copy isn't installed.
+    ///
+    /// The direct copy serves paste requests from a thread of its own until
+    /// another client takes over the selection. Letting wl-clipboard-rs fork
+    /// a background server instead isn't safe from the multithreaded daemon.
+    pub async fn copy_to_clipboard(&self, text: &str) -> Result<()> {
+        let spawned = tokio::process::Command::new("wl-copy")
+            .arg(text)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn();
+        match spawned {
+            Ok(_) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                debug!("wl-copy not installed, using the Wayland clipboard directly");
+                let bytes = text.as_bytes().to_vec();
+                std::thread::Builder::new()
+                    .name("clipboard".to_string())
+                    .spawn(move || {
+                        use wl_clipboard_rs::copy::{MimeType, Options, Source};
+                        let mut options = Options::new();
+                        options.foreground(true);
+                        if let Err(e) = options.copy(Source::Bytes(bytes.into()), MimeType::Text) {
+                            warn!("Failed to serve the clipboard: {}", e);
+                        }
+                    })?;
+            }
+            Err(e) => return Err(e.into()),
+        }
+        debug!("Copied to clipboard ({} chars)", text.len());
+        Ok(())
     }
 
     pub async fn type_text(&self, text: &str, word_delay_ms: u64) -> Result<()> {
-        debug!("Typing text: {}", text);
+        self.type_text_with(InjectionMethod::Wtype, text, word_delay_ms).await
+    }
+
+    /// Type text with a specific injection method (`Auto` behaves like `Wtype`)
+    pub async fn type_text_with(&self, method: InjectionMethod, text: &str, word_delay_ms: u64) -> Result<()> {
+        self.type_text_with_progress(method, text, word_delay_ms, |_| {}).await
+    }
+
+    /// Type text, reporting the fraction typed (0.0-1.0) after each batch.
+    ///
+    /// Rate-limited typing reports after every word; fast mode reports once at the end.
+    pub async fn type_text_with_progress(
+        &self,
+        method: InjectionMethod,
+        text: &str,
+        word_delay_ms: u64,
+        mut on_progress: impl FnMut(f32),
+    ) -> Result<()> {
+        debug!("Typing text via {}: {}", method.program(), text);
 
         if word_delay_ms > 0 {
             // Rate-limited mode: word-by-word with delays to avoid overwhelming
             // terminal UIs like Claude Code's React/Ink interface (React error #185)
+            let word_count = text.split_whitespace().count();
             for (i, word) in text.split_whitespace().enumerate() {
                 let chunk = if i == 0 {
                     word.to_string()
@@ -24,33 +181,40 @@ impl KeyboardInjector {
                     format!(" {}", word)
                 };
 
-                let output = tokio::process::Command::new("wtype")
-                    .arg(&chunk)
-                    .output()
-                    .await?;
-
-                if !output.status.success() {
-                    let stderr = String::from_utf8_lossy(&output.stderr);
-                    anyhow::bail!("wtype failed: {}", stderr);
-                }
-
+                run(method, method.type_command(&chunk)).await?;
+                on_progress((i + 1) as f32 / word_count as f32);
                 tokio::time::sleep(Duration::from_millis(word_delay_ms)).await;
             }
         } else {
             // Fast mode: type all text at once
-            let output = tokio::process::Command::new("wtype")
-                .arg(text)
-                .output()
-                .await?;
-
-            if !output.status.success() {
-                let stderr = String::from_utf8_lossy(&output.stderr);
-                anyhow::bail!("wtype failed: {}", stderr);
-            }
+            run(method, method.type_command(text)).await?;
+            on_progress(1.0);
         }
 
         Ok(())
     }
+
+    /// Press a named key (xkb keysym name, e.g. "Return")
+    pub async fn press_key(&self, key: &str) -> Result<()> {
+        self.press_key_with(InjectionMethod::Wtype, key).await
+    }
+
+    /// Press a named key with a specific injection method
+    pub async fn press_key_with(&self, method: InjectionMethod, key: &str) -> Result<()> {
+        debug!("Pressing key via {}: {}", method.program(), key);
+        run(method, method.key_command(key)).await
+    }
+}
+
+async fn run(method: InjectionMethod, mut command: tokio::process::Command) -> Result<()> {
+    let output = command.output().await?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("{} failed: {}", method.program(), stderr);
+    }
+
+    Ok(())
 }
 
 #[cfg(test)]
@@ -62,6 +226,39 @@ mod tests {
         let _injector = KeyboardInjector::new();
     }
 
+    #[test]
+    fn test_output_mode_dispatch() {
+        let cases = [
+            (OutputMode::Type, true, false),
+            (OutputMode::Clipboard, false, true),
+            (OutputMode::Both, true, true),
+        ];
+        for (mode, types, copies) in cases {
+            let injector = KeyboardInjector::with_output_mode(mode);
+            assert_eq!(injector.output_mode().types(), types, "{:?}", mode);
+            assert_eq!(injector.output_mode().copies(), copies, "{:?}", mode);
+        }
+        assert_eq!(KeyboardInjector::new().output_mode(), OutputMode::Both);
+    }
+
+    #[test]
+    fn test_output_mode_from_str() {
+        assert_eq!(OutputMode::from_str("Clipboard"), Some(OutputMode::Clipboard));
+        assert_eq!(OutputMode::from_str("no-type"), Some(OutputMode::Clipboard));
+        assert_eq!(OutputMode::from_str(" type "), Some(OutputMode::Type));
+        assert_eq!(OutputMode::from_str("both"), Some(OutputMode::Both));
+        assert_eq!(OutputMode::from_str("paste"), None);
+    }
+
+    #[test]
+    fn test_injection_method_resolve() {
+        assert_eq!(InjectionMethod::from_str("XDoTool"), Some(InjectionMethod::Xdotool));
+        assert_eq!(InjectionMethod::from_str("ydotool"), None);
+        assert_eq!(InjectionMethod::Auto.resolve(true), InjectionMethod::Xdotool);
+        assert_eq!(InjectionMethod::Auto.resolve(false), InjectionMethod::Wtype);
+        assert_eq!(InjectionMethod::Wtype.resolve(true), InjectionMethod::Wtype);
+    }
+
     #[tokio::test]
     async fn test_type_text_interface() {
         let injector = KeyboardInjector::new();
@@ -0,0 +1,87 @@
+//! Lightweight language identification of transcribed text.
+//!
+//! Counts common function words per language over the first words of the
+//! live preview. Cheap enough to run before the final pass, and only used to
+//! pick which model the final pass runs on, so a missed guess just keeps the
+//! default model.
+
+/// Words looked at, bounding the cost for long sessions
+const MAX_WORDS: usize = 60;
+
+/// Function-word hits needed before trusting a guess
+const MIN_HITS: usize = 2;
+
+/// Frequent short words that are rare in the other listed languages
+const STOPWORDS: &[(&str, &[&str])] = &[
+    ("en", &["the", "and", "is", "are", "you", "that", "of", "to", "it", "this", "with", "have", "what", "was"]),
+    ("de", &["der", "die", "das", "und", "ist", "nicht", "ich", "ein", "eine", "mit", "zu", "auf", "wir", "sie"]),
+    ("fr", &["le", "la", "les", "et", "est", "je", "pas", "une", "des", "que", "pour", "dans", "vous", "nous"]),
+    ("es", &["el", "los", "las", "y", "es", "que", "una", "por", "para", "con", "pero", "muy", "yo", "está"]),
+    ("it", &["il", "lo", "gli", "è", "che", "non", "una", "per", "con", "sono", "ma", "della", "io", "questo"]),
+    ("pt", &["o", "os", "as", "e", "é", "não", "uma", "com", "para", "mas", "você", "isso", "eu", "está"]),
+    ("nl", &["de", "het", "een", "en", "is", "niet", "ik", "dat", "van", "met", "voor", "zijn", "wij", "maar"]),
+];
+
+/// Language the final pass should use for the `language` setting: "auto" (or
+/// blank) detects it from the `preview` text, anything else is taken as a pinned
+/// code. None means no language could be determined, and the default model runs.
+///
+/// Parakeet models have no language parameter of their own; the code only
+/// selects a model from `language_engine_map`.
+pub fn resolve_language<'a>(setting: &'a str, preview: &str) -> Option<&'a str> {
+    match setting.trim() {
+        "" | "auto" => detect_language(preview),
+        pinned => Some(pinned),
+    }
+}
+
+/// Most likely language code ("en", "de", ...) of `text`, or None when there
+/// is too little evidence or two languages tie
+pub fn detect_language(text: &str) -> Option<&'static str> {
+    let words: Vec<String> = text
+        .split_whitespace()
+        .take(MAX_WORDS)
+        .map(|w| w.trim_matches(|c: char| !c.is_alphanumeric()).to_lowercase())
+        .collect();
+
+    let mut scores: Vec<(&'static str, usize)> = STOPWORDS
+        .iter()
+        .map(|&(code, stopwords)| {
+            (code, words.iter().filter(|w| stopwords.contains(&w.as_str())).count())
+        })
+        .collect();
+    scores.sort_by(|a, b| b.1.cmp(&a.1));
+
+    let (best, hits) = scores[0];
+    let runner_up = scores.get(1).map_or(0, |&(_, hits)| hits);
+    (hits >= MIN_HITS && hits > runner_up).then_some(best)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detects_common_languages() {
+        assert_eq!(detect_language("I think that the meeting is at three, and you have the notes"), Some("en"));
+        assert_eq!(detect_language("Ich glaube, dass die Besprechung nicht um drei ist und wir sind spät"), Some("de"));
+        assert_eq!(detect_language("Je pense que la réunion est pour demain et nous avons les notes"), Some("fr"));
+        assert_eq!(detect_language("Creo que la reunión es por la tarde y está muy lejos para mi"), Some("es"));
+    }
+
+    #[test]
+    fn test_resolve_language() {
+        let german = "Ich glaube, dass die Besprechung nicht um drei ist";
+        assert_eq!(resolve_language("auto", german), Some("de"));
+        assert_eq!(resolve_language("", german), Some("de"));
+        // A pinned language wins over what the preview looks like
+        assert_eq!(resolve_language("fr", german), Some("fr"));
+        assert_eq!(resolve_language("auto", "ok"), None);
+    }
+
+    #[test]
+    fn test_too_little_evidence() {
+        assert_eq!(detect_language(""), None);
+        assert_eq!(detect_language("Kubernetes deployment"), None);
+    }
+}
@@ -0,0 +1,71 @@
+//! Remembering the input device picked from the tray.
+//!
+//! A device chosen at runtime is written to `~/.cache/voice-dictation/last-device`
+//! and used again when the daemon next starts, as long as that device is still
+//! plugged in. Otherwise the configured `audio_device` applies, and an explicit
+//! `audio_device` in the config always wins over the saved choice.
+
+use crate::audio_backend::DeviceInfo;
+use std::fs;
+use std::path::PathBuf;
+use tracing::{debug, warn};
+
+fn state_path() -> Option<PathBuf> {
+    Some(dirs::cache_dir()?.join("voice-dictation").join("last-device"))
+}
+
+/// Record the selected device; `None` (system default) forgets the saved one
+pub fn save(device: Option<&str>) {
+    let Some(path) = state_path() else {
+        return;
+    };
+    let result = match device {
+        Some(name) => path
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|_| fs::write(&path, name)),
+        None => match fs::remove_file(&path) {
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            other => other,
+        },
+    };
+    if let Err(e) = result {
+        warn!("Can't save the selected device to {}: {}", path.display(), e);
+    }
+}
+
+/// The saved device, if any
+pub fn load() -> Option<String> {
+    let path = state_path()?;
+    let name = fs::read_to_string(&path).ok()?;
+    let name = name.trim();
+    (!name.is_empty()).then(|| name.to_string())
+}
+
+/// The saved device if it's among `available`
+pub fn restore(saved: Option<String>, available: &[DeviceInfo]) -> Option<String> {
+    let saved = saved?;
+    if available.iter().any(|d| d.name == saved) {
+        Some(saved)
+    } else {
+        debug!("Last used device {} isn't connected, using the default", saved);
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn device(name: &str) -> DeviceInfo {
+        DeviceInfo { name: name.to_string(), description: name.to_string(), is_default: false }
+    }
+
+    #[test]
+    fn test_restore_only_connected_device() {
+        let available = [device("usb-mic"), device("builtin")];
+        assert_eq!(restore(Some("usb-mic".to_string()), &available), Some("usb-mic".to_string()));
+        assert_eq!(restore(Some("unplugged".to_string()), &available), None);
+        assert_eq!(restore(None, &available), None);
+    }
+}
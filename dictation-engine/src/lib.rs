@@ -7,7 +7,7 @@ use std::sync::Arc;
 use std::time::{Duration, Instant};
 use systemd::daemon::{notify, STATE_READY, STATE_WATCHDOG};
 use tokio::sync::{broadcast, mpsc, Mutex, RwLock};
-use tracing::{debug, error, info, warn};
+use tracing::{debug, error, info, info_span, warn, Instrument};
 
 pub mod audio_backend;
 mod chunking;
@@ -15,11 +15,26 @@ pub mod control_ipc;
 pub mod dbus_control;
 mod debug_audio;
 mod engine;
+pub mod file_transcribe;
+mod final_pass;
+mod input_gain;
 mod app_profile;
+mod audio_buffer;
+mod audio_preprocess;
 mod keyboard;
+mod last_device;
+mod language_id;
+mod media_control;
+mod metrics;
 mod model_selector;
+mod notification;
+mod pause_split;
 pub mod parakeet_engine;
 mod post_processing;
+mod resample;
+mod silence_timeout;
+mod sound_cues;
+mod wakeword;
 mod window_detect;
 mod window_target;
 pub mod user_dictionary;
@@ -29,31 +44,19 @@ mod tray;
 
 pub use dictation_types::{GuiControl, GuiState, GuiStatus};
 
-/// Check if media is playing and pause it. Returns true if media was paused.
-fn pause_media_if_playing() -> bool {
-    let Ok(output) = std::process::Command::new("playerctl")
-        .arg("status")
-        .output() else { return false };
-    let playing = String::from_utf8_lossy(&output.stdout).contains("Playing");
-    if playing {
-        let _ = std::process::Command::new("playerctl").arg("pause").output();
-        info!("Paused media playback");
-    }
-    playing
-}
-
-/// Resume media playback.
-fn resume_media() {
-    let _ = std::process::Command::new("playerctl").arg("play").output();
-    info!("Resumed media playback");
-}
+/// GUI → daemon status channel capacity. The daemon drains it continuously after init.
+const GUI_STATUS_CHANNEL_CAPACITY: usize = 32;
 
 use audio_backend::{AudioBackend, AudioBackendConfig, BackendType};
-use dbus_control::DaemonCommand;
+use dbus_control::{DaemonCommand, StopBehavior};
 use engine::TranscriptionEngine;
-use keyboard::KeyboardInjector;
+use keyboard::{InjectionMethod, KeyboardInjector, OutputMode};
 use model_selector::ModelSpec;
-use post_processing::{Pipeline, SanitizationProcessor, TextProcessor};
+use post_processing::{
+    dedup_edge_tokens, strip_processor_toggles, strip_submit_command, Pipeline, ProcessorKind,
+    ProcessorOverrides, PunctuationContext, SanitizationProcessor, SpacingOptions, TextProcessor,
+    UnicodeForm,
+};
 use user_dictionary::UserDictionary;
 
 // Re-export DaemonState from dbus_control
@@ -61,7 +64,8 @@ use dbus_control::DaemonState;
 
 // Recording session context
 struct RecordingSession {
-    #[allow(dead_code)]
+    /// Short correlation ID tagged on this session's logs
+    id: String,
     start_time: Instant,
     engine: Arc<dyn TranscriptionEngine>,
 }
@@ -81,19 +85,196 @@ struct DaemonConfig {
     #[serde(default = "default_model", alias = "preview_model")]
     model: String,
 
+    // Final-pass fallback chain, tried in order until one gives non-empty text:
+    // "final" (full-buffer pass), "reload" (fresh engine on the session audio),
+    // "preview" (cached live text)
+    #[serde(default = "default_final_pass_chain")]
+    final_pass_chain: Vec<String>,
+
+    // Spoken language: "auto" (or empty) detects it from the live preview text, a
+    // code ("de") pins it. Only consulted when language_engine_map is set, since
+    // the language is applied by switching the final-pass model.
+    #[serde(default = "default_language")]
+    language: String,
+
+    // Language code -> model spec ("de" = "parakeet:de") for the final pass.
+    // Languages not listed keep the default model.
+    #[serde(default)]
+    language_engine_map: std::collections::HashMap<String, String>,
+
+    // Check available memory at startup and transcribe in shorter chunks when the
+    // model's usual footprint wouldn't fit, instead of getting OOM-killed
+    #[serde(default)]
+    auto_model_by_memory: bool,
+
+    // Chunks of long recordings (over ~30s) transcribed at once. Each extra chunk
+    // loads another model instance (~2.5 GB), so raise only with spare RAM and cores.
+    #[serde(default = "default_chunk_parallelism")]
+    chunk_parallelism: usize,
+
     // Post-processing
     #[serde(default = "default_enable_acronyms")]
     enable_acronyms: bool,
-    #[serde(default = "default_enable_punctuation")]
+    // Terminal period and punctuation spacing (opt-in; capitalization is separate)
+    #[serde(default)]
     enable_punctuation: bool,
+    // Skip the terminal period for utterances shorter than trailing_punctuation_min_words
+    // and for terminals/browsers (search boxes, command lines)
+    #[serde(default)]
+    suppress_trailing_punctuation_short: bool,
+    #[serde(default = "default_trailing_punctuation_min_words")]
+    trailing_punctuation_min_words: usize,
+    // Capitalize sentence starts and the pronoun "I"
+    #[serde(default = "default_enable_capitalization")]
+    enable_capitalization: bool,
     #[serde(default = "default_enable_grammar")]
     enable_grammar: bool,
+    // Drop a doubled first/last word or stray leading filler that the final pass added
+    // but the live preview of the same audio didn't have
+    #[serde(default)]
+    enable_edge_dedup: bool,
+
+    // Spoken "send it"/"submit" at the end of an utterance presses Enter after typing
+    #[serde(default = "default_enable_submit_command")]
+    enable_submit_command: bool,
+
+    // Spoken "snake case"/"camel case"/... commands join following words into identifiers
+    #[serde(default = "default_enable_code_case")]
+    enable_code_case: bool,
+
+    // Spoken "heading"/"bullet point"/"bold"/"italic" insert markdown syntax.
+    // A non-empty markdown_apps limits this to the listed window classes.
+    #[serde(default)]
+    enable_markdown_commands: bool,
+    #[serde(default)]
+    markdown_apps: Vec<String>,
+
+    // Spoken "period"/"comma"/"new line"/"open paren" become the symbol. "period",
+    // "colon" and "dash" after "the"/"a"/"trial"/... stay words; "literal period" types
+    // the word. punctuation_commands adds or replaces phrases (phrase = symbol)
+    #[serde(default)]
+    enable_punctuation_commands: bool,
+    #[serde(default)]
+    punctuation_commands: std::collections::HashMap<String, String>,
+
+    // Spoken "<processor> on|off" at the start or end of an utterance ("grammar off")
+    // toggles that post-processor until the daemon restarts
+    #[serde(default)]
+    enable_processor_toggle_commands: bool,
+
+    // Whitespace around typed text: trim the transcription, then optionally add a
+    // leading space (appending to existing text) and/or a trailing space
+    #[serde(default = "default_trim_output")]
+    trim_output: bool,
+    #[serde(default)]
+    prepend_space: bool,
+    #[serde(default)]
+    append_space: bool,
+
+    // Adapt typed text to the focused app (escape shell characters in terminals,
+    // strip control characters and ANSI escapes everywhere)
+    #[serde(default = "default_enable_sanitization")]
+    enable_sanitization: bool,
+
+    // Unicode normalization of typed/copied text: "nfc" (composed, default), "nfd", "none"
+    #[serde(default = "default_unicode_normalization")]
+    unicode_normalization: String,
+
+    // Also show the final transcription in a desktop notification (freedesktop
+    // notifications over D-Bus) once it has been typed or copied, e.g. when the
+    // overlay was on another monitor
+    #[serde(default)]
+    notify_on_confirm: bool,
+
+    // Don't type the result; only broadcast it with the TranscriptionComplete D-Bus
+    // signal (sent for every session either way) for scripts to consume
+    #[serde(default)]
+    emit_dbus_only: bool,
+
+    // Show a typing progress ring on the overlay while the result is injected,
+    // instead of the processing spinner
+    #[serde(default = "default_show_typing_state")]
+    show_typing_state: bool,
+
+    // Sound cues when recording starts and after the result is typed (played with
+    // pw-play/paplay). The start cue finishes (up to 0.5s) before capture begins.
+    #[serde(default)]
+    enable_sound_cues: bool,
+    #[serde(default)]
+    start_sound: String,
+    #[serde(default)]
+    confirm_sound: String,
+
+    // Skip typing when keyboard focus is on the dictation overlay itself
+    #[serde(default = "default_suppress_self_injection")]
+    suppress_self_injection: bool,
+
+    // Window classes where dictation refuses to start (e.g. games, video players).
+    // A non-empty allowlist limits dictation to the listed classes instead.
+    #[serde(default)]
+    app_blocklist: Vec<String>,
+    #[serde(default)]
+    app_allowlist: Vec<String>,
+
+    // Keystroke injection: "auto" (xdotool for XWayland windows, wtype otherwise),
+    // "wtype", or "xdotool"
+    #[serde(default = "default_injection_method")]
+    injection_method: String,
+
+    // Where the final text goes: "type", "clipboard" (paste it yourself) or "both"
+    // (clipboard as a backup, then typed)
+    #[serde(default = "default_output_mode")]
+    output_mode: String,
+
+    // Post-processor order by name: acronyms, punctuation_commands, punctuation, capitalization,
+    // grammar, code_case, markdown.
+    // Empty = default order; unlisted processors are disabled.
+    #[serde(default)]
+    post_processing_order: Vec<String>,
+
+    // Extra spell-check dictionary files, loaded in order after the bundled defaults
+    // (later files override earlier ones; "!word" removes a word)
+    #[serde(default)]
+    dictionary_files: Vec<String>,
 
     // Audio capture
     #[serde(default = "default_silence_threshold_db")]
     silence_threshold_db: f32,
+    // Confirm the recording once the speaker has stopped talking for this long
+    // after speaking, 0 = off
+    #[serde(default)]
+    silence_timeout_ms: u64,
+    // What counts as speech for silence_timeout_ms: "energy" (above vad_energy_threshold_db)
+    // or "vad" (the VAD's decision, which ignores steady background noise)
+    #[serde(default = "default_silence_detector")]
+    silence_detector: String,
+    // Start dictation by saying this phrase on its own (empty = off). The mic then stays
+    // open permanently, ignoring idle_release_timeout_secs; short utterances are
+    // transcribed and checked for the phrase
+    #[serde(default)]
+    wake_word: String,
+    // Audio discarded right after a capture stream starts (pops, DC settling), 0 = off
+    #[serde(default = "default_capture_warmup_ms")]
+    capture_warmup_ms: u64,
+    // Save every session's audio to /tmp/voice-dictation-debug (also on with RUST_LOG=debug)
     #[serde(default = "default_debug_audio")]
     debug_audio: bool,
+    // Saved session recordings kept before the oldest are deleted
+    #[serde(default = "default_session_audio_keep")]
+    session_audio_keep: usize,
+    // Total size cap for saved session recordings in MB (0 = no cap)
+    #[serde(default)]
+    session_audio_max_mb: u64,
+
+    // Voice activity detection backend: "energy" (dB threshold) or "silero" (neural, ONNX)
+    #[serde(default = "default_vad_backend")]
+    vad_backend: String,
+    // Silero speech probability threshold (0.0-1.0)
+    #[serde(default = "default_vad_threshold")]
+    vad_threshold: f32,
+    // Energy VAD speech threshold (dB)
+    #[serde(default = "default_vad_energy_threshold_db")]
+    vad_energy_threshold_db: f32,
 
     // Trailing audio buffer after stop command (captures final words)
     #[serde(default = "default_trailing_buffer_ms")]
@@ -103,6 +284,29 @@ struct DaemonConfig {
     #[serde(default = "default_audio_backend")]
     audio_backend: String,
 
+    // PipeWire: delay before re-attaching to a selected device that disappeared and came
+    // back (e.g. after suspend), doubling on each flap up to the max (milliseconds)
+    #[serde(default = "default_pipewire_reconnect_backoff_ms")]
+    pipewire_reconnect_backoff_ms: u64,
+    #[serde(default = "default_pipewire_reconnect_backoff_max_ms")]
+    pipewire_reconnect_backoff_max_ms: u64,
+
+    // External command that session audio is piped through before the engine and VAD
+    // (raw 16kHz mono i16 LE on stdin and stdout, e.g. an RNNoise filter). Its buffering
+    // delays the live preview; raw audio is used if it fails. Empty = disabled.
+    #[serde(default)]
+    audio_preprocess_command: String,
+
+    // Fixed multiplier for the audio fed to the engine, for consistently quiet mics.
+    // VAD, silence detection and the spectrum see the unamplified level.
+    // Overflowing samples are clamped.
+    #[serde(default = "default_input_gain")]
+    input_gain: f32,
+    // Normalize the input toward a common reference level (after input_gain), boosting
+    // quiet mics up to 8x; loud input is left as is
+    #[serde(default)]
+    auto_gain: bool,
+
     // Idle release timeout: how long to keep mic open after stop before releasing (seconds)
     #[serde(default = "default_idle_release_timeout_secs")]
     idle_release_timeout_secs: u64,
@@ -111,22 +315,352 @@ struct DaemonConfig {
     #[serde(default = "default_media_resume_delay_ms")]
     media_resume_delay_ms: u64,
 
+    // Media pause while recording: "auto" (playerctl if installed, else MPRIS),
+    // "playerctl", "mpris" (native D-Bus), or "none"
+    #[serde(default = "default_media_control")]
+    media_control: String,
+
+    // What the Stop command does while recording: "cancel" (discard, default) or
+    // "confirm" (finalize and type like Confirm). `voice-dictation cancel` always discards.
+    #[serde(default = "default_stop_behavior")]
+    stop_behavior: String,
+
+    // Double-tap gesture: a second start/toggle within this many milliseconds of the
+    // one that started recording cancels it instead of confirming (0 = off)
+    #[serde(default)]
+    double_tap_ms: u64,
+
+    // Let a command (e.g. StartRecording) arriving during the overlay closing delay
+    // cut the delay short and be handled immediately
+    #[serde(default = "default_interrupt_closing")]
+    interrupt_closing: bool,
+
+    // Act on a StartRecording sent while the daemon is still starting (GUI init, model
+    // load) once startup finishes, instead of dropping it
+    #[serde(default = "default_queue_startup_commands")]
+    queue_startup_commands: bool,
+
     // Engine idle timeout: drop ORT sessions after N seconds idle to reclaim BFCArena memory (seconds)
     #[serde(default = "default_engine_idle_timeout_secs")]
     engine_idle_timeout_secs: u64,
+
+    // Max session audio kept in memory (seconds, 0 = unbounded). Older audio is spilled
+    // to a temp file and still transcribed, or dropped if audio_buffer_spill = false.
+    #[serde(default = "default_max_audio_buffer_seconds")]
+    max_audio_buffer_seconds: u64,
+    #[serde(default = "default_audio_buffer_spill")]
+    audio_buffer_spill: bool,
+
+    // Prometheus-style metrics exporter on 127.0.0.1 (opt-in)
+    #[serde(default = "default_enable_metrics_server")]
+    enable_metrics_server: bool,
+    #[serde(default = "default_metrics_port")]
+    metrics_port: u16,
+
+    // Utterances shorter than this use the cached preview transcription instead of a
+    // final full-buffer pass (seconds, 0 = always run the final pass). Single Parakeet
+    // model, so no extra memory is used.
+    #[serde(default = "default_small_model_max_seconds")]
+    small_model_max_seconds: f32,
+
+    // Live preview refresh interval (milliseconds); lower is snappier but costs more CPU
+    #[serde(default = "default_preview_interval_ms")]
+    preview_interval_ms: u64,
+
+    // Live preview only transcribes the last N seconds of audio (0 = the whole
+    // recording), keeping preview latency flat on long dictations
+    #[serde(default)]
+    preview_window_seconds: u64,
+
+    // Show the final text, exactly as it will be typed, in the overlay for this long before typing
+    // (milliseconds, 0 = off), so corrections to the live preview are visible
+    #[serde(default)]
+    commit_display_ms: u64,
+
+    // Silence added before and after the audio for the final pass (milliseconds, 0 = off).
+    // Helps when the first or last word gets clipped by an abrupt start/stop.
+    #[serde(default)]
+    final_pass_silence_pad_ms: u64,
+
+    // Drop words that are mostly undecodable characters (U+FFFD) and strip stray ones
+    // from the rest. Off leaves them in the text; either way they are logged
+    #[serde(default = "default_drop_undecodable_words")]
+    drop_undecodable_words: bool,
+
+    // Longest the final pass may take (milliseconds, 0 = no limit). Past it, the live
+    // preview result is typed instead so a slow engine never leaves the user waiting.
+    #[serde(default)]
+    engine_timeout_ms: u64,
+
+    // Discard a final result the engine scored below this confidence (0.0-1.0, 0 = off)
+    // instead of typing it, e.g. garbage text from a cough. Results without a score
+    // (the preview text, engines that don't report one) are always kept.
+    #[serde(default)]
+    min_confidence: f32,
+
+    // Cut leading/trailing silence (below vad_energy_threshold_db) from the audio before
+    // the final pass, keeping silence_margin_ms around the speech. Speeds up the pass and
+    // avoids hallucinated words on a long silent tail.
+    #[serde(default)]
+    trim_silence: bool,
+    #[serde(default = "default_silence_margin_ms")]
+    silence_margin_ms: u64,
+
+    // Break long dictations where the speaker paused for at least pause_threshold_ms,
+    // using the engine's word timestamps. pause_break: "sentence" (". "), "newline"
+    // or "paragraph"
+    #[serde(default)]
+    split_on_pauses: bool,
+    #[serde(default = "default_pause_threshold_ms")]
+    pause_threshold_ms: u64,
+    #[serde(default = "default_pause_break")]
+    pause_break: String,
+
+    // Overlay fade-in when it appears (milliseconds, 0 = instant) and easing curve name
+    #[serde(default = "default_startup_fade_duration_ms", alias = "startup_fade_duration")]
+    startup_fade_duration_ms: u64,
+    #[serde(default = "default_startup_fade_easing")]
+    startup_fade_easing: String,
+
+    // Easing curve name for the overlay collapse after confirm/cancel
+    #[serde(default = "default_closing_easing")]
+    closing_easing: String,
+
+    // Focused-monitor detection for the overlay: "auto", "hyprland", "sway", "niri", "none"
+    #[serde(default = "default_monitor_backend")]
+    monitor_backend: String,
+
+    // Output (e.g. "DP-1") for the overlay when the focused monitor can't be detected.
+    // Empty = show on all monitors.
+    #[serde(default)]
+    fallback_monitor: String,
+
+    // Overlay rasterization: "gpu" (FemtoVG on the GPU driver) or "software" (Mesa
+    // llvmpipe, a workaround for driver rendering glitches). LIBGL_ALWAYS_SOFTWARE
+    // set in the environment overrides this.
+    #[serde(default = "default_overlay_renderer", alias = "slint_backend")]
+    overlay_renderer: String,
+
+    // Overlay placement: "screen" (bottom-center), "cursor" (below the mouse), or
+    // "window" (bottom of the focused window). Falls back to "screen" without Hyprland.
+    #[serde(default = "default_overlay_position")]
+    overlay_position: String,
+
+    // Overlay transcription text color ("#rrggbb") and opacity (0.0-1.0)
+    #[serde(default = "default_overlay_text_color")]
+    overlay_text_color: String,
+    #[serde(default = "default_overlay_text_opacity")]
+    overlay_text_opacity: f32,
+    // Width the transcription text may use inside the overlay (logical px, 0 = full
+    // width of 348); the overlay and spectrum keep their size
+    #[serde(default)]
+    overlay_text_max_width: f32,
+    // Shown in the overlay until the first word is recognized ("" = no placeholder)
+    #[serde(default = "default_listening_placeholder")]
+    listening_placeholder: String,
+
+    // Show a gentle pulse instead of flat bars when every spectrum band stays below
+    // the threshold (0.0-1.0 band energy), so a quiet mic still looks live
+    #[serde(default)]
+    spectrum_idle_animation: bool,
+    #[serde(default = "default_spectrum_idle_threshold")]
+    spectrum_idle_threshold: f32,
+
+    // Spectrum bar response: band RMS is multiplied by spectrum_gain, clipped to 0-1,
+    // then raised to spectrum_gamma (<1 makes quiet speech more visible, >1 compresses it)
+    #[serde(default = "default_spectrum_gain")]
+    spectrum_gain: f32,
+    #[serde(default = "default_spectrum_gamma")]
+    spectrum_gamma: f32,
+
+    // Hold a marker at each band's recent peak, falling at spectrum_peak_decay
+    // full bar heights per second
+    #[serde(default)]
+    spectrum_peak_hold: bool,
+    #[serde(default = "default_spectrum_peak_decay")]
+    spectrum_peak_decay: f32,
+
+    // Number of spectrum bars in the listening overlay
+    #[serde(default = "default_spectrum_band_count")]
+    spectrum_band_count: usize,
+
+    // Blend each spectrum frame with the previous one (0 = off, up to 0.95 for
+    // slow, smooth bars); bars also fall back to zero when audio stops
+    #[serde(default)]
+    spectrum_smoothing: f32,
 }
 
 fn default_model() -> String { "parakeet:default".to_string() }
 fn default_enable_acronyms() -> bool { true }
-fn default_enable_punctuation() -> bool { true }
+fn default_enable_capitalization() -> bool { true }
+fn default_trailing_punctuation_min_words() -> usize { 4 }
 fn default_enable_grammar() -> bool { true }
+fn default_enable_submit_command() -> bool { false }
+fn default_enable_code_case() -> bool { false }
+fn default_trim_output() -> bool { true }
+fn default_enable_sanitization() -> bool { true }
+fn default_drop_undecodable_words() -> bool { true }
+fn default_unicode_normalization() -> String { "nfc".to_string() }
+fn default_suppress_self_injection() -> bool { true }
+fn default_show_typing_state() -> bool { true }
+fn default_injection_method() -> String { "auto".to_string() }
+fn default_output_mode() -> String { "both".to_string() }
 fn default_silence_threshold_db() -> f32 { -60.0 }
+fn default_capture_warmup_ms() -> u64 { 50 }
 fn default_debug_audio() -> bool { false }
+fn default_session_audio_keep() -> usize { debug_audio::MAX_DEBUG_FILES }
+fn default_vad_backend() -> String { "energy".to_string() }
+fn default_vad_threshold() -> f32 { 0.5 }
+fn default_vad_energy_threshold_db() -> f32 { -45.0 }
 fn default_trailing_buffer_ms() -> u64 { 750 }
 fn default_audio_backend() -> String { "auto".to_string() }
+fn default_pipewire_reconnect_backoff_ms() -> u64 { 500 }
+fn default_pipewire_reconnect_backoff_max_ms() -> u64 { 30_000 }
 fn default_idle_release_timeout_secs() -> u64 { 30 }
 fn default_media_resume_delay_ms() -> u64 { 25 }
+fn default_media_control() -> String { "auto".to_string() }
+fn default_stop_behavior() -> String { "cancel".to_string() }
+fn default_interrupt_closing() -> bool { true }
+fn default_queue_startup_commands() -> bool { true }
 fn default_engine_idle_timeout_secs() -> u64 { 300 }  // 5 minutes
+fn default_max_audio_buffer_seconds() -> u64 { 1800 }  // 30 minutes (~58MB)
+fn default_audio_buffer_spill() -> bool { true }
+fn default_enable_metrics_server() -> bool { false }
+fn default_metrics_port() -> u16 { 9464 }
+fn default_small_model_max_seconds() -> f32 { 0.0 }
+fn default_preview_interval_ms() -> u64 { 200 }
+fn default_input_gain() -> f32 { 1.0 }
+fn default_silence_margin_ms() -> u64 { 200 }
+fn default_pause_threshold_ms() -> u64 { 1500 }
+fn default_pause_break() -> String { "sentence".to_string() }
+fn default_silence_detector() -> String { "energy".to_string() }
+fn default_chunk_parallelism() -> usize { 1 }
+fn default_startup_fade_duration_ms() -> u64 { 150 }
+fn default_startup_fade_easing() -> String { "ease-out".to_string() }
+fn default_final_pass_chain() -> Vec<String> { vec!["final".to_string(), "preview".to_string()] }
+fn default_language() -> String { "auto".to_string() }
+fn default_closing_easing() -> String { "ease-in".to_string() }
+fn default_monitor_backend() -> String { "auto".to_string() }
+fn default_overlay_renderer() -> String { "gpu".to_string() }
+fn default_listening_placeholder() -> String { "Listening...".to_string() }
+fn default_overlay_position() -> String { "screen".to_string() }
+fn default_overlay_text_color() -> String { "#ffffff".to_string() }
+fn default_overlay_text_opacity() -> f32 { 1.0 }
+fn default_spectrum_idle_threshold() -> f32 { 0.05 }
+fn default_spectrum_gain() -> f32 { slint_gui::DEFAULT_SPECTRUM_GAIN }
+fn default_spectrum_gamma() -> f32 { 1.0 }
+fn default_spectrum_peak_decay() -> f32 { slint_gui::DEFAULT_PEAK_DECAY }
+fn default_spectrum_band_count() -> usize { slint_gui::DEFAULT_BAND_COUNT }
+
+impl DaemonConfig {
+    /// Configs written before capitalization was split out of the punctuation
+    /// processor have no `enable_capitalization` key; there `enable_punctuation`
+    /// and a "punctuation" entry in `post_processing_order` meant capitalization
+    /// only. Map them onto capitalization so upgrading doesn't change the output.
+    /// Returns true when the old layout was found and changed something.
+    fn migrate_punctuation_split(&mut self, raw: &toml::Table) -> bool {
+        let Some(daemon) = raw.get("daemon").and_then(|daemon| daemon.as_table()) else {
+            return false;
+        };
+        if daemon.contains_key("enable_capitalization") {
+            return false;
+        }
+        let is_punctuation = |name: &String| ProcessorKind::from_name(name) == Some(ProcessorKind::Punctuation);
+        let legacy_flag = daemon.get("enable_punctuation").and_then(|value| value.as_bool());
+        let legacy_order = self.post_processing_order.iter().any(is_punctuation);
+        if legacy_flag.is_none() && !legacy_order {
+            return false;
+        }
+
+        self.enable_capitalization = legacy_flag.unwrap_or_else(default_enable_capitalization);
+        self.enable_punctuation = false;
+        for name in self.post_processing_order.iter_mut().filter(|name| is_punctuation(&**name)) {
+            *name = "capitalization".to_string();
+        }
+        warn!(
+            "config.toml predates the capitalization/punctuation split, so enable_punctuation and the \
+             \"punctuation\" processor are read as enable_capitalization = {} and \"capitalization\". \
+             Set enable_capitalization (and enable_punctuation for automatic periods) to silence this",
+            self.enable_capitalization
+        );
+        true
+    }
+
+    /// Whether the config enables post-processor `kind`. Per-app filters and
+    /// spoken overrides are applied by the caller.
+    fn processor_enabled(&self, kind: ProcessorKind) -> bool {
+        match kind {
+            ProcessorKind::Acronyms => self.enable_acronyms,
+            ProcessorKind::PunctuationCommands => self.enable_punctuation_commands,
+            ProcessorKind::Capitalization => self.enable_capitalization,
+            ProcessorKind::Punctuation => self.enable_punctuation,
+            ProcessorKind::Grammar => self.enable_grammar,
+            ProcessorKind::CodeCase => self.enable_code_case,
+            ProcessorKind::Markdown => self.enable_markdown_commands,
+        }
+    }
+
+    /// Clamp numeric settings to usable ranges, warning about each adjustment.
+    /// Returns the number of values that were out of range.
+    fn validate_and_clamp(&mut self) -> usize {
+        [
+            clamp_setting("overlay_text_opacity", &mut self.overlay_text_opacity, 0.0, 1.0),
+            clamp_setting("overlay_text_max_width", &mut self.overlay_text_max_width, 0.0, slint_gui::MAX_TEXT_WIDTH),
+            clamp_setting("spectrum_idle_threshold", &mut self.spectrum_idle_threshold, 0.0, 1.0),
+            clamp_setting("spectrum_gain", &mut self.spectrum_gain, 1.0, 100.0),
+            clamp_setting("spectrum_gamma", &mut self.spectrum_gamma, 0.1, 5.0),
+            clamp_setting("spectrum_peak_decay", &mut self.spectrum_peak_decay, 0.05, 10.0),
+            clamp_setting("spectrum_band_count", &mut self.spectrum_band_count, 1, slint_gui::MAX_BAND_COUNT),
+            clamp_setting("spectrum_smoothing", &mut self.spectrum_smoothing, 0.0, slint_gui::MAX_SPECTRUM_SMOOTHING),
+            clamp_setting("vad_threshold", &mut self.vad_threshold, 0.0, 1.0),
+            clamp_setting("silence_threshold_db", &mut self.silence_threshold_db, -120.0, 0.0),
+            clamp_setting("silence_timeout_ms", &mut self.silence_timeout_ms, 0, 60000),
+            clamp_setting("vad_energy_threshold_db", &mut self.vad_energy_threshold_db, -120.0, 0.0),
+            clamp_setting("small_model_max_seconds", &mut self.small_model_max_seconds, 0.0, 60.0),
+            clamp_setting("preview_interval_ms", &mut self.preview_interval_ms, 50, 2000),
+            clamp_setting("preview_window_seconds", &mut self.preview_window_seconds, 0, 600),
+            clamp_setting("commit_display_ms", &mut self.commit_display_ms, 0, 3000),
+            clamp_setting("chunk_parallelism", &mut self.chunk_parallelism, 1, 8),
+            clamp_setting("input_gain", &mut self.input_gain, 0.1, 10.0),
+            clamp_setting("startup_fade_duration_ms", &mut self.startup_fade_duration_ms, 0, 5000),
+            clamp_setting("trailing_buffer_ms", &mut self.trailing_buffer_ms, 0, 5000),
+            clamp_setting("capture_warmup_ms", &mut self.capture_warmup_ms, 0, 1000),
+            clamp_setting("final_pass_silence_pad_ms", &mut self.final_pass_silence_pad_ms, 0, 2000),
+            clamp_setting("silence_margin_ms", &mut self.silence_margin_ms, 0, 2000),
+            clamp_setting("pause_threshold_ms", &mut self.pause_threshold_ms, 200, 10000),
+            clamp_setting("double_tap_ms", &mut self.double_tap_ms, 0, 2000),
+            clamp_setting("session_audio_keep", &mut self.session_audio_keep, 1, 10_000),
+            clamp_setting("engine_timeout_ms", &mut self.engine_timeout_ms, 0, PROCESSING_STALL_BUDGET.as_millis() as u64),
+            clamp_setting("min_confidence", &mut self.min_confidence, 0.0, 1.0),
+            clamp_setting("media_resume_delay_ms", &mut self.media_resume_delay_ms, 0, 10_000),
+            clamp_setting("pipewire_reconnect_backoff_ms", &mut self.pipewire_reconnect_backoff_ms, 0, 60_000),
+            clamp_setting("pipewire_reconnect_backoff_max_ms", &mut self.pipewire_reconnect_backoff_max_ms, 0, 600_000),
+            clamp_setting("metrics_port", &mut self.metrics_port, 1, u16::MAX),
+        ]
+        .into_iter()
+        .filter(|&clamped| clamped)
+        .count()
+    }
+}
+
+/// Clamp `value` to `min..=max` (NaN becomes `min`), warning when it changes.
+fn clamp_setting<T>(name: &str, value: &mut T, min: T, max: T) -> bool
+where
+    T: PartialOrd + Copy + std::fmt::Display,
+{
+    let clamped = match value.partial_cmp(&min) {
+        Some(std::cmp::Ordering::Less) | None => min,
+        _ if *value > max => max,
+        _ => *value,
+    };
+
+    if clamped.partial_cmp(value) != Some(std::cmp::Ordering::Equal) {
+        warn!("Config {} = {} is out of range ({} to {}), using {}", name, value, min, max, clamped);
+        *value = clamped;
+        return true;
+    }
+    false
+}
 
 /// Convert decibels to linear amplitude (RMS threshold).
 fn db_to_linear(db: f32) -> f32 {
@@ -140,12 +674,165 @@ fn load_config() -> Result<Config> {
     let config_str = fs::read_to_string(&config_path)
         .map_err(|e| anyhow::anyhow!("Failed to read config file {}: {}", config_path, e))?;
 
-    let config: Config = toml::from_str(&config_str)
+    parse_config(&config_str)
+}
+
+/// Parse config.toml contents, upgrading old layouts and clamping values
+fn parse_config(config_str: &str) -> Result<Config> {
+    let mut config: Config = toml::from_str(config_str)
         .map_err(|e| anyhow::anyhow!("Failed to parse config: {}", e))?;
+    let raw: toml::Table = toml::from_str(config_str)
+        .map_err(|e| anyhow::anyhow!("Failed to parse config: {}", e))?;
+
+    config.daemon.migrate_punctuation_split(&raw);
+    config.daemon.validate_and_clamp();
 
     Ok(config)
 }
 
+/// Model and engine options from the config
+fn model_spec_from_config(daemon: &DaemonConfig) -> Result<ModelSpec> {
+    let mut model_spec = ModelSpec::parse(&daemon.model)
+        .map_err(|e| anyhow::anyhow!("Invalid model '{}': {}", daemon.model, e))?;
+
+    info!("Model: {}", model_spec);
+
+    model_spec.chunk_parallelism = daemon.chunk_parallelism;
+    model_spec.silence_trim = daemon.trim_silence.then(|| vad::SilenceTrim {
+        threshold_db: daemon.vad_energy_threshold_db,
+        margin_ms: daemon.silence_margin_ms,
+    });
+    model_spec.pause_split = daemon.split_on_pauses.then(|| pause_split::PauseSplit {
+        threshold_secs: daemon.pause_threshold_ms as f32 / 1000.0,
+        separator: pause_split::PauseBreak::from_str(&daemon.pause_break).unwrap_or_else(|| {
+            warn!("Unknown pause_break '{}', using sentence", daemon.pause_break);
+            pause_split::PauseBreak::Sentence
+        }),
+    });
+    model_spec.silence_pad_ms = daemon.final_pass_silence_pad_ms;
+    model_spec.preview_window_seconds = daemon.preview_window_seconds;
+    model_spec.drop_undecodable_words = daemon.drop_undecodable_words;
+
+    if daemon.auto_model_by_memory {
+        match model_selector::available_memory_mb() {
+            Some(available_mb) => model_spec.fit_to_memory(available_mb),
+            None => warn!("Couldn't read available memory from /proc/meminfo, keeping default chunk length"),
+        }
+    }
+
+    Ok(model_spec)
+}
+
+/// Settings used when the config file is missing or invalid
+fn default_config() -> Config {
+    Config {
+        daemon: DaemonConfig {
+            audio_device: "default".to_string(),
+            sample_rate: "16000".to_string(),
+            model: default_model(),
+            final_pass_chain: default_final_pass_chain(),
+            language: default_language(),
+            language_engine_map: Default::default(),
+            auto_model_by_memory: false,
+            chunk_parallelism: default_chunk_parallelism(),
+            enable_acronyms: default_enable_acronyms(),
+            enable_punctuation: false,
+            suppress_trailing_punctuation_short: false,
+            trailing_punctuation_min_words: default_trailing_punctuation_min_words(),
+            enable_capitalization: default_enable_capitalization(),
+            enable_grammar: default_enable_grammar(),
+            enable_edge_dedup: false,
+            enable_submit_command: default_enable_submit_command(),
+            enable_code_case: default_enable_code_case(),
+            enable_markdown_commands: false,
+            enable_punctuation_commands: false,
+            punctuation_commands: std::collections::HashMap::new(),
+            enable_processor_toggle_commands: false,
+            markdown_apps: Vec::new(),
+            trim_output: default_trim_output(),
+            enable_sanitization: default_enable_sanitization(),
+            unicode_normalization: default_unicode_normalization(),
+            prepend_space: false,
+            append_space: false,
+            notify_on_confirm: false,
+            emit_dbus_only: false,
+            enable_sound_cues: false,
+            start_sound: String::new(),
+            confirm_sound: String::new(),
+            show_typing_state: default_show_typing_state(),
+            suppress_self_injection: default_suppress_self_injection(),
+            app_blocklist: Vec::new(),
+            app_allowlist: Vec::new(),
+            injection_method: default_injection_method(),
+            output_mode: default_output_mode(),
+            post_processing_order: Vec::new(),
+            dictionary_files: Vec::new(),
+            silence_threshold_db: default_silence_threshold_db(),
+            silence_timeout_ms: 0,
+            silence_detector: default_silence_detector(),
+            wake_word: String::new(),
+            capture_warmup_ms: default_capture_warmup_ms(),
+            debug_audio: default_debug_audio(),
+            session_audio_keep: default_session_audio_keep(),
+            session_audio_max_mb: 0,
+            vad_backend: default_vad_backend(),
+            vad_threshold: default_vad_threshold(),
+            vad_energy_threshold_db: default_vad_energy_threshold_db(),
+            trailing_buffer_ms: default_trailing_buffer_ms(),
+            audio_backend: default_audio_backend(),
+            pipewire_reconnect_backoff_ms: default_pipewire_reconnect_backoff_ms(),
+            pipewire_reconnect_backoff_max_ms: default_pipewire_reconnect_backoff_max_ms(),
+            audio_preprocess_command: String::new(),
+            input_gain: default_input_gain(),
+            auto_gain: false,
+            idle_release_timeout_secs: default_idle_release_timeout_secs(),
+            media_resume_delay_ms: default_media_resume_delay_ms(),
+            media_control: default_media_control(),
+            stop_behavior: default_stop_behavior(),
+            double_tap_ms: 0,
+            interrupt_closing: default_interrupt_closing(),
+            queue_startup_commands: default_queue_startup_commands(),
+            engine_idle_timeout_secs: default_engine_idle_timeout_secs(),
+            max_audio_buffer_seconds: default_max_audio_buffer_seconds(),
+            audio_buffer_spill: default_audio_buffer_spill(),
+            enable_metrics_server: default_enable_metrics_server(),
+            metrics_port: default_metrics_port(),
+            small_model_max_seconds: default_small_model_max_seconds(),
+            preview_interval_ms: default_preview_interval_ms(),
+            preview_window_seconds: 0,
+            commit_display_ms: 0,
+            final_pass_silence_pad_ms: 0,
+            drop_undecodable_words: default_drop_undecodable_words(),
+            engine_timeout_ms: 0,
+            min_confidence: 0.0,
+            trim_silence: false,
+            silence_margin_ms: default_silence_margin_ms(),
+            split_on_pauses: false,
+            pause_threshold_ms: default_pause_threshold_ms(),
+            pause_break: default_pause_break(),
+            startup_fade_duration_ms: default_startup_fade_duration_ms(),
+            startup_fade_easing: default_startup_fade_easing(),
+            closing_easing: default_closing_easing(),
+            monitor_backend: default_monitor_backend(),
+            fallback_monitor: String::new(),
+            overlay_renderer: default_overlay_renderer(),
+            overlay_position: default_overlay_position(),
+            overlay_text_color: default_overlay_text_color(),
+            overlay_text_opacity: default_overlay_text_opacity(),
+            overlay_text_max_width: 0.0,
+            listening_placeholder: default_listening_placeholder(),
+            spectrum_idle_animation: false,
+            spectrum_idle_threshold: default_spectrum_idle_threshold(),
+            spectrum_gain: default_spectrum_gain(),
+            spectrum_gamma: default_spectrum_gamma(),
+            spectrum_peak_hold: false,
+            spectrum_peak_decay: default_spectrum_peak_decay(),
+            spectrum_band_count: default_spectrum_band_count(),
+            spectrum_smoothing: 0.0,
+        }
+    }
+}
+
 /// Watch dictionary files and reload on changes.
 async fn watch_dictionary_files(user_dict: Arc<UserDictionary>) -> Result<()> {
     let paths = user_dict.watch_paths();
@@ -204,6 +891,132 @@ async fn watch_dictionary_files(user_dict: Arc<UserDictionary>) -> Result<()> {
     Ok(())
 }
 
+/// Max time between state machine loop iterations before the watchdog stops pinging.
+/// Idle/Recording iterations wait at most 100ms for commands.
+const LOOP_STALL_BUDGET: Duration = Duration::from_secs(10);
+
+/// Tick budget for a single Processing iteration (final transcription + typing)
+const PROCESSING_STALL_BUDGET: Duration = Duration::from_secs(300);
+
+/// How long a new session waits for a final pass abandoned by engine_timeout_ms to
+/// stop before it gives up on the shared engine and loads a fresh one
+const ABANDONED_PASS_GRACE: Duration = Duration::from_secs(2);
+
+/// How long the overlay closing animation is given before the GUI is hidden
+const CLOSING_DELAY: Duration = Duration::from_millis(slint_gui::CLOSING_DURATION.as_millis() as u64 + 50);
+
+/// Brief collapse flash so a refused start is visible
+async fn show_refused_start(gui_control_tx: &broadcast::Sender<GuiControl>) {
+    let _ = gui_control_tx.send(GuiControl::SetClosing);
+    tokio::time::sleep(CLOSING_DELAY).await;
+    let _ = gui_control_tx.send(GuiControl::SetHidden);
+}
+
+/// Wait out the overlay closing animation.
+///
+/// When `interruptible`, a command arriving during the delay ends it early and is
+/// returned so the Idle state can handle it right away (e.g. a StartRecording issued
+/// immediately after confirming).
+async fn wait_for_closing(
+    command_rx: &mut mpsc::Receiver<DaemonCommand>,
+    interruptible: bool,
+) -> Option<DaemonCommand> {
+    if !interruptible {
+        tokio::time::sleep(CLOSING_DELAY).await;
+        return None;
+    }
+
+    tokio::select! {
+        _ = tokio::time::sleep(CLOSING_DELAY) => None,
+        cmd = command_rx.recv() => cmd,
+    }
+}
+
+/// Reduce commands received while the daemon was still starting to the one to run.
+///
+/// Shutdown always wins. Otherwise the session commands (start/stop/confirm) are
+/// replayed as toggles on a daemon with no session: only a trailing StartRecording
+/// survives, since stopping or confirming a session that never started is a no-op.
+/// Other commands need a running daemon's context and are dropped.
+fn coalesce_startup_commands(commands: Vec<DaemonCommand>) -> Option<DaemonCommand> {
+    if commands.iter().any(|cmd| matches!(cmd, DaemonCommand::Shutdown)) {
+        return Some(DaemonCommand::Shutdown);
+    }
+
+    let last_session_command = commands.iter().rev().find(|cmd| {
+        matches!(
+            cmd,
+            DaemonCommand::StartRecording
+                | DaemonCommand::StopRecording
+                | DaemonCommand::Confirm
+                | DaemonCommand::Cancel
+        )
+    });
+    for cmd in &commands {
+        if !matches!(
+            cmd,
+            DaemonCommand::StartRecording
+                | DaemonCommand::StopRecording
+                | DaemonCommand::Confirm
+                | DaemonCommand::Cancel
+        ) {
+            warn!("Dropping {:?} received during startup", cmd);
+        }
+    }
+
+    match last_session_command {
+        Some(DaemonCommand::StartRecording) => Some(DaemonCommand::StartRecording),
+        _ => None,
+    }
+}
+
+/// The command to act on while recording, after `stop_behavior` and the
+/// double-tap gesture are applied
+fn resolve_recording_command(
+    cmd: DaemonCommand,
+    stop_behavior: StopBehavior,
+    since_start: Duration,
+    double_tap: Duration,
+) -> DaemonCommand {
+    let cmd = stop_behavior.resolve(cmd);
+    let is_tap = matches!(cmd, DaemonCommand::StartRecording | DaemonCommand::Confirm);
+    let resolved = dbus_control::resolve_double_tap(cmd, since_start, double_tap);
+    if is_tap && matches!(resolved, DaemonCommand::Cancel) {
+        info!("Double tap {}ms after starting, cancelling", since_start.as_millis());
+    }
+    resolved
+}
+
+/// Apply `profile`'s sanitization rules to `text`, or return it unchanged when
+/// sanitization is disabled
+fn sanitize_for(profile: &app_profile::AppProfile, text: &str, enabled: bool) -> Result<String> {
+    if !enabled {
+        return Ok(text.to_string());
+    }
+    SanitizationProcessor::new(profile.sanitization.clone(), profile.category).process(text)
+}
+
+/// Sanitize `text` for `profile`, then normalize it to `form`: exactly what gets typed
+fn prepare_output(profile: &app_profile::AppProfile, text: &str, enabled: bool, form: UnicodeForm) -> Result<String> {
+    Ok(form.apply(&sanitize_for(profile, text, enabled)?))
+}
+
+/// Short unique ID for correlating one recording session's logs
+fn new_session_id() -> String {
+    use std::hash::BuildHasher;
+    // RandomState is seeded differently per instance, so this is unpredictable enough for log tags
+    let hash = std::collections::hash_map::RandomState::new().hash_one(epoch_ms());
+    format!("{:08x}", hash as u32)
+}
+
+/// Current time in milliseconds since the Unix epoch
+fn epoch_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
 /// Health state shared between subsystems and D-Bus service.
 pub struct HealthState {
     /// Whether audio is flowing (updated by audio forwarding thread)
@@ -216,6 +1029,9 @@ pub struct HealthState {
     pub last_audio_timestamp_ms: AtomicU64,
     /// Last error message (if any)
     pub last_error: RwLock<Option<String>>,
+    /// Deadline (ms since epoch) by which the state machine loop must tick again.
+    /// 0 = loop not started yet.
+    pub loop_deadline_ms: AtomicU64,
 }
 
 impl HealthState {
@@ -226,9 +1042,22 @@ impl HealthState {
             gui_healthy: AtomicBool::new(false),
             last_audio_timestamp_ms: AtomicU64::new(0),
             last_error: RwLock::new(None),
+            loop_deadline_ms: AtomicU64::new(0),
         }
     }
 
+    /// Record a state machine loop tick; the loop must tick again within `budget`.
+    pub fn touch_loop(&self, budget: Duration) {
+        let deadline = epoch_ms() + budget.as_millis() as u64;
+        self.loop_deadline_ms.store(deadline, Ordering::Relaxed);
+    }
+
+    /// Whether the state machine loop is within its tick budget (true before it starts)
+    pub fn is_loop_responsive(&self) -> bool {
+        let deadline = self.loop_deadline_ms.load(Ordering::Relaxed);
+        deadline == 0 || epoch_ms() <= deadline
+    }
+
     /// Check if all subsystems are healthy enough to send watchdog keepalive
     pub fn is_healthy(&self) -> bool {
         // Engine health is the critical check - if it loaded, we're functional
@@ -294,8 +1123,11 @@ impl DeviceManager {
             debug!("DeviceManager: Cleared idle timer (restarting before timeout)");
         }
 
-        // Clear the needs_recreate flag (we'll recreate anyway if backend is None)
-        self.needs_recreate.swap(false, std::sync::atomic::Ordering::SeqCst);
+        // A device was plugged in or removed since the backend was created: rebuild it
+        // so a new mic (or a changed default) is captured instead of the stale stream
+        if self.needs_recreate.swap(false, std::sync::atomic::Ordering::SeqCst) && self.backend.take().is_some() {
+            info!("DeviceManager: Audio devices changed, recreating backend");
+        }
 
         // Recreate backend if it was released (dropped after idle) or device changed
         if self.backend.is_none() {
@@ -476,12 +1308,14 @@ async fn drain_audio_channel(
     drained
 }
 
-#[tokio::main]
-pub async fn run() -> Result<()> {
+/// Run the daemon. `log_level` sets the default tracing level (INFO when None);
+/// RUST_LOG directives still override it.
+pub fn run(log_level: Option<tracing::Level>) -> Result<()> {
     tracing_subscriber::fmt()
         .with_env_filter(
-            tracing_subscriber::EnvFilter::from_default_env()
-                .add_directive(tracing::Level::INFO.into()),
+            tracing_subscriber::EnvFilter::builder()
+                .with_default_directive(log_level.unwrap_or(tracing::Level::INFO).into())
+                .from_env_lossy(),
         )
         .init();
 
@@ -489,45 +1323,65 @@ pub async fn run() -> Result<()> {
 
     let config = load_config().unwrap_or_else(|e| {
         warn!("Failed to load config: {}, using defaults", e);
-        Config {
-            daemon: DaemonConfig {
-                audio_device: "default".to_string(),
-                sample_rate: "16000".to_string(),
-                model: default_model(),
-                enable_acronyms: default_enable_acronyms(),
-                enable_punctuation: default_enable_punctuation(),
-                enable_grammar: default_enable_grammar(),
-                silence_threshold_db: default_silence_threshold_db(),
-                debug_audio: default_debug_audio(),
-                trailing_buffer_ms: default_trailing_buffer_ms(),
-                audio_backend: default_audio_backend(),
-                idle_release_timeout_secs: default_idle_release_timeout_secs(),
-                media_resume_delay_ms: default_media_resume_delay_ms(),
-                engine_idle_timeout_secs: default_engine_idle_timeout_secs(),
-            }
-        }
+        default_config()
     });
 
-    let sample_rate: u32 = config.daemon.sample_rate.parse()
+    let renderer = slint_gui::OverlayRenderer::from_str(&config.daemon.overlay_renderer)
+        .unwrap_or_else(|| {
+            warn!("Unknown overlay_renderer '{}' (expected gpu or software), using gpu", config.daemon.overlay_renderer);
+            slint_gui::OverlayRenderer::Gpu
+        });
+    // The renderer is chosen through the process environment, which is only
+    // safe to modify while this is the sole thread
+    renderer.apply();
+
+    tokio::runtime::Runtime::new()?.block_on(run_daemon(config, renderer))
+}
+
+async fn run_daemon(config: Config, renderer: slint_gui::OverlayRenderer) -> Result<()> {
+    // Capture runs at the configured rate; audio is resampled to the engine rate
+    // before it reaches the engine, VAD, and spectrum
+    let capture_rate: u32 = config.daemon.sample_rate.parse()
         .unwrap_or_else(|_| {
             warn!("Invalid sample_rate '{}', defaulting to 16000", config.daemon.sample_rate);
             16000
         });
+    let sample_rate = parakeet_engine::PARAKEET_SAMPLE_RATE;
+    if capture_rate != sample_rate {
+        info!("Resampling capture audio {} Hz -> {} Hz for the engine", capture_rate, sample_rate);
+    }
 
     // Convert silence threshold from dB to linear RMS value
     let silence_threshold = db_to_linear(config.daemon.silence_threshold_db);
     info!("Silence threshold: {:.1}dB ({:.6} linear)", config.daemon.silence_threshold_db, silence_threshold);
 
     info!("Config loaded: audio_device={}, sample_rate={}",
-          config.daemon.audio_device, sample_rate);
+          config.daemon.audio_device, capture_rate);
 
     // Initialize user dictionary
+    let dictionary_files: Vec<std::path::PathBuf> = config.daemon.dictionary_files.iter()
+        .map(|path| std::path::PathBuf::from(shellexpand::tilde(path).as_ref()))
+        .collect();
     let user_dict = Arc::new(UserDictionary::new().unwrap_or_else(|e| {
         warn!("Failed to initialize user dictionary: {}, spell checking will use defaults only", e);
         UserDictionary::empty()
-    }));
+    }).with_extra_files(dictionary_files));
     info!("User dictionary initialized");
 
+    let processor_order = post_processing::resolve_order(&config.daemon.post_processing_order);
+    let command_vocabulary = Arc::new(post_processing::CommandVocabulary::new(&config.daemon.punctuation_commands));
+    let acronyms = post_processing::AcronymProcessor::from_user_config();
+    let spacing = SpacingOptions {
+        trim: config.daemon.trim_output,
+        prepend_space: config.daemon.prepend_space,
+        append_space: config.daemon.append_space,
+    };
+    info!("Post-processing order: {:?}", processor_order);
+    let unicode_form = UnicodeForm::from_str(&config.daemon.unicode_normalization).unwrap_or_else(|| {
+        warn!("Unknown unicode_normalization '{}', using nfc", config.daemon.unicode_normalization);
+        UnicodeForm::Nfc
+    });
+
     // Spawn file watcher for dictionary hot-reload
     let user_dict_watcher = Arc::clone(&user_dict);
     tokio::spawn(async move {
@@ -537,10 +1391,38 @@ pub async fn run() -> Result<()> {
     });
 
     // Parse model specification (Parakeet only)
-    let model_spec = ModelSpec::parse(&config.daemon.model)
-        .map_err(|e| anyhow::anyhow!("Invalid model '{}': {}", config.daemon.model, e))?;
-
-    info!("Model: {}", model_spec);
+    let model_spec = model_spec_from_config(&config.daemon)?;
+
+    let final_pass_chain = final_pass::parse_chain(&config.daemon.final_pass_chain);
+    info!(
+        "Final pass chain: {}",
+        final_pass_chain.iter().map(|s| s.name()).collect::<Vec<_>>().join(" → ")
+    );
+
+    // Final-pass models per spoken language, sharing the default model's settings
+    let mut language_specs = std::collections::HashMap::new();
+    for (language, spec) in &config.daemon.language_engine_map {
+        match ModelSpec::parse(spec) {
+            Ok(parsed) => {
+                let spec = model_spec.for_language(&parsed.model_name);
+                if spec.is_available() {
+                    language_specs.insert(language.trim().to_lowercase(), spec);
+                } else {
+                    warn!("Model for language '{}' not found at {:?}, ignoring", language, spec.model_path());
+                }
+            }
+            Err(e) => warn!("Ignoring invalid language_engine_map entry '{}': {}", language, e),
+        }
+    }
+    let language_setting = config.daemon.language.trim().to_lowercase();
+    // Loaded per-language engines, kept between sessions like the session engine.
+    // A final pass takes its engine out while running, so a pass abandoned by
+    // engine_timeout_ms never shares it with the next session.
+    let language_engines: Arc<std::sync::Mutex<std::collections::HashMap<String, Arc<dyn TranscriptionEngine>>>> =
+        Default::default();
+    if !language_specs.is_empty() {
+        info!("Language: {}, final-pass models for: {:?}", language_setting, language_specs.keys().collect::<Vec<_>>());
+    }
 
     // Validate that configured model is available
     if !model_spec.is_available() {
@@ -554,17 +1436,30 @@ pub async fn run() -> Result<()> {
     // Create shared health state
     let health_state = Arc::new(HealthState::new());
 
-    // Spawn dedicated watchdog task — decoupled from the event loop so long typing/processing
-    // operations don't starve the watchdog and cause systemd to kill us.
-    tokio::spawn(async move {
-        let mut interval = tokio::time::interval(Duration::from_secs(15));
-        loop {
-            interval.tick().await;
-            if let Err(e) = notify(false, [(STATE_WATCHDOG, "1")].iter()) {
-                debug!("Failed to send watchdog keepalive: {}", e);
+    // Spawn dedicated watchdog task when systemd requests it (WATCHDOG_USEC). Pings are gated
+    // on the state machine loop having ticked within its budget, so a wedged loop gets
+    // restarted while long typing/processing operations get a larger budget.
+    let watchdog_usec = std::env::var("WATCHDOG_USEC").ok().and_then(|v| v.parse::<u64>().ok());
+    if let Some(usec) = watchdog_usec.filter(|&usec| usec > 0) {
+        let ping_interval = Duration::from_micros(usec / 2);
+        info!("systemd watchdog enabled (ping every {:?})", ping_interval);
+        let health_watchdog = Arc::clone(&health_state);
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(ping_interval);
+            loop {
+                interval.tick().await;
+                if !health_watchdog.is_loop_responsive() {
+                    warn!("State machine loop unresponsive, withholding watchdog keepalive");
+                    continue;
+                }
+                if let Err(e) = notify(false, [(STATE_WATCHDOG, "1")].iter()) {
+                    debug!("Failed to send watchdog keepalive: {}", e);
+                }
             }
-        }
-    });
+        });
+    } else {
+        debug!("systemd watchdog not enabled (WATCHDOG_USEC unset)");
+    }
 
     // Create audio channel (shared between DeviceManager and processing)
     let (audio_tx, audio_rx) = mpsc::unbounded_channel::<Vec<i16>>();
@@ -573,7 +1468,7 @@ pub async fn run() -> Result<()> {
     // Create GUI channels for integrated communication
     let (gui_control_tx, _) = broadcast::channel::<GuiControl>(100);
     let (spectrum_tx, _) = broadcast::channel::<Vec<f32>>(50);
-    let (gui_status_tx, mut gui_status_rx) = mpsc::channel::<GuiStatus>(10);
+    let (gui_status_tx, mut gui_status_rx) = mpsc::channel::<GuiStatus>(GUI_STATUS_CHANNEL_CAPACITY);
 
     // Parse audio device config
     let audio_device_name = if config.daemon.audio_device.is_empty() || config.daemon.audio_device == "default" {
@@ -589,14 +1484,28 @@ pub async fn run() -> Result<()> {
             BackendType::Auto
         });
 
+    // Without a configured device, reuse the one last picked from the tray if it's connected
+    let audio_device_name = audio_device_name.or_else(|| {
+        let saved = last_device::load()?;
+        let available = audio_backend::list_devices(backend_type)
+            .map_err(|e| warn!("Can't list devices to restore {}: {}", saved, e))
+            .ok()?;
+        let restored = last_device::restore(Some(saved), &available)?;
+        info!("Using last selected device: {}", restored);
+        Some(restored)
+    });
+
     // Create DeviceManager with eager-loaded audio backend
     info!("Creating DeviceManager with pre-loaded audio backend...");
     let device_manager_config = DeviceManagerConfig {
         backend_type,
         backend_config: AudioBackendConfig {
             device_name: audio_device_name.clone(),
-            sample_rate,
+            sample_rate: capture_rate,
             silence_threshold,
+            reconnect_backoff_min_ms: config.daemon.pipewire_reconnect_backoff_ms,
+            reconnect_backoff_max_ms: config.daemon.pipewire_reconnect_backoff_max_ms,
+            warmup_ms: config.daemon.capture_warmup_ms,
         },
         idle_release_timeout_secs: config.daemon.idle_release_timeout_secs,
     };
@@ -606,13 +1515,131 @@ pub async fn run() -> Result<()> {
     device_manager.spawn_device_watcher();
     info!("Audio streams pre-loaded and ready (fast startup enabled)");
 
-    let keyboard = Arc::new(KeyboardInjector::new());
+    let output_mode = OutputMode::from_str(&config.daemon.output_mode).unwrap_or_else(|| {
+        warn!("Unknown output_mode '{}', using both", config.daemon.output_mode);
+        OutputMode::Both
+    });
+    let keyboard = Arc::new(KeyboardInjector::with_output_mode(output_mode));
+    let injection_method = InjectionMethod::from_str(&config.daemon.injection_method).unwrap_or_else(|| {
+        warn!("Unknown injection_method '{}', using auto", config.daemon.injection_method);
+        InjectionMethod::Auto
+    });
+    let stop_behavior = StopBehavior::from_str(&config.daemon.stop_behavior).unwrap_or_else(|| {
+        warn!("Unknown stop_behavior '{}', using cancel", config.daemon.stop_behavior);
+        StopBehavior::Cancel
+    });
+
+    // Voice activity detection (drives speaking state for the overlay)
+    let vad_backend = vad::VadBackend::from_str(&config.daemon.vad_backend).unwrap_or_else(|| {
+        warn!("Unknown vad_backend '{}', using energy", config.daemon.vad_backend);
+        vad::VadBackend::Energy
+    });
+    let vad_stream = Arc::new(std::sync::Mutex::new(vad::VadStream::new(vad::create_vad_backend(
+        vad_backend,
+        config.daemon.vad_threshold,
+        config.daemon.vad_energy_threshold_db,
+        sample_rate,
+    ))));
+    let vad_speaking = Arc::new(AtomicBool::new(false));
+    let silence_timeout = silence_timeout::SilenceTimeout::new(config.daemon.silence_timeout_ms);
+    let silence_detector = silence_timeout::SpeechDetector::from_str(&config.daemon.silence_detector)
+        .unwrap_or_else(|| {
+            warn!("Unknown silence_detector '{}', using energy", config.daemon.silence_detector);
+            silence_timeout::SpeechDetector::Energy
+        });
+    // The backends already drop chunks below silence_threshold_db, so the energy
+    // detector needs the (higher) speech threshold to ever see silence
+    let speech_threshold = db_to_linear(config.daemon.vad_energy_threshold_db);
+    if silence_timeout.is_enabled() {
+        info!("Auto-confirm after {}ms of silence ({:?})", config.daemon.silence_timeout_ms, silence_detector);
+    }
+
+    // Create watch channel for state sharing with D-Bus
+    let (state_tx, state_rx) = tokio::sync::watch::channel(DaemonState::Idle);
+
+    // Last finalized transcription (pre-sanitization), shared with D-Bus for RepeatLast
+    let last_transcription: Arc<RwLock<Option<String>>> = Arc::new(RwLock::new(None));
+    // ID of the active recording session (None when idle), exposed over D-Bus
+    let current_session_id: Arc<RwLock<Option<String>>> = Arc::new(RwLock::new(None));
+
+    // Last finished session, written out on demand by DumpDiagnostics (`voice-dictation diagnose --bundle`)
+    let last_diagnostics: Arc<RwLock<Option<debug_audio::SessionDiagnostics>>> = Arc::new(RwLock::new(None));
+
+    let app_filter = window_detect::AppFilter::new(&config.daemon.app_blocklist, &config.daemon.app_allowlist);
+
+    // Register D-Bus before the slow GUI/engine startup so early commands reach the
+    // daemon; they queue in the command channel until the state machine starts
+    let (dbus_conn, command_sender, mut command_rx) = dbus_control::create_dbus_service(
+        state_rx,
+        Arc::clone(&health_state),
+        Arc::clone(&last_transcription),
+        Arc::clone(&current_session_id),
+        Arc::clone(&last_diagnostics),
+        app_filter.clone(),
+        gui_control_tx.clone(),
+    )
+    .await?;
+    // dbus_conn stays alive for the daemon's lifetime (also sends notifications)
+
+    let media_control = media_control::MediaControl::from_str(&config.daemon.media_control)
+        .unwrap_or_else(|| {
+            warn!("Unknown media_control '{}', using auto", config.daemon.media_control);
+            media_control::MediaControl::Auto
+        });
+    let media_controller = media_control::MediaController::new(media_control, dbus_conn.clone());
 
     // Spawn integrated GUI
     info!("Spawning integrated GUI...");
     let gui_control_tx_gui = gui_control_tx.clone();
     let spectrum_tx_gui = spectrum_tx.clone();
     let runtime_handle = tokio::runtime::Handle::current();
+    let gui_config = slint_gui::GuiConfig {
+        startup_fade_duration_ms: config.daemon.startup_fade_duration_ms,
+        startup_fade_easing: slint_gui::Easing::from_str(&config.daemon.startup_fade_easing)
+            .unwrap_or_else(|| {
+                warn!("Unknown startup_fade_easing '{}', using ease-out", config.daemon.startup_fade_easing);
+                slint_gui::Easing::EaseOut
+            }),
+        closing_easing: slint_gui::Easing::from_str(&config.daemon.closing_easing)
+            .unwrap_or_else(|| {
+                warn!("Unknown closing_easing '{}', using ease-in", config.daemon.closing_easing);
+                slint_gui::Easing::EaseIn
+            }),
+        text_color: slint_gui::parse_hex_color(&config.daemon.overlay_text_color)
+            .unwrap_or_else(|| {
+                warn!("Invalid overlay_text_color '{}', using white", config.daemon.overlay_text_color);
+                slint_gui::GuiConfig::default().text_color
+            }),
+        text_opacity: config.daemon.overlay_text_opacity.clamp(0.0, 1.0),
+        monitor_backend: slint_gui::MonitorBackend::from_str(&config.daemon.monitor_backend)
+            .unwrap_or_else(|| {
+                warn!("Unknown monitor_backend '{}', using auto", config.daemon.monitor_backend);
+                slint_gui::MonitorBackend::Auto
+            }),
+        fallback_monitor: config.daemon.fallback_monitor.clone(),
+        spectrum_idle_animation: config.daemon.spectrum_idle_animation,
+        spectrum_idle_threshold: config.daemon.spectrum_idle_threshold,
+        spectrum_curve: slint_gui::SpectrumCurve {
+            gain: config.daemon.spectrum_gain,
+            gamma: config.daemon.spectrum_gamma,
+        },
+        spectrum_peak_hold: config.daemon.spectrum_peak_hold,
+        spectrum_peak_decay: config.daemon.spectrum_peak_decay,
+        spectrum_band_count: config.daemon.spectrum_band_count,
+        spectrum_smoothing: config.daemon.spectrum_smoothing,
+        text_max_width: if config.daemon.overlay_text_max_width > 0.0 {
+            config.daemon.overlay_text_max_width
+        } else {
+            slint_gui::MAX_TEXT_WIDTH
+        },
+        position: slint_gui::OverlayPosition::from_str(&config.daemon.overlay_position)
+            .unwrap_or_else(|| {
+                warn!("Unknown overlay_position '{}', using screen", config.daemon.overlay_position);
+                slint_gui::OverlayPosition::Screen
+            }),
+        listening_placeholder: config.daemon.listening_placeholder.clone(),
+        renderer,
+    };
 
     let _gui_handle = tokio::task::spawn_blocking(move || {
         slint_gui::run_integrated(
@@ -620,15 +1647,23 @@ pub async fn run() -> Result<()> {
             spectrum_tx_gui,
             gui_status_tx,
             runtime_handle,
+            gui_config,
         )
     });
 
     // Wait for GUI to initialize (with timeout)
     info!("Waiting for GUI to initialize...");
-    let gui_available = match tokio::time::timeout(
-        Duration::from_secs(5),
-        gui_status_rx.recv()
-    ).await {
+    let gui_available = match tokio::time::timeout(Duration::from_secs(5), async {
+        // Transition events may race ahead of Ready; only Ready/Error/ShuttingDown decide init
+        loop {
+            match gui_status_rx.recv().await {
+                Some(GuiStatus::TransitionComplete { from, to }) => {
+                    debug!("Ignoring GUI transition {:?} -> {:?} during init", from, to);
+                }
+                other => break other,
+            }
+        }
+    }).await {
         Ok(Some(GuiStatus::Ready)) => {
             info!("GUI ready");
             true
@@ -638,10 +1673,8 @@ pub async fn run() -> Result<()> {
             warn!("Continuing without GUI overlay - daemon will operate in headless mode");
             false
         }
-        Ok(Some(GuiStatus::TransitionComplete { .. })) => {
-            warn!("Unexpected TransitionComplete during init, assuming GUI unavailable");
-            false
-        }
+        // Filtered out by the loop above
+        Ok(Some(GuiStatus::TransitionComplete { .. })) => false,
         Ok(Some(GuiStatus::ShuttingDown)) => {
             warn!("GUI is shutting down during init, continuing without GUI");
             false
@@ -664,23 +1697,48 @@ pub async fn run() -> Result<()> {
         info!("Running in headless mode (no visual overlay)");
     }
 
+    // Keep draining GUI status after init so the bounded channel never fills up and
+    // late errors are reflected in health state instead of being dropped
+    let health_gui = Arc::clone(&health_state);
+    tokio::spawn(async move {
+        while let Some(status) = gui_status_rx.recv().await {
+            match status {
+                GuiStatus::TransitionComplete { from, to } => {
+                    debug!("GUI transition complete: {:?} -> {:?}", from, to);
+                }
+                GuiStatus::Ready => {
+                    info!("GUI reported ready");
+                    health_gui.gui_healthy.store(true, Ordering::Relaxed);
+                }
+                GuiStatus::Error(e) => {
+                    error!("GUI error: {}", e);
+                    health_gui.gui_healthy.store(false, Ordering::Relaxed);
+                }
+                GuiStatus::ShuttingDown => {
+                    warn!("GUI is shutting down");
+                    health_gui.gui_healthy.store(false, Ordering::Relaxed);
+                }
+            }
+        }
+        debug!("GUI status channel closed");
+    });
+
+    // Audio past max_audio_buffer_seconds spills here (or is dropped) during long sessions
+    let audio_spill_path = config.daemon.audio_buffer_spill.then(|| {
+        std::env::temp_dir().join(format!("voice-dictation-audio-{}.raw", std::process::id()))
+    });
+
     // Pre-load engine at startup for instant recording start
     info!("Pre-loading Parakeet engine (blocking call before D-Bus)...");
-    let mut preview_engine: Option<Arc<dyn TranscriptionEngine>> = Some(model_spec.create_engine(sample_rate)?);
+    let mut preview_engine: Option<Arc<dyn TranscriptionEngine>> = Some(model_spec.create_engine(sample_rate, config.daemon.max_audio_buffer_seconds, audio_spill_path.clone())?);
     let mut engine_stopped_at: Option<Instant> = None;
+    // Final pass cut off by engine_timeout_ms, possibly still reading the session engine
+    let mut abandoned_final_pass: Option<tokio::task::JoinHandle<_>> = None;
     info!("Parakeet engine loaded and ready");
 
     // Mark engine as healthy after successful load
     health_state.engine_healthy.store(true, Ordering::Relaxed);
 
-    // Create watch channel for state sharing with D-Bus
-    let (state_tx, state_rx) = tokio::sync::watch::channel(DaemonState::Idle);
-
-    // Create D-Bus service for control commands with health state
-    let (dbus_conn, command_sender, mut command_rx) =
-        dbus_control::create_dbus_service(state_rx, Arc::clone(&health_state)).await?;
-    let _dbus_conn = dbus_conn; // Keep alive
-
     #[cfg(feature = "tray")]
     let _tray_handle = {
         let tray_tx = command_sender.lock().await.clone();
@@ -688,6 +1746,24 @@ pub async fn run() -> Result<()> {
         tray::spawn_tray(tray_rx, tray_tx, backend_type, audio_device_name.clone()).await
     };
 
+    let metrics = Arc::new(metrics::Metrics::new(&config.daemon.audio_backend));
+    if config.daemon.enable_metrics_server {
+        let metrics_server = Arc::clone(&metrics);
+        let port = config.daemon.metrics_port;
+        let metrics_state_rx = state_tx.subscribe();
+        tokio::spawn(async move {
+            if let Err(e) = metrics::serve_metrics(metrics_server, port, metrics_state_rx).await {
+                error!("Metrics exporter error: {}", e);
+            }
+        });
+    }
+
+    let wake_word = wakeword::WakeWord::new(&config.daemon.wake_word);
+    let wake_command_tx = command_sender.lock().await.clone();
+    if wake_word.is_some() {
+        info!("Listening for wake word '{}' while idle", config.daemon.wake_word.trim());
+    }
+
     // Keep command_sender alive (used by D-Bus service)
     let _command_sender = command_sender;
 
@@ -698,13 +1774,38 @@ pub async fn run() -> Result<()> {
         warn!("Failed to notify systemd (Ready): {}", e);
     }
 
+    let markdown_filter = window_detect::AppFilter::new(&[], &config.daemon.markdown_apps);
+    // Set by spoken toggle commands, overriding the enable_* settings
+    let mut processor_overrides = ProcessorOverrides::default();
+
     // State machine variables
     let mut daemon_state = DaemonState::Idle;
     let mut session: Option<RecordingSession> = None;
     let mut audio_task: Option<tokio::task::JoinHandle<()>> = None;
     let mut preview_task: Option<tokio::task::JoinHandle<()>> = None;
-    let mut media_was_playing = false;
+    let mut paused_media: Option<media_control::PausedMedia> = None;
     let mut window_target: Option<window_target::WindowTarget> = None;
+    // Command received while the overlay was closing (or during startup), handled on
+    // the next Idle tick
+    let mut pending_command: Option<DaemonCommand> = None;
+    // Listens for the wake word while idle (None = not listening)
+    let mut wake_listener: Option<wakeword::WakeListener> = None;
+    // Don't retry opening the mic for the wake word before this
+    let mut wake_retry_at: Option<Instant> = None;
+
+    // Commands sent while GUI init and model load were running
+    let mut startup_commands = Vec::new();
+    while let Ok(cmd) = command_rx.try_recv() {
+        startup_commands.push(cmd);
+    }
+    if !startup_commands.is_empty() {
+        if config.daemon.queue_startup_commands {
+            pending_command = coalesce_startup_commands(startup_commands);
+            info!("Commands received during startup, running: {:?}", pending_command);
+        } else {
+            info!("Dropping {} command(s) received during startup", startup_commands.len());
+        }
+    }
     // Cancellation channel for graceful task shutdown
     let (cancel_tx, _cancel_rx) = tokio::sync::watch::channel(false);
 
@@ -713,6 +1814,7 @@ pub async fn run() -> Result<()> {
 
     // ===== PERSISTENT STATE MACHINE LOOP =====
     loop {
+        health_state.touch_loop(LOOP_STALL_BUDGET);
 
         match daemon_state {
             DaemonState::Idle => {
@@ -724,25 +1826,50 @@ pub async fn run() -> Result<()> {
                 // Check engine idle timeout (release ORT sessions to reclaim BFCArena memory)
                 if let Some(stopped_at) = engine_stopped_at {
                     let timeout = Duration::from_secs(config.daemon.engine_idle_timeout_secs);
-                    if stopped_at.elapsed() >= timeout && preview_engine.is_some() {
+                    // The wake word needs the engine loaded while idle
+                    if stopped_at.elapsed() >= timeout && preview_engine.is_some() && wake_word.is_none() {
                         info!("Engine idle timeout expired, releasing ORT sessions to free memory");
                         preview_engine = None;
+                        language_engines.lock().unwrap().clear();
+                        // The last session's diagnostics would otherwise keep the model alive
+                        debug_audio::release_session_audio(&last_diagnostics).await;
                         engine_stopped_at = None;
                         health_state.engine_healthy.store(false, Ordering::Relaxed);
                     }
                 }
 
                 // Wait for D-Bus commands with timeout
-                match tokio::time::timeout(Duration::from_millis(100), command_rx.recv()).await {
+                let next_command = match pending_command.take() {
+                    Some(cmd) => Ok(Some(cmd)),
+                    None => tokio::time::timeout(Duration::from_millis(100), command_rx.recv()).await,
+                };
+                match next_command {
                     Ok(Some(cmd)) => match cmd {
                         DaemonCommand::StartRecording => {
                             info!("Received StartRecording command");
+                            // Free the engine and audio channel, and close the mic so the
+                            // start cue isn't captured
+                            if let Some(listener) = wake_listener.take() {
+                                listener.stop().await;
+                                let _ = device_manager.stop();
+                            }
                             // Capture focused window before pausing media (to lock typing target)
                             window_target = window_target::WindowTarget::capture().await;
                             if let Some(ref wt) = window_target {
                                 info!("Captured window target: class={}", wt.class());
                             }
-                            media_was_playing = pause_media_if_playing();
+
+                            if app_filter.is_active() {
+                                let class = window_target.as_ref().map(|wt| wt.class());
+                                if !app_filter.allows(class) {
+                                    info!("Dictation disabled for focused app '{}', not starting", class.unwrap_or("unknown"));
+                                    window_target = None;
+                                    show_refused_start(&gui_control_tx).await;
+                                    continue;
+                                }
+                                debug!("Dictation allowed for focused app '{}'", class.unwrap_or("unknown"));
+                            }
+                            paused_media = media_controller.pause_if_playing().await;
 
                             // Drain any stale audio data from the channel before starting
                             {
@@ -756,6 +1883,11 @@ pub async fn run() -> Result<()> {
                                 }
                             }
 
+                            // Play the start cue before capture so it isn't recorded
+                            if config.daemon.enable_sound_cues {
+                                sound_cues::play_and_wait(&config.daemon.start_sound, sound_cues::START_CUE_MAX_WAIT).await;
+                            }
+
                             // Start pre-loaded audio streams (fast - no device enumeration)
                             device_manager.start()?;
                             info!("Audio capture started (pre-loaded streams)");
@@ -763,26 +1895,46 @@ pub async fn run() -> Result<()> {
                             // Mark audio as healthy at start
                             health_state.audio_healthy.store(true, Ordering::Relaxed);
 
+                            // Clear the previous session from the persistent engine (the stale
+                            // audio above was drained first); a broken one is recreated below.
+                            // Its diagnostics lose their audio along with it.
+                            debug_audio::release_session_audio(&last_diagnostics).await;
+                            // The abandoned pass was cancelled and stops at its next chunk;
+                            // clearing the engine under it would corrupt both sessions
+                            if let Some(pass) = abandoned_final_pass.take() {
+                                if tokio::time::timeout(ABANDONED_PASS_GRACE, pass).await.is_err() {
+                                    warn!("Abandoned final pass is still running, loading a fresh engine for this session");
+                                    preview_engine = None;
+                                }
+                            }
+                            if let Some(engine) = &preview_engine {
+                                if let Err(e) = engine.clear_buffer() {
+                                    warn!("Couldn't clear the transcription engine, recreating it: {}", e);
+                                    preview_engine = None;
+                                }
+                            }
+
                             // Recreate engine if it was released due to idle timeout
                             if preview_engine.is_none() {
                                 info!("Recreating transcription engine (was released for idle memory savings)...");
-                                preview_engine = Some(model_spec.create_engine(sample_rate)?);
+                                preview_engine = Some(model_spec.create_engine(sample_rate, config.daemon.max_audio_buffer_seconds, audio_spill_path.clone())?);
                                 health_state.engine_healthy.store(true, Ordering::Relaxed);
                                 info!("Engine recreated and ready");
                             }
                             engine_stopped_at = None;
 
-                            // Reset the pre-loaded engine for new session
-                            let engine = preview_engine.as_ref().unwrap();
-                            engine.reset();
-                            let session_engine = Arc::clone(engine);
+                            let session_engine = Arc::clone(preview_engine.as_ref().unwrap());
 
                             // Signal UI to show
                             gui_control_tx.send(GuiControl::SetListening)
                                 .map_err(|e| anyhow::anyhow!("Failed to send SetListening: {}", e))?;
 
                             // Create session
+                            let session_id = new_session_id();
+                            let session_span = info_span!("session", id = %session_id);
+                            *current_session_id.write().await = Some(session_id.clone());
                             session = Some(RecordingSession {
+                                id: session_id,
                                 start_time: Instant::now(),
                                 engine: Arc::clone(&session_engine),
                             });
@@ -790,8 +1942,12 @@ pub async fn run() -> Result<()> {
                             // Reset cancellation flag for new session
                             let _ = cancel_tx.send(false);
 
-                            // Notify for waking preview task when new audio arrives
-                            let audio_notify = Arc::new(tokio::sync::Notify::new());
+                            // Fresh VAD state for this session
+                            if let Ok(mut vad) = vad_stream.lock() {
+                                vad.reset();
+                            }
+                            vad_speaking.store(false, Ordering::Relaxed);
+                            silence_timeout.arm();
 
                             // Start audio processing task
                             let engine_clone = Arc::clone(&session_engine);
@@ -800,7 +1956,29 @@ pub async fn run() -> Result<()> {
                             let mut cancel_rx = cancel_tx.subscribe();
                             let trailing_buffer_ms = config.daemon.trailing_buffer_ms;
                             let health_clone = Arc::clone(&health_state);
-                            let audio_notify_tx = Arc::clone(&audio_notify);
+                            let vad_stream_audio = Arc::clone(&vad_stream);
+                            let vad_speaking_audio = Arc::clone(&vad_speaking);
+                            let silence_timeout_audio = silence_timeout.clone();
+                            let gui_control_tx_audio = gui_control_tx.clone();
+                            let mut resampler = resample::StreamResampler::new(capture_rate, sample_rate);
+                            // Clipping is averaged over 5s windows
+                            let mut input_gain = input_gain::InputGain::new(config.daemon.input_gain, sample_rate as usize * 5)
+                                .with_auto_gain(config.daemon.auto_gain, sample_rate);
+                            let mut preprocessor = if config.daemon.audio_preprocess_command.trim().is_empty() {
+                                None
+                            } else {
+                                // Give up on the command once 2s of audio is stuck in it
+                                match audio_preprocess::AudioPreprocessor::spawn(
+                                    &config.daemon.audio_preprocess_command,
+                                    sample_rate as usize * 2,
+                                ) {
+                                    Ok(pre) => Some(pre),
+                                    Err(e) => {
+                                        warn!("{:#}, using raw audio", e);
+                                        None
+                                    }
+                                }
+                            };
                             audio_task = Some(tokio::spawn(async move {
                                 let mut buffer = Vec::new();
                                 let trailing_duration = Duration::from_millis(trailing_buffer_ms);
@@ -838,7 +2016,12 @@ pub async fn run() -> Result<()> {
                                                     health_clone.last_audio_timestamp_ms.store(now_ms, Ordering::Relaxed);
                                                     health_clone.audio_healthy.store(true, Ordering::Relaxed);
 
+                                                    let samples = resampler.process(&samples);
+
                                                     let samples_f32: Vec<f32> = samples.iter().map(|&s| s as f32 / 32768.0).collect();
+                                                    if silence_detector == silence_timeout::SpeechDetector::Energy {
+                                                        silence_timeout_audio.note_audio(&samples_f32, speech_threshold, now_ms);
+                                                    }
                                                     buffer.extend_from_slice(&samples_f32);
 
                                                     while buffer.len() >= 512 {
@@ -846,10 +2029,42 @@ pub async fn run() -> Result<()> {
                                                         let _ = spectrum_tx_clone.send(chunk);
                                                     }
 
+                                                    // Spectrum stays on raw audio so the overlay doesn't lag
+                                                    let samples = match preprocessor.as_mut() {
+                                                        Some(pre) => pre.process(&samples),
+                                                        None => samples,
+                                                    };
+                                                    if samples.is_empty() {
+                                                        continue;
+                                                    }
+
+                                                    if let Ok(mut vad) = vad_stream_audio.lock() {
+                                                        match vad.push(&samples) {
+                                                            Ok(Some(event)) => {
+                                                                debug!("VAD: {:?}", event);
+                                                                // Sent on the transition so the overlay doesn't wait
+                                                                // for the next preview; that one reports text settling
+                                                                let _ = gui_control_tx_audio.send(GuiControl::UpdateVadState {
+                                                                    is_speaking: event == vad::VadEvent::SpeechStart,
+                                                                    text_settled: false,
+                                                                });
+                                                            }
+                                                            Ok(None) => {}
+                                                            Err(e) => debug!("VAD error: {}", e),
+                                                        }
+                                                        vad_speaking_audio.store(vad.is_speaking(), Ordering::Relaxed);
+                                                        if silence_detector == silence_timeout::SpeechDetector::Vad {
+                                                            silence_timeout_audio.note_vad(vad.is_speaking(), now_ms);
+                                                        }
+                                                    }
+
+                                                    // Gain only feeds the engine: speech and silence are judged on
+                                                    // the mic's real level, so boosted noise can't count as speech
+                                                    let mut samples = samples;
+                                                    input_gain.apply(&mut samples);
                                                     if let Err(e) = engine_clone.process_audio(&samples) {
                                                         error!("Processing error: {}", e);
                                                     }
-                                                    audio_notify_tx.notify_one();
                                                 }
                                                 None => break,
                                             }
@@ -859,29 +2074,52 @@ pub async fn run() -> Result<()> {
                                         }
                                     }
                                 }
+                                // Audio still buffered in the preprocess command belongs to the final pass
+                                if let Some(mut pre) = preprocessor.take() {
+                                    let mut tail = tokio::task::spawn_blocking(move || pre.finish(Duration::from_millis(500)))
+                                        .await
+                                        .unwrap_or_default();
+                                    if !tail.is_empty() {
+                                        input_gain.apply(&mut tail);
+                                        if let Err(e) = engine_clone.process_audio(&tail) {
+                                            error!("Processing error: {}", e);
+                                        }
+                                    }
+                                }
                                 debug!("Audio task: exiting gracefully");
-                            }));
+                            }.instrument(session_span.clone())));
 
                             // Start preview task
                             let engine_clone = Arc::clone(&session_engine);
                             let gui_control_tx_preview = gui_control_tx.clone();
-                            let enable_acronyms = config.daemon.enable_acronyms;
-                            let enable_punctuation = config.daemon.enable_punctuation;
-                            let user_dict_preview = Arc::clone(&user_dict);
+                            let markdown_allowed = markdown_filter.allows(window_target.as_ref().map(|wt| wt.class()));
+                            let pipeline = Pipeline::from_order_with_context(
+                                &processor_order,
+                                |kind| match kind {
+                                    ProcessorKind::Grammar => false,  // grammar disabled in preview for speed
+                                    // A spoken "markdown on" doesn't reach apps outside markdown_apps
+                                    ProcessorKind::Markdown => markdown_allowed
+                                        && processor_overrides.enabled(kind, config.daemon.processor_enabled(kind)),
+                                    _ => processor_overrides.enabled(kind, config.daemon.processor_enabled(kind)),
+                                },
+                                Some(Arc::clone(&user_dict)),
+                                None,
+                                Some(Arc::clone(&command_vocabulary)),
+                                Some(acronyms.clone()),
+                            );
+                            let strip_toggles_preview = config.daemon.enable_processor_toggle_commands;
                             let mut cancel_rx_preview = cancel_tx.subscribe();
-                            let audio_notify_rx = Arc::clone(&audio_notify);
+                            let vad_speaking_preview = Arc::clone(&vad_speaking);
+                            let health_preview = Arc::clone(&health_state);
+                            let preview_interval = Duration::from_millis(config.daemon.preview_interval_ms);
                             preview_task = Some(tokio::spawn(async move {
-                                let pipeline = Pipeline::from_config_with_dict(
-                                    enable_acronyms,
-                                    enable_punctuation,
-                                    false,  // grammar disabled in preview for speed
-                                    Some(user_dict_preview),
-                                );
-
+                                // Fixed refresh cadence; a slow transcription delays the next tick
+                                // instead of queueing a burst of catch-up refreshes
+                                let mut refresh = tokio::time::interval(preview_interval);
+                                refresh.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
                                 let mut last_text = String::new();
                                 let mut last_text_change = Instant::now();
                                 const TEXT_SETTLED_THRESHOLD_MS: u64 = 300;
-                                const MAX_PREVIEW_WAIT_MS: u64 = 200;
 
                                 loop {
                                     tokio::select! {
@@ -892,15 +2130,16 @@ pub async fn run() -> Result<()> {
                                                 break;
                                             }
                                         }
-                                        _ = async {
-                                            // Wake on new audio or after max wait (for settled detection)
-                                            tokio::select! {
-                                                _ = audio_notify_rx.notified() => {}
-                                                _ = tokio::time::sleep(Duration::from_millis(MAX_PREVIEW_WAIT_MS)) => {}
-                                            }
-                                        } => {
+                                        _ = refresh.tick() => {
                                             match engine_clone.get_current_text() {
                                                 Ok(text_raw) => {
+                                                    // Toggles take effect at the end of the session, but
+                                                    // shouldn't show up in the preview
+                                                    let text_raw = if strip_toggles_preview {
+                                                        strip_processor_toggles(&text_raw).0
+                                                    } else {
+                                                        text_raw
+                                                    };
                                                     let text_processed = match pipeline.process(&text_raw) {
                                                         Ok(processed) => processed,
                                                         Err(e) => {
@@ -920,7 +2159,10 @@ pub async fn run() -> Result<()> {
                                                     }
 
                                                     let text_settled = last_text_change.elapsed().as_millis() >= TEXT_SETTLED_THRESHOLD_MS as u128;
-                                                    let is_speaking = !text_processed.is_empty() && !text_settled;
+                                                    // Backends drop silent chunks, so a stale audio timestamp also means silence
+                                                    let last_audio_ms = health_preview.last_audio_timestamp_ms.load(Ordering::Relaxed);
+                                                    let audio_recent = epoch_ms().saturating_sub(last_audio_ms) < TEXT_SETTLED_THRESHOLD_MS;
+                                                    let is_speaking = vad_speaking_preview.load(Ordering::Relaxed) && audio_recent;
 
                                                     let _ = gui_control_tx_preview.send(GuiControl::UpdateTranscription {
                                                         text: text_processed,
@@ -938,23 +2180,66 @@ pub async fn run() -> Result<()> {
                                     }
                                 }
                                 debug!("Preview task: exiting gracefully");
-                            }));
+                            }.instrument(session_span)));
 
                             daemon_state = DaemonState::Recording;
                             let _ = state_tx.send(daemon_state);
-                            info!("Entered Recording state");
+                            metrics::Metrics::inc(&metrics.sessions_started);
+                            info!(session = %session.as_ref().map_or("", |s| s.id.as_str()), "Entered Recording state");
                         }
                         DaemonCommand::SwitchDevice(name) => {
                             info!("Switching audio device to {:?}", name.as_deref().unwrap_or("Default"));
+                            // The wake listener restarts on the new device
+                            if let Some(listener) = wake_listener.take() {
+                                listener.stop().await;
+                            }
+                            last_device::save(name.as_deref());
                             device_manager.set_device(name);
                         }
+                        DaemonCommand::RepeatLast => {
+                            let Some(text) = last_transcription.read().await.clone() else {
+                                info!("No previous transcription to repeat");
+                                continue;
+                            };
+
+                            // Type into whatever is focused now, using its app profile
+                            let target = window_target::WindowTarget::capture().await;
+                            let profile = match &target {
+                                Some(wt) => app_profile::AppProfile::from_window_class(wt.class()),
+                                None => app_profile::AppProfile::for_category(window_detect::AppCategory::General),
+                            };
+                            let sanitized = match prepare_output(&profile, &text, config.daemon.enable_sanitization, unicode_form) {
+                                Ok(s) => s,
+                                Err(e) => {
+                                    warn!("Sanitization failed for repeat, typing unsanitized text: {}", e);
+                                    unicode_form.apply(&text)
+                                }
+                            };
+
+                            if target.as_ref().is_some_and(|wt| wt.is_own_window())
+                                && config.daemon.suppress_self_injection
+                            {
+                                warn!("Focused window is the dictation overlay, not repeating");
+                                continue;
+                            }
+
+                            let method = injection_method.resolve(target.as_ref().is_some_and(|wt| wt.is_xwayland()));
+                            info!("Repeating last transcription ({} chars, {:?} mode, via {:?})", sanitized.len(), profile.category, method);
+                            if let Err(e) = keyboard.type_text_with(method, &spacing.apply(&sanitized), profile.word_delay_ms).await {
+                                warn!("Failed to repeat last transcription: {}", e);
+                            }
+                        }
                         DaemonCommand::Shutdown => {
                             info!("Received Shutdown command");
                             let _ = gui_control_tx.send(GuiControl::Exit);
                             break;
                         }
-                        _ => {
-                            warn!("Ignoring unexpected command in Idle state");
+                        DaemonCommand::Confirm => {
+                            // Usually a toggle keybind racing the CLI's state file
+                            info!("Confirm received while idle, nothing to confirm (no recording in progress)");
+                        }
+                        cmd => {
+                            warn!("Ignoring unexpected command in Idle state: {:?}", cmd);
                         }
                     }
                     Ok(None) => {
@@ -962,7 +2247,41 @@ pub async fn run() -> Result<()> {
                         break;
                     }
                     Err(_) => {
-                        // Timeout - continue loop
+                        // Timeout - (re)start wake word listening when nothing else is going on
+                        let Some(wake_word) = &wake_word else {
+                            continue;
+                        };
+                        if wake_listener.as_ref().is_some_and(|listener| !listener.is_finished())
+                            || wake_retry_at.is_some_and(|at| Instant::now() < at)
+                        {
+                            continue;
+                        }
+                        let Some(engine) = &preview_engine else {
+                            continue;
+                        };
+                        if let Err(e) = device_manager.start() {
+                            warn!("Couldn't open the mic for the wake word, retrying in 5s: {}", e);
+                            wake_retry_at = Some(Instant::now() + Duration::from_secs(5));
+                            continue;
+                        }
+                        wake_retry_at = None;
+                        // Wake word checks reuse the engine's audio buffer
+                        debug_audio::release_session_audio(&last_diagnostics).await;
+                        let vad = vad::VadStream::new(vad::create_vad_backend(
+                            vad_backend,
+                            config.daemon.vad_threshold,
+                            config.daemon.vad_energy_threshold_db,
+                            sample_rate,
+                        ));
+                        wake_listener = Some(wakeword::WakeListener::spawn(
+                            wake_word.clone(),
+                            Arc::clone(&audio_rx_shared),
+                            vad,
+                            resample::StreamResampler::new(capture_rate, sample_rate),
+                            Arc::clone(engine),
+                            sample_rate,
+                            wake_command_tx.clone(),
+                        ));
                     }
                 }
             }
@@ -984,6 +2303,7 @@ pub async fn run() -> Result<()> {
                         let _ = device_manager.stop();
                         let _ = gui_control_tx.send(GuiControl::SetHidden);
                         session = None;
+                        *current_session_id.write().await = None;
                         daemon_state = DaemonState::Idle;
                         let _ = state_tx.send(daemon_state);
                         info!("Recovered to Idle state after audio task crash");
@@ -993,14 +2313,21 @@ pub async fn run() -> Result<()> {
 
                 // Check for D-Bus commands while recording (non-blocking)
                 match tokio::time::timeout(Duration::from_millis(100), command_rx.recv()).await {
-                    Ok(Some(cmd)) => match cmd {
+                    Ok(Some(cmd)) => match resolve_recording_command(
+                        cmd,
+                        stop_behavior,
+                        session.as_ref().map(|s| s.start_time.elapsed()).unwrap_or(Duration::MAX),
+                        Duration::from_millis(config.daemon.double_tap_ms),
+                    ) {
                         DaemonCommand::Confirm => {
                             info!("Received Confirm command");
+                            metrics::Metrics::inc(&metrics.sessions_confirmed);
                             daemon_state = DaemonState::Processing;
                             let _ = state_tx.send(daemon_state);
                         }
-                        DaemonCommand::StopRecording => {
-                            info!("Received StopRecording (cancel)");
+                        DaemonCommand::Cancel => {
+                            info!("Received cancel");
+                            metrics::Metrics::inc(&metrics.sessions_cancelled);
 
                             // 1. Stop audio backends (pause streams)
                             let _ = device_manager.stop();
@@ -1022,7 +2349,11 @@ pub async fn run() -> Result<()> {
                             // Hide GUI
                             let _ = gui_control_tx.send(GuiControl::SetHidden);
 
-                            session = None;
+                            if let Some(s) = session.take() {
+                                info!(session = %s.id, "Session cancelled");
+                            }
+                            dbus_control::emit_transcription_complete(&dbus_conn, "").await;
+                            *current_session_id.write().await = None;
                             daemon_state = DaemonState::Idle;
                             let _ = state_tx.send(daemon_state);
                             info!("Returned to Idle state");
@@ -1047,6 +2378,7 @@ pub async fn run() -> Result<()> {
                         DaemonCommand::SwitchDevice(name) => {
                             warn!("Device switch to {:?} requested during recording, will apply on next session",
                                   name.as_deref().unwrap_or("Default"));
+                            last_device::save(name.as_deref());
                             device_manager.set_device(name);
                         }
                         _ => {
@@ -1058,20 +2390,29 @@ pub async fn run() -> Result<()> {
                         break;
                     }
                     Err(_) => {
-                        // Timeout - continue recording
+                        // Timeout - continue recording, unless the speaker went quiet
+                        if silence_timeout.expired(epoch_ms()) {
+                            info!("Silent for {}ms after speech, confirming", config.daemon.silence_timeout_ms);
+                            metrics::Metrics::inc(&metrics.sessions_confirmed);
+                            daemon_state = DaemonState::Processing;
+                            let _ = state_tx.send(daemon_state);
+                        }
                     }
                 }
             }
 
             DaemonState::Processing => {
-                info!("Entering Processing state");
+                let session_id = session.as_ref().map(|s| s.id.clone()).unwrap_or_default();
+                info!(session = %session_id, "Entering Processing state");
+                // Final transcription + typing can legitimately block the loop for a while
+                health_state.touch_loop(PROCESSING_STALL_BUDGET);
 
-                if media_was_playing {
-                    media_was_playing = false;
+                if let Some(paused) = paused_media.take() {
                     let delay = config.daemon.media_resume_delay_ms;
+                    let controller = media_controller.clone();
                     tokio::spawn(async move {
                         tokio::time::sleep(Duration::from_millis(delay)).await;
-                        resume_media();
+                        controller.resume(paused).await;
                     });
                 }
 
@@ -1105,30 +2446,214 @@ pub async fn run() -> Result<()> {
                 let audio_buffer_len = session_engine.as_ref().get_audio_buffer().len();
                 info!("Audio buffer contains {} samples", audio_buffer_len);
 
-                if audio_buffer_len > 0 {
-                    // Run final transcription on full buffer (including trailing audio)
-                    let preview_text = session_engine.as_ref().get_final_result()
-                        .unwrap_or_else(|e| {
-                            warn!("Final transcription failed: {}, falling back to cached text", e);
-                            session_engine.as_ref().get_cached_text()
+                let final_text = if audio_buffer_len > 0 {
+                    let utterance_secs = audio_buffer_len as f32 / sample_rate as f32;
+                    let cached_text = session_engine.as_ref().get_cached_text();
+                    // Preview text from a preview window only covers the end of the session
+                    let cached_complete = session_engine.cached_text_covers_session();
+                    let use_cached = config.daemon.small_model_max_seconds > 0.0
+                        && utterance_secs < config.daemon.small_model_max_seconds
+                        && cached_complete
+                        && !cached_text.trim().is_empty();
+
+                    let mut final_stage = final_pass::FinalPassStage::Preview;
+                    let mut confidence = None;
+                    let preview_text = if use_cached {
+                        // Short utterance: the preview pass already covered it, skip final-pass latency
+                        info!("Using cached preview result for {:.1}s utterance (< {:.1}s)",
+                              utterance_secs, config.daemon.small_model_max_seconds);
+                        cached_text
+                    } else {
+                        // Run final transcription on full buffer (including trailing audio)
+                        info!("Running final transcription pass for {:.1}s utterance", utterance_secs);
+                        let final_pass_start = Instant::now();
+                        let mut chain = final_pass_chain.clone();
+                        let engine = Arc::clone(&session_engine);
+                        let mut reload_spec = model_spec.clone();
+                        let mut language_model = None;
+                        if !language_specs.is_empty() {
+                            // Detection only reads the first words of the preview, so it adds no
+                            // noticeable latency; loading the language model is bounded by engine_timeout_ms
+                            let language = language_id::resolve_language(&language_setting, &cached_text);
+                            match language.and_then(|code| language_specs.get(code).map(|spec| (code, spec))) {
+                                Some((code, spec)) if spec.model_name != model_spec.model_name => {
+                                    info!("Language '{}': final pass on model {}", code, spec);
+                                    chain = final_pass::reload_first(&chain);
+                                    reload_spec = spec.clone();
+                                    language_model = Some(code.to_string());
+                                }
+                                _ => info!("Language {:?}: final pass on default model", language),
+                            }
+                        }
+                        let reload_model = reload_spec.to_string();
+                        let language_engines = Arc::clone(&language_engines);
+                        // Set on timeout so the abandoned pass stops instead of running
+                        // more chunks or stages on a model the next session needs
+                        let cancelled = Arc::new(AtomicBool::new(false));
+                        let reload_engine_slot: Arc<std::sync::Mutex<Option<Arc<dyn TranscriptionEngine>>>> =
+                            Default::default();
+                        let mut final_pass_task = {
+                            let cancelled = Arc::clone(&cancelled);
+                            let reload_engine_slot = Arc::clone(&reload_engine_slot);
+                            tokio::task::spawn_blocking(move || {
+                                let cached = language_model.as_ref()
+                                    .and_then(|code| language_engines.lock().unwrap().remove(code));
+                                let result = final_pass::run_chain(&chain, engine.as_ref(), || {
+                                    let reload_engine = match cached {
+                                        Some(cached) => {
+                                            cached.reset();
+                                            cached
+                                        }
+                                        // The reload stage gets the whole session audio, so its engine is unbounded
+                                        None => reload_spec.create_engine(sample_rate, 0, None)?,
+                                    };
+                                    *reload_engine_slot.lock().unwrap() = Some(Arc::clone(&reload_engine));
+                                    // The timeout may have fired while the model was loading
+                                    if cancelled.load(Ordering::Relaxed) {
+                                        reload_engine.cancel_final_pass();
+                                    }
+                                    Ok(reload_engine)
+                                }, &cancelled);
+                                // Only language engines are reused; the default reload stage
+                                // exists to recover from a bad engine state with a fresh one
+                                let used_engine = reload_engine_slot.lock().unwrap().take();
+                                if let (Some(code), Some(used)) = (language_model, used_engine) {
+                                    language_engines.lock().unwrap().insert(code, used);
+                                }
+                                result
+                            })
+                        };
+                        let engine_timeout_ms = config.daemon.engine_timeout_ms;
+                        let joined = if engine_timeout_ms > 0 {
+                            match tokio::time::timeout(Duration::from_millis(engine_timeout_ms), &mut final_pass_task).await {
+                                Ok(joined) => joined,
+                                Err(_) => {
+                                    // The abandoned pass stops at its next chunk; fall back to the
+                                    // preview text (when it covers the whole session) instead of
+                                    // running another inference
+                                    warn!("Final pass exceeded engine_timeout_ms ({}ms), using the live preview result", engine_timeout_ms);
+                                    cancelled.store(true, Ordering::Relaxed);
+                                    session_engine.cancel_final_pass();
+                                    if let Some(reload_engine) = reload_engine_slot.lock().unwrap().as_ref() {
+                                        reload_engine.cancel_final_pass();
+                                    }
+                                    abandoned_final_pass = Some(final_pass_task);
+                                    Ok(Some((cached_text.clone(), final_pass::FinalPassStage::Preview, None))
+                                        .filter(|(text, _, _)| cached_complete && !text.trim().is_empty()))
+                                }
+                            }
+                        } else {
+                            final_pass_task.await
+                        };
+                        let chain_result = joined.unwrap_or_else(|e| {
+                            error!("Final pass task failed: {}", e);
+                            None
                         });
-                    info!("Transcription: '{}'", preview_text);
+                        let result = match chain_result {
+                            Some((text, stage, score)) => {
+                                match stage {
+                                    // Parakeet buffers the whole session, so no second model is needed
+                                    final_pass::FinalPassStage::Final => debug!("Final pass reused the preview engine instance"),
+                                    final_pass::FinalPassStage::Reload => info!("Final pass ran on a separately loaded {} engine", reload_model),
+                                    final_pass::FinalPassStage::Preview => {}
+                                }
+                                final_stage = stage;
+                                confidence = score;
+                                text
+                            }
+                            None => {
+                                warn!("Every final pass stage failed or returned no text");
+                                String::new()
+                            }
+                        };
+                        metrics.observe_final_pass(final_pass_start.elapsed());
+
+                        if config.daemon.enable_edge_dedup {
+                            let deduped = dedup_edge_tokens(&result, &cached_text);
+                            if deduped != result {
+                                info!("Trimmed doubled edge tokens: '{}' → '{}'", result, deduped);
+                            }
+                            deduped
+                        } else {
+                            result
+                        }
+                    };
+                    Some((preview_text, final_stage, confidence))
+                } else {
+                    None
+                };
+
+                // A low-confidence result is most likely noise; skip it like an empty recording
+                let final_text = final_text.filter(|(text, _, confidence)| {
+                    let discard = final_pass::below_confidence(*confidence, config.daemon.min_confidence);
+                    if discard {
+                        info!("Discarding '{}': confidence {:.2} is below min_confidence {:.2}",
+                              text, confidence.unwrap_or_default(), config.daemon.min_confidence);
+                    }
+                    !discard
+                });
+
+                if let Some((preview_text, final_stage, _)) = final_text {
+                    info!(session = %session_id, "Transcription: '{}'", preview_text);
+                    let accurate_text = preview_text.clone();
+
+                    // Detect trailing submit command before post-processing
+                    let (preview_text, submit) = if config.daemon.enable_submit_command {
+                        strip_submit_command(&preview_text)
+                    } else {
+                        (preview_text, false)
+                    };
+                    if submit {
+                        info!("Submit command detected, will press Enter after typing");
+                    }
+
+                    // Spoken processor toggles apply to this and later sessions
+                    let preview_text = if config.daemon.enable_processor_toggle_commands {
+                        let (text, toggles) = strip_processor_toggles(&preview_text);
+                        for (kind, enabled) in toggles {
+                            info!("Spoken toggle: {:?} {}", kind, if enabled { "on" } else { "off" });
+                            processor_overrides.set(kind, enabled);
+                        }
+                        text
+                    } else {
+                        preview_text
+                    };
+
+                    // Build per-app profile from captured window class
+                    let profile = match &window_target {
+                        Some(wt) => app_profile::AppProfile::from_window_class(wt.class()),
+                        None => app_profile::AppProfile::for_category(window_detect::AppCategory::General),
+                    };
 
                     // Apply post-processing pipeline
-                    let pipeline = Pipeline::from_config_with_dict(
-                        config.daemon.enable_acronyms,
-                        config.daemon.enable_punctuation,
-                        config.daemon.enable_grammar,
+                    let punctuation_context = config.daemon.suppress_trailing_punctuation_short.then(|| PunctuationContext {
+                        app_category: profile.category,
+                        min_words_for_period: config.daemon.trailing_punctuation_min_words,
+                    });
+                    let pipeline = Pipeline::from_order_with_context(
+                        &processor_order,
+                        |kind| {
+                            processor_overrides.enabled(kind, config.daemon.processor_enabled(kind))
+                                // A spoken "markdown on" doesn't reach apps outside markdown_apps
+                                && (kind != ProcessorKind::Markdown
+                                    || markdown_filter.allows(window_target.as_ref().map(|wt| wt.class())))
+                        },
                         Some(Arc::clone(&user_dict)),
+                        punctuation_context,
+                        Some(Arc::clone(&command_vocabulary)),
+                        Some(acronyms.clone()),
                     );
                     let processed_result = pipeline.process(&preview_text)?;
 
                     if !pipeline.is_empty() && preview_text != processed_result {
                         info!("[Final] Processed: '{}'", processed_result);
                     }
+                    if !processed_result.trim().is_empty() {
+                        *last_transcription.write().await = Some(processed_result.clone());
+                    }
 
                     // Save debug audio if enabled
-                    if debug_audio::is_debug_audio_enabled() {
+                    if config.daemon.debug_audio || debug_audio::is_debug_audio_enabled() {
                         let audio_buffer = session_engine.get_audio_buffer();
                         let metadata = debug_audio::AudioMetadata {
                             timestamp: chrono::Utc::now(),
@@ -1140,36 +2665,61 @@ pub async fn run() -> Result<()> {
                             preview_text: preview_text.clone(),
                             final_text: processed_result.clone(),
                             preview_engine: "parakeet".to_string(),
-                            accurate_engine: "parakeet".to_string(),
+                            accurate_engine: format!("parakeet ({})", final_stage.name()),
                             same_model_used: true,
                         };
-                        if let Err(e) = debug_audio::save_debug_audio(&audio_buffer, sample_rate, metadata) {
+                        let retention = debug_audio::Retention {
+                            max_files: config.daemon.session_audio_keep,
+                            max_bytes: config.daemon.session_audio_max_mb * 1024 * 1024,
+                        };
+                        if let Err(e) = debug_audio::save_debug_audio(&audio_buffer, sample_rate, metadata, retention) {
                             warn!("Failed to save debug audio: {}", e);
                         }
                     }
 
-                    // Build per-app profile from captured window class
-                    let profile = match &window_target {
-                        Some(wt) => app_profile::AppProfile::from_window_class(wt.class()),
-                        None => app_profile::AppProfile::for_category(window_detect::AppCategory::General),
-                    };
+                    let sanitized_result = prepare_output(
+                        &profile,
+                        &processed_result,
+                        config.daemon.enable_sanitization,
+                        unicode_form,
+                    )?;
+                    // D-Bus listeners get exactly the text that is typed or copied
+                    dbus_control::emit_transcription_complete(&dbus_conn, &sanitized_result).await;
+
+                    // Show the text about to be typed so a final-pass correction doesn't
+                    // just snap in as the overlay closes
+                    if config.daemon.commit_display_ms > 0 && !sanitized_result.trim().is_empty() {
+                        let _ = gui_control_tx.send(GuiControl::ShowResult(sanitized_result.clone()));
+                        tokio::time::sleep(Duration::from_millis(config.daemon.commit_display_ms)).await;
+                        let _ = gui_control_tx.send(GuiControl::SetProcessing);
+                    }
 
-                    let sanitizer = SanitizationProcessor::new(profile.sanitization.clone(), profile.category);
-                    let sanitized_result = sanitizer.process(&processed_result)?;
-
-                    // Copy to clipboard as backup (wl-copy for Wayland)
-                    match tokio::process::Command::new("wl-copy")
-                        .arg(&sanitized_result)
-                        .stdin(std::process::Stdio::null())
-                        .stdout(std::process::Stdio::null())
-                        .stderr(std::process::Stdio::null())
-                        .spawn()
-                    {
-                        Ok(_) => {
-                            debug!("Copied to clipboard ({} chars)", sanitized_result.len());
-                        }
-                        Err(e) => {
-                            warn!("Failed to run wl-copy: {}", e);
+                    // Keep this session for an on-demand diagnostic bundle; the audio stays
+                    // in the engine until a bundle is actually requested
+                    *last_diagnostics.write().await = Some(debug_audio::SessionDiagnostics {
+                        timestamp: chrono::Utc::now(),
+                        session_id: session_id.clone(),
+                        engine: Some(Arc::clone(&session_engine)),
+                        audio_included: false,
+                        audio_truncated_secs: 0.0,
+                        sample_rate,
+                        capture_sample_rate: capture_rate,
+                        sample_format: "s16le mono".to_string(),
+                        audio_backend: format!("{:?}", device_manager.config.backend_type),
+                        audio_device: device_manager.config.backend_config.device_name.clone()
+                            .unwrap_or_else(|| "default".to_string()),
+                        preview_text: session_engine.get_cached_text(),
+                        accurate_text,
+                        processed_text: processed_result.clone(),
+                        typed_text: sanitized_result.clone(),
+                    });
+
+                    // Copy to clipboard (the backup when typing too)
+                    let mut copied = false;
+                    if output_mode.copies() && !sanitized_result.is_empty() {
+                        match keyboard.copy_to_clipboard(&sanitized_result).await {
+                            Ok(()) => copied = true,
+                            Err(e) => warn!("Failed to copy to clipboard: {}", e),
                         }
                     }
 
@@ -1180,22 +2730,79 @@ pub async fn run() -> Result<()> {
 
                     let expected_typing_secs = (sanitized_result.len() as u64 * profile.word_delay_ms) / 1000;
                     if expected_typing_secs > 15 {
-                        warn!("Typing will take ~{}s ({} chars at {}ms/char) — text is already in clipboard if interrupted", expected_typing_secs, sanitized_result.len(), profile.word_delay_ms);
+                        warn!("Typing will take ~{}s ({} chars at {}ms/char){}", expected_typing_secs, sanitized_result.len(), profile.word_delay_ms,
+                              if copied { " — text is already in clipboard if interrupted" } else { "" });
                     }
+                    let focus_on_overlay = config.daemon.suppress_self_injection
+                        && window_detect::focused_window_is_overlay().await;
+                    let mut typed = false;
+                    let is_xwayland = match window_target {
+                        Some(ref wt) => wt.is_xwayland(),
+                        None => window_detect::focused_window_is_xwayland().await,
+                    };
+                    let method = injection_method.resolve(is_xwayland);
+                    info!(session = %session_id, "Injection path: {:?} (xwayland={})", method, is_xwayland);
                     info!("Typing final text ({:?} mode, delay={}ms)...", profile.category, profile.word_delay_ms);
-                    keyboard.type_text(&sanitized_result, profile.word_delay_ms).await?;
+                    if config.daemon.emit_dbus_only {
+                        info!("emit_dbus_only is set, result sent over D-Bus only");
+                    } else if !output_mode.types() {
+                        info!("output_mode is clipboard, not typing");
+                    } else if focus_on_overlay {
+                        if copied {
+                            warn!("Focused window is the dictation overlay, skipping typing (text is in clipboard)");
+                        } else {
+                            warn!("Focused window is the dictation overlay, skipping typing; the text was not copied anywhere");
+                        }
+                    } else if !sanitized_result.is_empty() {
+                        typed = true;
+                        let typed_text = spacing.apply(&sanitized_result);
+                        if config.daemon.show_typing_state {
+                            let _ = gui_control_tx.send(GuiControl::SetTyping);
+                        }
+                        keyboard
+                            .type_text_with_progress(method, &typed_text, profile.word_delay_ms, |progress| {
+                                if config.daemon.show_typing_state {
+                                    let _ = gui_control_tx.send(GuiControl::UpdateTypingProgress(progress));
+                                }
+                            })
+                            .await?;
+                        metrics.chars_typed.fetch_add(typed_text.chars().count() as u64, Ordering::Relaxed);
+                    }
                     info!("Typed!");
 
+                    // Only once the text actually landed somewhere
+                    if config.daemon.notify_on_confirm && (typed || copied) {
+                        let summary = if typed { "Dictation typed" } else { "Dictation copied" };
+                        let connection = dbus_conn.clone();
+                        let text = sanitized_result.clone();
+                        tokio::spawn(async move {
+                            notification::notify_transcription(&connection, summary, &text).await;
+                        });
+                    }
+
+                    if config.daemon.enable_sound_cues {
+                        sound_cues::play(&config.daemon.confirm_sound);
+                    }
+
+                    if submit && !focus_on_overlay && !config.daemon.emit_dbus_only && output_mode.types() {
+                        if let Err(e) = keyboard.press_key_with(method, "Return").await {
+                            warn!("Failed to press Enter for submit command: {}", e);
+                        }
+                    }
+
                     // Send to GUI via channel
                     gui_control_tx.send(GuiControl::SetClosing)
                         .map_err(|e| anyhow::anyhow!("Failed to send SetClosing: {}", e))?;
-
-                    tokio::time::sleep(tokio::time::Duration::from_millis(350)).await;
                 } else {
                     info!("No text to type");
+                    dbus_control::emit_transcription_complete(&dbus_conn, "").await;
                     gui_control_tx.send(GuiControl::SetClosing)
                         .map_err(|e| anyhow::anyhow!("Failed to send SetClosing: {}", e))?;
-                    tokio::time::sleep(tokio::time::Duration::from_millis(350)).await;
+                }
+
+                pending_command = wait_for_closing(&mut command_rx, config.daemon.interrupt_closing).await;
+                if let Some(ref cmd) = pending_command {
+                    info!("Closing delay interrupted by {:?}", cmd);
                 }
 
                 // Hide GUI and return to Idle
@@ -1206,10 +2813,11 @@ pub async fn run() -> Result<()> {
                 let _ = device_manager.stop();
 
                 session = None;
+                *current_session_id.write().await = None;
                 engine_stopped_at = Some(Instant::now());
                 daemon_state = DaemonState::Idle;
                 let _ = state_tx.send(daemon_state);
-                info!("Processing complete - returned to Idle state");
+                info!(session = %session_id, "Processing complete - returned to Idle state");
             }
         }
     }
@@ -1217,3 +2825,109 @@ pub async fn run() -> Result<()> {
     info!("Daemon shutting down");
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_daemon(extra: &str) -> DaemonConfig {
+        let toml_str = format!(
+            "[daemon]\naudio_device = \"default\"\nsample_rate = \"16000\"\n{}",
+            extra
+        );
+        toml::from_str::<Config>(&toml_str).unwrap().daemon
+    }
+
+    #[test]
+    fn test_defaults_are_in_range() {
+        let mut daemon = parse_daemon("");
+        assert_eq!(daemon.validate_and_clamp(), 0);
+    }
+
+    #[test]
+    fn test_clamps_out_of_range_values() {
+        let mut daemon = parse_daemon(
+            "overlay_text_opacity = 5.0\nvad_threshold = -0.5\nsilence_threshold_db = 12.0\nstartup_fade_duration_ms = 60000\n",
+        );
+        assert_eq!(daemon.validate_and_clamp(), 4);
+        assert_eq!(daemon.overlay_text_opacity, 1.0);
+        assert_eq!(daemon.vad_threshold, 0.0);
+        assert_eq!(daemon.silence_threshold_db, 0.0);
+        assert_eq!(daemon.startup_fade_duration_ms, 5000);
+    }
+
+    #[test]
+    fn test_legacy_punctuation_means_capitalization() {
+        let header = "[daemon]\naudio_device = \"default\"\nsample_rate = \"16000\"\n";
+        let daemon = parse_config(&format!(
+            "{}enable_punctuation = false\npost_processing_order = [\"acronyms\", \"punctuation\"]\n",
+            header
+        ))
+        .unwrap()
+        .daemon;
+        assert!(!daemon.enable_capitalization);
+        assert!(!daemon.enable_punctuation);
+        assert_eq!(daemon.post_processing_order, ["acronyms", "capitalization"]);
+
+        // Old default: capitalization on, no automatic period
+        let daemon = parse_config(&format!("{}enable_punctuation = true\n", header)).unwrap().daemon;
+        assert!(daemon.enable_capitalization);
+        assert!(!daemon.enable_punctuation);
+    }
+
+    #[test]
+    fn test_split_punctuation_config_unchanged() {
+        let header = "[daemon]\naudio_device = \"default\"\nsample_rate = \"16000\"\n";
+        let daemon = parse_config(&format!(
+            "{}enable_capitalization = false\nenable_punctuation = true\npost_processing_order = [\"punctuation\"]\n",
+            header
+        ))
+        .unwrap()
+        .daemon;
+        assert!(!daemon.enable_capitalization);
+        assert!(daemon.enable_punctuation);
+        assert_eq!(daemon.post_processing_order, ["punctuation"]);
+    }
+
+    #[test]
+    fn test_clamp_setting_nan_uses_min() {
+        let mut value = f32::NAN;
+        assert!(clamp_setting("test", &mut value, 0.0, 1.0));
+        assert_eq!(value, 0.0);
+    }
+
+    #[test]
+    fn test_startup_toggle_mashing_keeps_trailing_start() {
+        use DaemonCommand::*;
+        assert!(matches!(coalesce_startup_commands(vec![StartRecording]), Some(StartRecording)));
+        assert!(matches!(
+            coalesce_startup_commands(vec![StartRecording, Confirm, StartRecording]),
+            Some(StartRecording)
+        ));
+        assert!(coalesce_startup_commands(vec![StartRecording, Confirm]).is_none());
+        assert!(coalesce_startup_commands(vec![RepeatLast]).is_none());
+    }
+
+    #[test]
+    fn test_startup_shutdown_wins() {
+        use DaemonCommand::*;
+        assert!(matches!(
+            coalesce_startup_commands(vec![Shutdown, StartRecording]),
+            Some(Shutdown)
+        ));
+    }
+
+    #[test]
+    fn test_terminal_output_escapes_shell_chars() {
+        let pipeline = Pipeline::from_config(false, false, true, false);
+        let processed = pipeline.process("echo $HOME").unwrap();
+        let terminal = app_profile::AppProfile::for_category(window_detect::AppCategory::Terminal);
+
+        let sanitized = sanitize_for(&terminal, &processed, true).unwrap();
+        assert_eq!(sanitized, "Echo \\$HOME");
+        assert_eq!(sanitize_for(&terminal, &processed, false).unwrap(), processed);
+
+        let editor = app_profile::AppProfile::for_category(window_detect::AppCategory::Editor);
+        assert_eq!(sanitize_for(&editor, &processed, true).unwrap(), "Echo $HOME");
+    }
+}
@@ -0,0 +1,197 @@
+//! Pausing media playback while recording.
+//!
+//! Either shells out to `playerctl` or talks MPRIS directly over the daemon's
+//! session bus. Failures are logged and never block a recording.
+
+use anyhow::Result;
+use std::path::Path;
+use tracing::{debug, info, warn};
+
+const MPRIS_PREFIX: &str = "org.mpris.MediaPlayer2.";
+const MPRIS_PATH: &str = "/org/mpris/MediaPlayer2";
+const MPRIS_PLAYER_INTERFACE: &str = "org.mpris.MediaPlayer2.Player";
+
+/// Configured media controller
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MediaControl {
+    /// playerctl when installed, native MPRIS otherwise
+    #[default]
+    Auto,
+    Playerctl,
+    Mpris,
+    None,
+}
+
+impl MediaControl {
+    /// Parse from config string
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "auto" => Some(MediaControl::Auto),
+            "playerctl" => Some(MediaControl::Playerctl),
+            "mpris" | "dbus" => Some(MediaControl::Mpris),
+            "none" | "off" | "disabled" => Some(MediaControl::None),
+            _ => None,
+        }
+    }
+
+    /// Pick a concrete controller for `Auto` given whether playerctl is installed
+    fn resolve(self, playerctl_installed: bool) -> Self {
+        match self {
+            MediaControl::Auto if playerctl_installed => MediaControl::Playerctl,
+            MediaControl::Auto => MediaControl::Mpris,
+            other => other,
+        }
+    }
+}
+
+/// What was paused, so only that is resumed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PausedMedia {
+    Playerctl,
+    /// Bus names of the MPRIS players that were playing
+    Mpris(Vec<String>),
+}
+
+#[derive(Clone)]
+pub struct MediaController {
+    control: MediaControl,
+    connection: zbus::Connection,
+}
+
+impl MediaController {
+    pub fn new(control: MediaControl, connection: zbus::Connection) -> Self {
+        let control = control.resolve(is_on_path("playerctl"));
+        match control {
+            MediaControl::None => info!("Media control disabled"),
+            other => info!("Media control: {:?}", other),
+        }
+        Self { control, connection }
+    }
+
+    /// Pause playing media, returning what was paused (None if nothing was playing)
+    pub async fn pause_if_playing(&self) -> Option<PausedMedia> {
+        let paused = match self.control {
+            MediaControl::Playerctl => playerctl_pause().await.then_some(PausedMedia::Playerctl),
+            MediaControl::Mpris => match mpris_pause(&self.connection).await {
+                Ok(players) if !players.is_empty() => Some(PausedMedia::Mpris(players)),
+                Ok(_) => None,
+                Err(e) => {
+                    warn!("MPRIS media pause failed: {}", e);
+                    None
+                }
+            },
+            MediaControl::Auto | MediaControl::None => None,
+        };
+        if paused.is_some() {
+            info!("Paused media playback");
+        }
+        paused
+    }
+
+    /// Resume media paused by `pause_if_playing`
+    pub async fn resume(&self, paused: PausedMedia) {
+        match paused {
+            PausedMedia::Playerctl => {
+                let _ = tokio::process::Command::new("playerctl").arg("play").output().await;
+            }
+            PausedMedia::Mpris(players) => {
+                for player in players {
+                    if let Err(e) = mpris_call(&self.connection, &player, "Play").await {
+                        warn!("Failed to resume {}: {}", player, e);
+                    }
+                }
+            }
+        }
+        info!("Resumed media playback");
+    }
+}
+
+async fn playerctl_pause() -> bool {
+    let Ok(output) = tokio::process::Command::new("playerctl").arg("status").output().await else {
+        return false;
+    };
+    let playing = String::from_utf8_lossy(&output.stdout).contains("Playing");
+    if playing {
+        let _ = tokio::process::Command::new("playerctl").arg("pause").output().await;
+    }
+    playing
+}
+
+/// Pause every MPRIS player that is playing, returning their bus names
+async fn mpris_pause(connection: &zbus::Connection) -> Result<Vec<String>> {
+    let dbus = zbus::fdo::DBusProxy::new(connection).await?;
+    let mut paused = Vec::new();
+
+    for name in dbus.list_names().await? {
+        let name = name.as_str();
+        if !name.starts_with(MPRIS_PREFIX) {
+            continue;
+        }
+        // One misbehaving player (or one that quit meanwhile) mustn't stop the
+        // rest from pausing, or from being resumed later
+        let proxy = match zbus::Proxy::new(connection, name, MPRIS_PATH, MPRIS_PLAYER_INTERFACE).await {
+            Ok(proxy) => proxy,
+            Err(e) => {
+                debug!("Couldn't connect to {}: {}", name, e);
+                continue;
+            }
+        };
+        match proxy.get_property::<String>("PlaybackStatus").await {
+            Ok(status) if status == "Playing" => match mpris_call(connection, name, "Pause").await {
+                Ok(()) => paused.push(name.to_string()),
+                Err(e) => warn!("Failed to pause {}: {}", name, e),
+            },
+            Ok(_) => {}
+            Err(e) => debug!("Couldn't read playback status of {}: {}", name, e),
+        }
+    }
+    Ok(paused)
+}
+
+async fn mpris_call(connection: &zbus::Connection, player: &str, method: &str) -> Result<()> {
+    let proxy = zbus::Proxy::new(connection, player, MPRIS_PATH, MPRIS_PLAYER_INTERFACE).await?;
+    proxy.call_method(method, &()).await?;
+    Ok(())
+}
+
+/// Whether an executable named `program` is on PATH
+fn is_on_path(program: &str) -> bool {
+    std::env::var_os("PATH")
+        .map(|paths| std::env::split_paths(&paths).any(|dir| is_executable(&dir.join(program))))
+        .unwrap_or(false)
+}
+
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    path.metadata()
+        .map(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!(MediaControl::from_str("auto"), Some(MediaControl::Auto));
+        assert_eq!(MediaControl::from_str("Playerctl"), Some(MediaControl::Playerctl));
+        assert_eq!(MediaControl::from_str(" mpris "), Some(MediaControl::Mpris));
+        assert_eq!(MediaControl::from_str("none"), Some(MediaControl::None));
+        assert_eq!(MediaControl::from_str("spotify"), None);
+    }
+
+    #[test]
+    fn test_auto_prefers_playerctl_when_installed() {
+        assert_eq!(MediaControl::Auto.resolve(true), MediaControl::Playerctl);
+        assert_eq!(MediaControl::Auto.resolve(false), MediaControl::Mpris);
+        assert_eq!(MediaControl::None.resolve(true), MediaControl::None);
+        assert_eq!(MediaControl::Mpris.resolve(true), MediaControl::Mpris);
+    }
+
+    #[test]
+    fn test_is_on_path() {
+        assert!(is_on_path("sh"));
+        assert!(!is_on_path("voice-dictation-no-such-program"));
+    }
+}
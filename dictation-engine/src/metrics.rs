@@ -0,0 +1,185 @@
+//! Prometheus-style metrics for the daemon.
+//!
+//! Counters are plain atomics updated from the state machine. When
+//! `enable_metrics_server` is set, a minimal HTTP handler on 127.0.0.1 serves
+//! them in the Prometheus text exposition format at any path (scrape `/metrics`).
+
+use anyhow::Result;
+use std::fmt::Write as _;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use tokio::sync::watch;
+use tracing::{debug, info, warn};
+
+use crate::dbus_control::DaemonState;
+
+/// Upper bounds (seconds) of the final-pass latency histogram buckets
+const LATENCY_BUCKETS: [f64; 8] = [0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0];
+
+/// Daemon metrics shared between the state machine and the exporter.
+pub struct Metrics {
+    pub sessions_started: AtomicU64,
+    pub sessions_confirmed: AtomicU64,
+    pub sessions_cancelled: AtomicU64,
+    /// Characters typed across all sessions
+    pub chars_typed: AtomicU64,
+    /// Per-bucket counts (non-cumulative), one per LATENCY_BUCKETS entry plus +Inf
+    latency_buckets: [AtomicU64; LATENCY_BUCKETS.len() + 1],
+    latency_sum_us: AtomicU64,
+    latency_count: AtomicU64,
+    /// Configured audio backend name ("auto", "pipewire", "cpal")
+    audio_backend: String,
+}
+
+impl Metrics {
+    pub fn new(audio_backend: &str) -> Self {
+        Self {
+            sessions_started: AtomicU64::new(0),
+            sessions_confirmed: AtomicU64::new(0),
+            sessions_cancelled: AtomicU64::new(0),
+            chars_typed: AtomicU64::new(0),
+            latency_buckets: Default::default(),
+            latency_sum_us: AtomicU64::new(0),
+            latency_count: AtomicU64::new(0),
+            audio_backend: audio_backend.to_lowercase(),
+        }
+    }
+
+    pub fn inc(counter: &AtomicU64) {
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Record the duration of a final transcription pass
+    pub fn observe_final_pass(&self, elapsed: Duration) {
+        let secs = elapsed.as_secs_f64();
+        let bucket = LATENCY_BUCKETS
+            .iter()
+            .position(|&bound| secs <= bound)
+            .unwrap_or(LATENCY_BUCKETS.len());
+        self.latency_buckets[bucket].fetch_add(1, Ordering::Relaxed);
+        self.latency_sum_us.fetch_add(elapsed.as_micros() as u64, Ordering::Relaxed);
+        self.latency_count.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Render all metrics in the Prometheus text exposition format
+    pub fn render(&self, state: DaemonState) -> String {
+        let mut out = String::new();
+
+        let counters = [
+            ("voice_dictation_sessions_started_total", "Recording sessions started", &self.sessions_started),
+            ("voice_dictation_sessions_confirmed_total", "Recording sessions confirmed", &self.sessions_confirmed),
+            ("voice_dictation_sessions_cancelled_total", "Recording sessions cancelled", &self.sessions_cancelled),
+            ("voice_dictation_chars_typed_total", "Characters of transcription typed", &self.chars_typed),
+        ];
+        for (name, help, value) in counters {
+            let _ = writeln!(out, "# HELP {} {}", name, help);
+            let _ = writeln!(out, "# TYPE {} counter", name);
+            let _ = writeln!(out, "{} {}", name, value.load(Ordering::Relaxed));
+        }
+
+        let name = "voice_dictation_final_pass_seconds";
+        let _ = writeln!(out, "# HELP {} Final transcription pass latency", name);
+        let _ = writeln!(out, "# TYPE {} histogram", name);
+        let mut cumulative = 0;
+        for (i, bound) in LATENCY_BUCKETS.iter().enumerate() {
+            cumulative += self.latency_buckets[i].load(Ordering::Relaxed);
+            let _ = writeln!(out, "{}_bucket{{le=\"{}\"}} {}", name, bound, cumulative);
+        }
+        cumulative += self.latency_buckets[LATENCY_BUCKETS.len()].load(Ordering::Relaxed);
+        let _ = writeln!(out, "{}_bucket{{le=\"+Inf\"}} {}", name, cumulative);
+        let sum_secs = self.latency_sum_us.load(Ordering::Relaxed) as f64 / 1_000_000.0;
+        let _ = writeln!(out, "{}_sum {}", name, sum_secs);
+        let _ = writeln!(out, "{}_count {}", name, self.latency_count.load(Ordering::Relaxed));
+
+        let name = "voice_dictation_audio_backend";
+        let _ = writeln!(out, "# HELP {} Configured audio backend", name);
+        let _ = writeln!(out, "# TYPE {} gauge", name);
+        let _ = writeln!(out, "{}{{backend=\"{}\"}} 1", name, self.audio_backend);
+
+        let name = "voice_dictation_state";
+        let _ = writeln!(out, "# HELP {} Current daemon state", name);
+        let _ = writeln!(out, "# TYPE {} gauge", name);
+        for candidate in [DaemonState::Idle, DaemonState::Recording, DaemonState::Processing] {
+            let value = if candidate == state { 1 } else { 0 };
+            let _ = writeln!(out, "{}{{state=\"{}\"}} {}", name, candidate, value);
+        }
+
+        out
+    }
+}
+
+/// Serve metrics over HTTP on 127.0.0.1:`port` until the task is dropped.
+pub async fn serve_metrics(
+    metrics: Arc<Metrics>,
+    port: u16,
+    state_rx: watch::Receiver<DaemonState>,
+) -> Result<()> {
+    let listener = TcpListener::bind(("127.0.0.1", port)).await?;
+    info!("Metrics exporter listening on http://127.0.0.1:{}/metrics", port);
+
+    loop {
+        let (mut stream, peer) = listener.accept().await?;
+        let metrics = Arc::clone(&metrics);
+        let state = *state_rx.borrow();
+
+        tokio::spawn(async move {
+            // Request line and headers are ignored; every path returns the metrics
+            let mut request = [0u8; 1024];
+            if let Err(e) = stream.read(&mut request).await {
+                debug!("Metrics request from {} failed: {}", peer, e);
+                return;
+            }
+
+            let body = metrics.render(state);
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            if let Err(e) = stream.write_all(response.as_bytes()).await {
+                warn!("Failed to write metrics response: {}", e);
+            }
+            let _ = stream.shutdown().await;
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_counters_and_state() {
+        let metrics = Metrics::new("PipeWire");
+        Metrics::inc(&metrics.sessions_started);
+        Metrics::inc(&metrics.sessions_started);
+        Metrics::inc(&metrics.sessions_cancelled);
+        metrics.chars_typed.fetch_add(42, Ordering::Relaxed);
+
+        let out = metrics.render(DaemonState::Recording);
+        assert!(out.contains("voice_dictation_sessions_started_total 2"));
+        assert!(out.contains("voice_dictation_sessions_cancelled_total 1"));
+        assert!(out.contains("voice_dictation_chars_typed_total 42"));
+        assert!(out.contains("voice_dictation_audio_backend{backend=\"pipewire\"} 1"));
+        assert!(out.contains("voice_dictation_state{state=\"recording\"} 1"));
+        assert!(out.contains("voice_dictation_state{state=\"idle\"} 0"));
+    }
+
+    #[test]
+    fn test_latency_histogram_is_cumulative() {
+        let metrics = Metrics::new("auto");
+        metrics.observe_final_pass(Duration::from_millis(80));
+        metrics.observe_final_pass(Duration::from_millis(700));
+        metrics.observe_final_pass(Duration::from_secs(60));
+
+        let out = metrics.render(DaemonState::Idle);
+        assert!(out.contains("voice_dictation_final_pass_seconds_bucket{le=\"0.1\"} 1"));
+        assert!(out.contains("voice_dictation_final_pass_seconds_bucket{le=\"1\"} 2"));
+        assert!(out.contains("voice_dictation_final_pass_seconds_bucket{le=\"30\"} 2"));
+        assert!(out.contains("voice_dictation_final_pass_seconds_bucket{le=\"+Inf\"} 3"));
+        assert!(out.contains("voice_dictation_final_pass_seconds_count 3"));
+    }
+}
@@ -6,15 +6,41 @@
 use anyhow::{anyhow, Result};
 use std::path::PathBuf;
 use std::sync::Arc;
-use tracing::info;
+use tracing::{info, warn};
 
 use crate::engine::TranscriptionEngine;
 use crate::parakeet_engine::ParakeetEngine;
+use crate::pause_split::PauseSplit;
+use crate::vad::SilenceTrim;
+
+/// Final-pass chunk length used when memory isn't a concern (seconds)
+pub const DEFAULT_CHUNK_SECONDS: u32 = 30;
+
+/// Approximate peak memory of the Parakeet TDT model by final-pass chunk length:
+/// (chunk seconds, MB) for the weights plus encoder activations of one chunk.
+/// Longest chunk first.
+const PARAKEET_MEMORY_MB: &[(u32, u64)] = &[(30, 3200), (20, 2700), (10, 2300)];
 
 /// Parsed model specification from config
 #[derive(Debug, Clone)]
 pub struct ModelSpec {
     pub model_name: String,
+    /// Directory under the models dir holding the ONNX files
+    pub model_dir: String,
+    /// Longest audio chunk transcribed in one pass (seconds)
+    pub chunk_seconds: u32,
+    /// Chunks of long audio transcribed at once, each on its own model instance
+    pub chunk_parallelism: usize,
+    /// Leading/trailing silence trimming for the final pass (None = off)
+    pub silence_trim: Option<SilenceTrim>,
+    /// Silence added around the final-pass audio (milliseconds, 0 = off)
+    pub silence_pad_ms: u64,
+    /// Breaks inserted at long pauses in the transcribed text (None = off)
+    pub pause_split: Option<PauseSplit>,
+    /// Seconds of recent audio the live preview covers (0 = whole buffer)
+    pub preview_window_seconds: u64,
+    /// Remove undecodable replacement characters from transcriptions
+    pub drop_undecodable_words: bool,
 }
 
 impl std::fmt::Display for ModelSpec {
@@ -46,9 +72,75 @@ impl ModelSpec {
 
         Ok(Self {
             model_name: parts[1].to_string(),
+            model_dir: "parakeet".to_string(),
+            chunk_seconds: DEFAULT_CHUNK_SECONDS,
+            chunk_parallelism: 1,
+            silence_trim: None,
+            silence_pad_ms: 0,
+            pause_split: None,
+            preview_window_seconds: 0,
+            drop_undecodable_words: true,
         })
     }
 
+    /// A per-language final-pass model with the same settings, loaded from
+    /// `models/parakeet-<model_name>` so it can sit next to the default model
+    pub fn for_language(&self, model_name: &str) -> Self {
+        Self {
+            model_name: model_name.to_string(),
+            model_dir: format!("parakeet-{}", model_name),
+            ..self.clone()
+        }
+    }
+
+    /// Shorten the chunk length, then lower `chunk_parallelism`, until the
+    /// expected footprint fits in `available_mb`. Every parallel chunk runs on
+    /// its own model instance, so the footprint scales with the parallelism.
+    ///
+    /// Falls back to the smallest footprint (with a warning) when nothing fits.
+    pub fn fit_to_memory(&mut self, available_mb: u64) {
+        let (full_secs, full_mb) = PARAKEET_MEMORY_MB[0];
+        let parallelism = self.chunk_parallelism.max(1);
+        let full_total = full_mb * parallelism as u64;
+        if available_mb >= full_total {
+            info!(
+                "{} MB available, enough for {}s chunks x{} (~{} MB)",
+                available_mb, full_secs, parallelism, full_total
+            );
+            return;
+        }
+
+        // Shorter chunks cost less accuracy than losing the parallel speedup
+        let fit = (1..=parallelism).rev().find_map(|instances| {
+            PARAKEET_MEMORY_MB
+                .iter()
+                .find(|&&(_, mb)| mb * instances as u64 <= available_mb)
+                .map(|&(secs, mb)| (secs, mb, instances))
+        });
+
+        match fit {
+            Some((secs, mb, instances)) => {
+                self.chunk_seconds = secs;
+                self.chunk_parallelism = instances;
+                warn!(
+                    "Only {} MB of memory available; parakeet needs ~{} MB with {}s chunks x{}. \
+                     Using {}s chunks x{} (~{} MB) to avoid running out of memory",
+                    available_mb, full_total, full_secs, parallelism, secs, instances, mb * instances as u64
+                );
+            }
+            None => {
+                let (secs, mb) = PARAKEET_MEMORY_MB[PARAKEET_MEMORY_MB.len() - 1];
+                self.chunk_seconds = secs;
+                self.chunk_parallelism = 1;
+                warn!(
+                    "Only {} MB of memory available; parakeet needs at least ~{} MB even with {}s chunks \
+                     and may be killed for running out of memory",
+                    available_mb, mb, secs
+                );
+            }
+        }
+    }
+
     /// Get the base models directory
     fn get_models_dir() -> PathBuf {
         let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
@@ -60,7 +152,7 @@ impl ModelSpec {
 
     /// Get the full path to the model
     pub fn model_path(&self) -> PathBuf {
-        Self::get_models_dir().join("parakeet")
+        Self::get_models_dir().join(&self.model_dir)
     }
 
     /// Check if the model is available on the filesystem
@@ -71,15 +163,45 @@ impl ModelSpec {
             && path.join("decoder_joint-model.onnx").exists()
     }
 
-    /// Create a transcription engine from this specification
-    pub fn create_engine(&self, sample_rate: u32) -> Result<Arc<dyn TranscriptionEngine>> {
+    /// Create a transcription engine from this specification.
+    ///
+    /// `max_buffer_seconds` bounds in-memory session audio (0 = unbounded); audio
+    /// past the limit is spilled to `spill_path` or dropped when it is None.
+    /// `silence_pad_ms` of silence is added around the audio for the final pass.
+    pub fn create_engine(
+        &self,
+        sample_rate: u32,
+        max_buffer_seconds: u64,
+        spill_path: Option<PathBuf>,
+    ) -> Result<Arc<dyn TranscriptionEngine>> {
         info!("Creating parakeet engine with model '{}'", self.model_name);
         let model_path = self.model_path();
-        let engine = ParakeetEngine::new(model_path, sample_rate)?;
+        let engine = ParakeetEngine::new(model_path.clone(), sample_rate)?
+            .with_buffer_limit(max_buffer_seconds, spill_path)
+            .with_silence_trim(self.silence_trim)
+            .with_silence_padding(self.silence_pad_ms)
+            .with_pause_split(self.pause_split)
+            .with_preview_window(self.preview_window_seconds)
+            .with_undecodable_dropping(self.drop_undecodable_words)
+            .with_chunk_seconds(self.chunk_seconds)
+            .with_chunk_parallelism(self.chunk_parallelism, &model_path)?;
         Ok(Arc::new(engine))
     }
 }
 
+/// Memory available for new allocations (MemAvailable in /proc/meminfo), in MB
+pub fn available_memory_mb() -> Option<u64> {
+    parse_mem_available(&std::fs::read_to_string("/proc/meminfo").ok()?)
+}
+
+fn parse_mem_available(meminfo: &str) -> Option<u64> {
+    meminfo
+        .lines()
+        .find_map(|line| line.strip_prefix("MemAvailable:"))
+        .and_then(|rest| rest.trim().trim_end_matches("kB").trim().parse::<u64>().ok())
+        .map(|kb| kb / 1024)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -97,9 +219,65 @@ mod tests {
         assert!(ModelSpec::parse("whisper:model").is_err());
     }
 
+    #[test]
+    fn test_model_dir() {
+        // Any configured model name uses the one downloaded model
+        let spec = ModelSpec::parse("parakeet:tdt-0.6b-v2").unwrap();
+        assert_eq!(spec.model_dir, "parakeet");
+        let german = spec.for_language("de");
+        assert_eq!(german.model_dir, "parakeet-de");
+        assert_eq!(format!("{}", german), "parakeet:de");
+    }
+
     #[test]
     fn test_display() {
         let spec = ModelSpec::parse("parakeet:default").unwrap();
         assert_eq!(format!("{}", spec), "parakeet:default");
     }
+
+    #[test]
+    fn test_parse_mem_available() {
+        let meminfo = "MemTotal:       16318128 kB\nMemFree:         1203400 kB\nMemAvailable:    8388608 kB\n";
+        assert_eq!(parse_mem_available(meminfo), Some(8192));
+        assert_eq!(parse_mem_available("MemTotal: 1 kB\n"), None);
+    }
+
+    #[test]
+    fn test_fit_to_memory_keeps_default_when_it_fits() {
+        let mut spec = ModelSpec::parse("parakeet:default").unwrap();
+        spec.fit_to_memory(8192);
+        assert_eq!(spec.chunk_seconds, DEFAULT_CHUNK_SECONDS);
+    }
+
+    #[test]
+    fn test_fit_to_memory_counts_parallel_instances() {
+        let fitted = |available_mb| {
+            let mut spec = ModelSpec::parse("parakeet:default").unwrap();
+            spec.chunk_parallelism = 4;
+            spec.fit_to_memory(available_mb);
+            (spec.chunk_seconds, spec.chunk_parallelism)
+        };
+        assert_eq!(fitted(12800), (DEFAULT_CHUNK_SECONDS, 4));
+        // Shorter chunks first, then fewer instances
+        assert_eq!(fitted(10800), (20, 4));
+        assert_eq!(fitted(8000), (10, 3));
+        assert_eq!(fitted(3000), (20, 1));
+        assert_eq!(fitted(2000), (10, 1));
+    }
+
+    #[test]
+    fn test_fit_to_memory_downgrades_chunk_length() {
+        let mut spec = ModelSpec::parse("parakeet:default").unwrap();
+        spec.fit_to_memory(2800);
+        assert_eq!(spec.chunk_seconds, 20);
+        spec.fit_to_memory(2400);
+        assert_eq!(spec.chunk_seconds, 10);
+    }
+
+    #[test]
+    fn test_fit_to_memory_uses_smallest_when_nothing_fits() {
+        let mut spec = ModelSpec::parse("parakeet:default").unwrap();
+        spec.fit_to_memory(1024);
+        assert_eq!(spec.chunk_seconds, 10);
+    }
 }
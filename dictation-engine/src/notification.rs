@@ -0,0 +1,108 @@
+//! Desktop notification mirror of the final transcription.
+//!
+//! Sent through `org.freedesktop.Notifications` on the daemon's session bus
+//! connection. Without a notification daemon the call fails and is only logged.
+
+use anyhow::Result;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU32, Ordering};
+use tracing::debug;
+use zbus::zvariant::Value;
+
+const NOTIFICATIONS_SERVICE: &str = "org.freedesktop.Notifications";
+const NOTIFICATIONS_PATH: &str = "/org/freedesktop/Notifications";
+const NOTIFICATIONS_INTERFACE: &str = "org.freedesktop.Notifications";
+
+/// Longest transcription shown in the notification body (characters)
+const MAX_BODY_CHARS: usize = 200;
+
+/// How long the notification stays up (milliseconds)
+const EXPIRE_TIMEOUT_MS: i32 = 5000;
+
+/// ID of the previous notification, replaced so rapid dictations don't stack up
+static LAST_NOTIFICATION_ID: AtomicU32 = AtomicU32::new(0);
+
+/// Show `text` in a desktop notification titled `summary`, logging instead of failing.
+pub async fn notify_transcription(connection: &zbus::Connection, summary: &str, text: &str) {
+    let body = escape_markup(&truncate_body(text, MAX_BODY_CHARS));
+    if let Err(e) = send(connection, summary, &body).await {
+        debug!("Transcription notification not shown (no notification daemon?): {}", e);
+    }
+}
+
+async fn send(connection: &zbus::Connection, summary: &str, body: &str) -> Result<()> {
+    let proxy = zbus::Proxy::new(
+        connection,
+        NOTIFICATIONS_SERVICE,
+        NOTIFICATIONS_PATH,
+        NOTIFICATIONS_INTERFACE,
+    )
+    .await?;
+
+    let actions: Vec<&str> = Vec::new();
+    let hints: HashMap<&str, Value> = HashMap::new();
+    let id: u32 = proxy
+        .call(
+            "Notify",
+            &(
+                "Voice Dictation",
+                LAST_NOTIFICATION_ID.load(Ordering::Relaxed),
+                "audio-input-microphone",
+                summary,
+                body,
+                actions,
+                hints,
+                EXPIRE_TIMEOUT_MS,
+            ),
+        )
+        .await?;
+    LAST_NOTIFICATION_ID.store(id, Ordering::Relaxed);
+    Ok(())
+}
+
+/// Escape the characters notification servers read as body markup, so dictated
+/// text like "<b>" or "AT&T" shows literally
+fn escape_markup(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Cut `text` to at most `max_chars` characters, marking the cut with an ellipsis
+fn truncate_body(text: &str, max_chars: usize) -> String {
+    match text.char_indices().nth(max_chars) {
+        Some((cut, _)) => format!("{}…", text[..cut].trim_end()),
+        None => text.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_short_text_unchanged() {
+        assert_eq!(truncate_body("hello world", 20), "hello world");
+    }
+
+    #[test]
+    fn test_long_text_truncated_on_char_boundary() {
+        assert_eq!(truncate_body("héllo wörld", 6), "héllo…");
+        assert_eq!(truncate_body("日本語のテキスト", 3), "日本語…");
+    }
+
+    #[test]
+    fn test_markup_escaped() {
+        assert_eq!(escape_markup("use <b> for AT&T's \"bold\""), "use &lt;b&gt; for AT&amp;T&apos;s &quot;bold&quot;");
+        assert_eq!(escape_markup("plain text"), "plain text");
+    }
+}
@@ -7,11 +7,18 @@
 use anyhow::Result;
 use parakeet_rs::{ParakeetTDT, TimestampMode, Transcriber};
 use std::path::PathBuf;
-use std::sync::{Arc, Mutex};
-use tracing::{debug, info};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use tracing::{debug, info, warn};
 
+use crate::audio_buffer::BoundedAudioBuffer;
 use crate::chunking::{transcribe_chunked_with_timestamps, ChunkConfig, TimestampedChunkResult};
 use crate::engine::TranscriptionEngine;
+use crate::pause_split::{self, PauseSplit};
+use crate::vad::SilenceTrim;
+
+/// Sample rate Parakeet models are trained on; capture audio is resampled to this
+pub const PARAKEET_SAMPLE_RATE: u32 = 16000;
 
 // Audio thresholds (at 16kHz sample rate)
 const MIN_AUDIO_SAMPLES: usize = 2400; // 0.15s minimum for transcription
@@ -25,9 +32,14 @@ const RETRANSCRIBE_THRESHOLD: usize = 4800; // 0.3s of new audio before re-trans
 ///
 /// Preview uses incremental transcription: only new audio since last call
 /// is transcribed and appended to cached results for rolling preview.
+///
+/// parakeet-rs returns tokens without scores, so final results carry no
+/// confidence and `min_confidence` doesn't filter them.
 pub struct ParakeetEngine {
     parakeet: Arc<Mutex<ParakeetTDT>>,
-    audio_buffer: Arc<Mutex<Vec<i16>>>,
+    /// Extra model instances so long audio can be transcribed a chunk per instance
+    chunk_models: Vec<Mutex<ParakeetTDT>>,
+    audio_buffer: Arc<Mutex<BoundedAudioBuffer>>,
     sample_rate: u32,
     /// Cached transcription text (accumulated from incremental transcriptions)
     current_text: Arc<Mutex<String>>,
@@ -35,6 +47,20 @@ pub struct ParakeetEngine {
     last_transcribed_len: Arc<Mutex<usize>>,
     /// Chunking configuration for long audio
     chunk_config: ChunkConfig,
+    /// Zero samples added before and after the audio for the final pass
+    final_pad_samples: usize,
+    /// Leading/trailing silence removed before the final pass
+    final_trim: Option<SilenceTrim>,
+    /// Most recent samples the live preview transcribes (0 = the whole in-memory buffer)
+    preview_window_samples: usize,
+    /// Set while a preview transcription runs, so a slow one isn't queued behind
+    preview_in_flight: AtomicBool,
+    /// Set when the pending final pass was abandoned; checked between chunks
+    cancelled: AtomicBool,
+    /// Whether current_text was transcribed from every sample of the session
+    text_covers_session: AtomicBool,
+    /// Remove undecodable replacement characters instead of only logging them
+    drop_undecodable: bool,
 }
 
 impl ParakeetEngine {
@@ -47,7 +73,7 @@ impl ParakeetEngine {
         info!("Loading Parakeet model from {:?}", model_path);
 
         // Parakeet requires 16kHz audio
-        if sample_rate != 16000 {
+        if sample_rate != PARAKEET_SAMPLE_RATE {
             anyhow::bail!("Parakeet requires 16kHz audio, got {} Hz", sample_rate);
         }
 
@@ -58,14 +84,123 @@ impl ParakeetEngine {
 
         Ok(Self {
             parakeet: Arc::new(Mutex::new(parakeet)),
-            audio_buffer: Arc::new(Mutex::new(Vec::with_capacity(480_000))), // ~30s at 16kHz
+            chunk_models: Vec::new(),
+            audio_buffer: Arc::new(Mutex::new(BoundedAudioBuffer::unbounded())),
             sample_rate,
             current_text: Arc::new(Mutex::new(String::new())),
             last_transcribed_len: Arc::new(Mutex::new(0)),
             chunk_config,
+            final_pad_samples: 0,
+            final_trim: None,
+            preview_window_samples: 0,
+            preview_in_flight: AtomicBool::new(false),
+            cancelled: AtomicBool::new(false),
+            text_covers_session: AtomicBool::new(true),
+            drop_undecodable: true,
         })
     }
 
+    /// Transcribe long audio in chunks of at most `max_chunk_seconds`.
+    ///
+    /// Shorter chunks lower peak memory (encoder attention grows with chunk length).
+    pub fn with_chunk_seconds(mut self, max_chunk_seconds: u32) -> Self {
+        self.chunk_config.max_chunk_seconds = max_chunk_seconds;
+        self
+    }
+
+    /// Transcribe up to `parallelism` chunks of long audio at once.
+    ///
+    /// Loads `parallelism - 1` extra model instances from `model_path`, each
+    /// costing the model's full memory footprint.
+    pub fn with_chunk_parallelism(mut self, parallelism: usize, model_path: &std::path::Path) -> Result<Self> {
+        let parallelism = parallelism.max(1);
+        for _ in 1..parallelism {
+            let model = ParakeetTDT::from_pretrained(model_path.to_str().unwrap_or("."), None)?;
+            self.chunk_models.push(Mutex::new(model));
+        }
+        if parallelism > 1 {
+            info!("Loaded {} extra Parakeet instances for parallel chunk transcription", parallelism - 1);
+        }
+        self.chunk_config = self.chunk_config.with_parallelism(parallelism);
+        Ok(self)
+    }
+
+    /// Lock an idle model instance, waiting on the primary one if all are busy
+    fn lock_model(&self) -> Result<MutexGuard<'_, ParakeetTDT>> {
+        for model in std::iter::once(self.parakeet.as_ref()).chain(self.chunk_models.iter()) {
+            if let Ok(guard) = model.try_lock() {
+                return Ok(guard);
+            }
+        }
+        self.parakeet.lock()
+            .map_err(|e| anyhow::anyhow!("Parakeet model lock poisoned: {}", e))
+    }
+
+    /// Bound the in-memory audio buffer to `max_seconds` (0 = unbounded).
+    ///
+    /// Older audio is spilled to `spill_path` (still included in the final pass)
+    /// or dropped when no spill path is given. Preview only covers the in-memory audio.
+    pub fn with_buffer_limit(self, max_seconds: u64, spill_path: Option<PathBuf>) -> Self {
+        let max_samples = max_seconds as usize * self.sample_rate as usize;
+        if let Ok(mut buffer) = self.audio_buffer.lock() {
+            *buffer = BoundedAudioBuffer::new(max_samples, spill_path);
+        }
+        self
+    }
+
+    /// Pad the final-pass audio with `pad_ms` of silence at each end (0 = off).
+    ///
+    /// Audio that starts or stops abruptly can lose its first or last word; a
+    /// short lead-in and tail of silence gives the model room at the edges.
+    pub fn with_silence_padding(mut self, pad_ms: u64) -> Self {
+        self.final_pad_samples = (pad_ms * self.sample_rate as u64 / 1000) as usize;
+        self
+    }
+
+    /// Cut leading and trailing silence from the final-pass audio (None = off).
+    ///
+    /// Less audio to transcribe, and no long silent tail for the model to
+    /// hallucinate on. Trimming happens before silence padding.
+    pub fn with_silence_trim(mut self, trim: Option<SilenceTrim>) -> Self {
+        self.final_trim = trim;
+        self
+    }
+
+    /// Limit the live preview to the last `seconds` of audio (0 = whole buffer).
+    ///
+    /// Keeps preview latency flat on long dictations; the final pass still
+    /// covers the full session, but the cached preview text (used for short
+    /// utterances and as the last fallback) only covers the window.
+    pub fn with_preview_window(mut self, seconds: u64) -> Self {
+        self.preview_window_samples = seconds as usize * self.sample_rate as usize;
+        self
+    }
+
+    /// Remove undecodable replacement characters from transcriptions (default
+    /// on). When off they are only logged, and typed as U+FFFD.
+    pub fn with_undecodable_dropping(mut self, drop: bool) -> Self {
+        self.drop_undecodable = drop;
+        self
+    }
+
+    /// Break transcribed text at long pauses between words (None = off).
+    ///
+    /// Uses Parakeet's word timestamps; when a pass returns none the text is
+    /// left unchanged.
+    pub fn with_pause_split(mut self, split: Option<PauseSplit>) -> Self {
+        self.chunk_config.pause_split = split;
+        self
+    }
+
+    /// Surround `samples` with `pad` zero samples on each side
+    fn pad_with_silence(samples: &[i16], pad: usize) -> Vec<i16> {
+        let mut padded = Vec::with_capacity(samples.len() + 2 * pad);
+        padded.resize(pad, 0);
+        padded.extend_from_slice(samples);
+        padded.resize(samples.len() + 2 * pad, 0);
+        padded
+    }
+
     /// Convert i16 samples to f32 for parakeet-rs
     fn samples_to_f32(samples: &[i16]) -> Vec<f32> {
         samples.iter().map(|&s| s as f32 / 32768.0).collect()
@@ -92,8 +227,7 @@ impl ParakeetEngine {
         }
 
         let f32_samples = Self::samples_to_f32(samples);
-        let mut parakeet = self.parakeet.lock()
-            .map_err(|e| anyhow::anyhow!("Parakeet model lock poisoned: {}", e))?;
+        let mut parakeet = self.lock_model()?;
         let result = parakeet.transcribe_samples(f32_samples, self.sample_rate, 1, Some(TimestampMode::Words))?;
 
         Ok(TimestampedChunkResult {
@@ -126,15 +260,73 @@ impl ParakeetEngine {
         let samples = &normalized;
 
         // Use timestamped chunking for better merge accuracy when chunking is needed
-        if self.chunk_config.needs_chunking(samples) {
-            return transcribe_chunked_with_timestamps(samples, &self.chunk_config, |chunk| {
+        let text = if self.chunk_config.needs_chunking(samples) {
+            transcribe_chunked_with_timestamps(samples, &self.chunk_config, |chunk| {
+                if self.cancelled.load(Ordering::Relaxed) {
+                    anyhow::bail!("Transcription cancelled");
+                }
                 self.transcribe_chunk_with_timestamps(chunk)
-            });
+            })?
+        } else if let Some(split) = self.chunk_config.pause_split {
+            // Short audio, but pause splitting needs the word timestamps
+            let result = self.transcribe_chunk_with_timestamps(samples)?;
+            if result.words.is_empty() {
+                result.text
+            } else {
+                pause_split::join_words(&result.words.iter().collect::<Vec<_>>(), Some(split))
+            }
+        } else {
+            // Short audio: single-pass transcription
+            self.transcribe_chunk(samples)?
+        };
+
+        if !self.drop_undecodable {
+            if text.contains(char::REPLACEMENT_CHARACTER) {
+                warn!("Transcription contained undecodable characters: '{}'", text);
+            }
+            return Ok(text);
         }
+        Ok(drop_undecodable(&text))
+    }
+}
+
+/// The last `window` samples of `samples` (all of them when `window` is 0)
+fn preview_window(samples: &[i16], window: usize) -> &[i16] {
+    if window == 0 || samples.len() <= window {
+        samples
+    } else {
+        &samples[samples.len() - window..]
+    }
+}
 
-        // Short audio: single-pass transcription
-        self.transcribe_chunk(samples)
+/// Remove U+FFFD replacement characters left by undecodable byte tokens.
+///
+/// Words made up mostly of replacement characters are dropped entirely; in
+/// other words the stray characters are removed, so "word\u{FFFD}s" types as "words".
+fn drop_undecodable(text: &str) -> String {
+    if !text.contains(char::REPLACEMENT_CHARACTER) {
+        return text.to_string();
     }
+
+    let mut dropped = 0;
+    let words: Vec<String> = text
+        .split_whitespace()
+        .filter_map(|word| {
+            let total = word.chars().count();
+            let replaced = word.chars().filter(|&c| c == char::REPLACEMENT_CHARACTER).count();
+            if replaced * 2 >= total {
+                dropped += 1;
+                return None;
+            }
+            Some(word.replace(char::REPLACEMENT_CHARACTER, ""))
+        })
+        .collect();
+
+    warn!(
+        "Transcription contained undecodable characters ({} garbled word(s) dropped): '{}'",
+        dropped, text
+    );
+    words.join(" ")
 }
 
 /// Normalize audio to a target RMS level for consistent transcription quality.
@@ -178,7 +370,7 @@ impl TranscriptionEngine for ParakeetEngine {
         // Running it here blocks audio capture and causes data loss
         let mut buffer = self.audio_buffer.lock()
             .map_err(|e| anyhow::anyhow!("Audio buffer lock poisoned: {}", e))?;
-        buffer.extend_from_slice(samples);
+        buffer.push(samples);
         Ok(())
     }
 
@@ -197,11 +389,12 @@ impl TranscriptionEngine for ParakeetEngine {
         }
 
         // Need minimum audio to transcribe
-        if buffer.len() < MIN_AUDIO_SAMPLES {
+        if buffer.recent().len() < MIN_AUDIO_SAMPLES {
             return Ok(String::new());
         }
 
-        let current_len = buffer.len();
+        // Total pushed samples keeps growing even when old audio is evicted
+        let current_len = buffer.total_len();
         let last_len_val = {
             let last_len = self.last_transcribed_len.lock()
                 .map_err(|e| anyhow::anyhow!("Last transcribed len lock poisoned: {}", e))?;
@@ -216,14 +409,24 @@ impl TranscriptionEngine for ParakeetEngine {
             return Ok(cached.clone());
         }
 
-        // Transcribe FULL buffer (same as get_final_result)
-        let full_audio = buffer.clone();
+        // Transcribe the in-memory buffer (the full session unless it exceeded the limit),
+        // or just its tail when a preview window is set
+        let full_audio = preview_window(buffer.recent(), self.preview_window_samples).to_vec();
         drop(buffer);
 
+        // A preview already in progress keeps the model busy; return the last text
+        // instead of queueing another inference behind it
+        if self.preview_in_flight.swap(true, Ordering::AcqRel) {
+            debug!("Preview transcription still running, reusing cached text");
+            return Ok(self.get_cached_text());
+        }
+
         debug!("Preview transcription: {} samples ({:.2}s)",
                full_audio.len(), full_audio.len() as f32 / 16000.0);
 
-        let full_text = self.transcribe_buffer(&full_audio)?;
+        let transcribed = self.transcribe_buffer(&full_audio);
+        self.preview_in_flight.store(false, Ordering::Release);
+        let full_text = transcribed?;
 
         // Replace cache with new result (not append)
         // Lock ordering: current_text -> last_transcribed_len
@@ -237,6 +440,7 @@ impl TranscriptionEngine for ParakeetEngine {
                 .map_err(|e| anyhow::anyhow!("Last transcribed len lock poisoned: {}", e))?;
             *last_len = current_len;
         }
+        self.text_covers_session.store(full_audio.len() == current_len, Ordering::Relaxed);
 
         Ok(full_text)
     }
@@ -244,11 +448,29 @@ impl TranscriptionEngine for ParakeetEngine {
     fn get_final_result(&self) -> Result<String> {
         let buffer = self.audio_buffer.lock()
             .map_err(|e| anyhow::anyhow!("Audio buffer lock poisoned: {}", e))?;
-        let samples = buffer.clone();
+        let mut samples = buffer.to_vec()?;
         drop(buffer);
+        if let Some(trim) = self.final_trim {
+            let range = trim.speech_range(&samples, self.sample_rate);
+            if range.len() < samples.len() {
+                debug!(
+                    "Trimmed silence for final pass: {} leading, {} trailing samples",
+                    range.start,
+                    samples.len() - range.end
+                );
+                samples = samples[range].to_vec();
+            }
+        }
+        if self.final_pad_samples > 0 && !samples.is_empty() {
+            return self.transcribe_buffer(&Self::pad_with_silence(&samples, self.final_pad_samples));
+        }
         self.transcribe_buffer(&samples)
     }
 
+    fn cached_text_covers_session(&self) -> bool {
+        self.text_covers_session.load(Ordering::Relaxed)
+    }
+
     fn get_cached_text(&self) -> String {
         // Return the cached preview text without re-transcribing
         // Useful in single-model mode where preview already has full transcription
@@ -259,11 +481,33 @@ impl TranscriptionEngine for ParakeetEngine {
 
     fn get_audio_buffer(&self) -> Vec<i16> {
         self.audio_buffer.lock()
-            .map(|guard| guard.clone())
+            .ok()
+            .and_then(|guard| guard.to_vec().ok())
             .unwrap_or_default()
     }
 
+    fn clear_buffer(&self) -> Result<()> {
+        self.cancelled.store(false, Ordering::Relaxed);
+        self.text_covers_session.store(true, Ordering::Relaxed);
+        // Same lock ordering as reset(), but a poisoned lock is an error
+        self.audio_buffer.lock()
+            .map_err(|e| anyhow::anyhow!("Audio buffer lock poisoned: {}", e))?
+            .clear();
+        self.current_text.lock()
+            .map_err(|e| anyhow::anyhow!("Current text lock poisoned: {}", e))?
+            .clear();
+        *self.last_transcribed_len.lock()
+            .map_err(|e| anyhow::anyhow!("Last transcribed len lock poisoned: {}", e))? = 0;
+        Ok(())
+    }
+
+    fn cancel_final_pass(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
     fn reset(&self) {
+        self.cancelled.store(false, Ordering::Relaxed);
+        self.text_covers_session.store(true, Ordering::Relaxed);
         // Lock ordering: audio_buffer -> current_text -> last_transcribed_len
         // Using if-let to gracefully handle poisoned locks without panicking
         if let Ok(mut buffer) = self.audio_buffer.lock() {
@@ -277,3 +521,54 @@ impl TranscriptionEngine for ParakeetEngine {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_silence_padding_length() {
+        // 200ms at 16kHz = 3200 samples on each side
+        let pad = (200 * PARAKEET_SAMPLE_RATE as u64 / 1000) as usize;
+        let samples = vec![1000i16; 8000];
+        let padded = ParakeetEngine::pad_with_silence(&samples, pad);
+
+        assert_eq!(padded.len(), 8000 + 2 * 3200);
+        assert!(padded[..pad].iter().all(|&s| s == 0));
+        assert!(padded[pad + 8000..].iter().all(|&s| s == 0));
+        assert_eq!(&padded[pad..pad + 8000], samples.as_slice());
+    }
+
+    #[test]
+    fn test_undecodable_bytes_are_removed() {
+        // "caf" + invalid continuation + " ok " + a whole invalid word
+        let decoded = String::from_utf8_lossy(b"caf\xC3 ok \xFF\xFE\xFD end").into_owned();
+        assert!(decoded.contains(char::REPLACEMENT_CHARACTER));
+        assert_eq!(drop_undecodable(&decoded), "caf ok end");
+    }
+
+    #[test]
+    fn test_mostly_garbled_word_dropped() {
+        assert_eq!(drop_undecodable("hello a\u{FFFD}\u{FFFD} world"), "hello world");
+        assert_eq!(drop_undecodable("wor\u{FFFD}ds"), "words");
+    }
+
+    #[test]
+    fn test_clean_text_unchanged() {
+        assert_eq!(drop_undecodable("  spacing is kept "), "  spacing is kept ");
+    }
+
+    #[test]
+    fn test_preview_window_keeps_tail() {
+        let samples: Vec<i16> = (0..10).collect();
+        assert_eq!(preview_window(&samples, 3), &[7, 8, 9]);
+        assert_eq!(preview_window(&samples, 0), samples.as_slice());
+        assert_eq!(preview_window(&samples, 20), samples.as_slice());
+    }
+
+    #[test]
+    fn test_zero_padding_is_identity() {
+        let samples = vec![1i16, 2, 3];
+        assert_eq!(ParakeetEngine::pad_with_silence(&samples, 0), samples);
+    }
+}
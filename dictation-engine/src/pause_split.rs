@@ -0,0 +1,146 @@
+//! Breaking run-on transcriptions at long pauses.
+//!
+//! Parakeet's word timestamps show where the speaker stopped; a gap between
+//! two words longer than the threshold becomes a sentence end, line break or
+//! paragraph break instead of a plain space.
+
+use parakeet_rs::TimedToken;
+
+/// What a long pause turns into
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PauseBreak {
+    /// End the sentence (". ") and capitalize the next word
+    #[default]
+    Sentence,
+    Newline,
+    /// Blank line between paragraphs
+    Paragraph,
+}
+
+impl PauseBreak {
+    /// Parse from config string
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "sentence" | "period" | ". " => Some(PauseBreak::Sentence),
+            "newline" | "line" | "\n" => Some(PauseBreak::Newline),
+            "paragraph" => Some(PauseBreak::Paragraph),
+            _ => None,
+        }
+    }
+}
+
+/// Pause length and the break it produces
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PauseSplit {
+    pub threshold_secs: f32,
+    pub separator: PauseBreak,
+}
+
+/// Join timestamped words into text, breaking at pauses when `split` is set
+pub fn join_words(words: &[&TimedToken], split: Option<PauseSplit>) -> String {
+    let mut result = String::new();
+    let mut previous: Option<&TimedToken> = None;
+
+    for word in words {
+        let is_standalone_punct = word.text.len() == 1
+            && word.text.chars().all(|c| matches!(c, '.' | ',' | '!' | '?' | ';' | ':'));
+
+        let pause = match (previous, split) {
+            (Some(prev), Some(split)) if !is_standalone_punct && word.start - prev.end >= split.threshold_secs => {
+                Some(split.separator)
+            }
+            _ => None,
+        };
+
+        match pause {
+            Some(PauseBreak::Sentence) => {
+                end_sentence(&mut result);
+                result.push(' ');
+                result.push_str(&capitalize_first(&word.text));
+            }
+            Some(separator) => {
+                let kept = result.trim_end().len();
+                result.truncate(kept);
+                result.push_str(if separator == PauseBreak::Paragraph { "\n\n" } else { "\n" });
+                result.push_str(&word.text);
+            }
+            None => {
+                if previous.is_some() && !is_standalone_punct {
+                    result.push(' ');
+                }
+                result.push_str(&word.text);
+            }
+        }
+        previous = Some(word);
+    }
+
+    result
+}
+
+/// Close the sentence at the end of `text` with a period unless it already ends one
+fn end_sentence(text: &mut String) {
+    let kept = text.trim_end().trim_end_matches([',', ';', ':']).len();
+    text.truncate(kept);
+    if !text.ends_with(['.', '!', '?']) {
+        text.push('.');
+    }
+}
+
+fn capitalize_first(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        None => String::new(),
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn token(text: &str, start: f32, end: f32) -> TimedToken {
+        TimedToken { text: text.to_string(), start, end }
+    }
+
+    fn words() -> Vec<TimedToken> {
+        vec![
+            token("first", 0.0, 0.4),
+            token("point,", 0.4, 0.8),
+            token("second", 2.5, 2.9),
+            token("point", 2.9, 3.3),
+            token(".", 3.3, 3.4),
+        ]
+    }
+
+    fn join(words: &[TimedToken], split: Option<PauseSplit>) -> String {
+        join_words(&words.iter().collect::<Vec<_>>(), split)
+    }
+
+    #[test]
+    fn test_no_split_joins_with_spaces() {
+        assert_eq!(join(&words(), None), "first point, second point.");
+    }
+
+    #[test]
+    fn test_sentence_break_at_long_pause() {
+        let split = PauseSplit { threshold_secs: 1.0, separator: PauseBreak::Sentence };
+        assert_eq!(join(&words(), Some(split)), "first point. Second point.");
+    }
+
+    #[test]
+    fn test_paragraph_break_and_short_pauses_ignored() {
+        let split = PauseSplit { threshold_secs: 1.0, separator: PauseBreak::Paragraph };
+        assert_eq!(join(&words(), Some(split)), "first point,\n\nsecond point.");
+
+        let split = PauseSplit { threshold_secs: 2.0, separator: PauseBreak::Newline };
+        assert_eq!(join(&words(), Some(split)), "first point, second point.");
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!(PauseBreak::from_str("Sentence"), Some(PauseBreak::Sentence));
+        assert_eq!(PauseBreak::from_str(" newline "), Some(PauseBreak::Newline));
+        assert_eq!(PauseBreak::from_str("paragraph"), Some(PauseBreak::Paragraph));
+        assert_eq!(PauseBreak::from_str("tab"), None);
+    }
+}
@@ -1,6 +1,10 @@
 use super::TextProcessor;
-use anyhow::Result;
+use anyhow::{Context, Result};
 use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+use tracing::warn;
 
 /// Acronym detection and conversion processor.
 ///
@@ -10,9 +14,16 @@ use std::collections::HashSet;
 /// - "u r l" → "URL"
 ///
 /// Uses a curated dictionary of common acronyms plus pattern matching
-/// for generic 2-5 letter sequences.
+/// for generic 2-5 letter sequences. Spoken forms from the user's
+/// `acronyms.toml` ("sequel" → "SQL") are checked first.
+///
+/// Cloning shares the dictionaries, so a processor loaded once can be handed
+/// to every pipeline.
+#[derive(Clone)]
 pub struct AcronymProcessor {
-    known_acronyms: HashSet<String>,
+    known_acronyms: Arc<HashSet<String>>,
+    /// Spoken words → output from a user file, longest phrase first
+    custom: Arc<Vec<(Vec<String>, String)>>,
 }
 
 impl AcronymProcessor {
@@ -79,7 +90,49 @@ impl AcronymProcessor {
         known_acronyms.insert("OS".to_string());
         known_acronyms.insert("VM".to_string());
 
-        Self { known_acronyms }
+        Self { known_acronyms: Arc::new(known_acronyms), custom: Arc::new(Vec::new()) }
+    }
+
+    /// Create a processor with the curated dictionary plus the mappings in
+    /// `path`, a TOML table of spoken form → output (`"a p i" = "API"`,
+    /// `"sequel" = "SQL"`).
+    ///
+    /// Entries whose value isn't a non-empty string are logged and skipped;
+    /// an unreadable or unparsable file is an error.
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read acronym file {}", path.display()))?;
+        let table: toml::Table = toml::from_str(&content)
+            .with_context(|| format!("Failed to parse acronym file {}", path.display()))?;
+
+        let mut custom = Vec::new();
+        for (spoken, output) in table {
+            let words: Vec<String> = spoken.split_whitespace().map(normalize).filter(|w| !w.is_empty()).collect();
+            match output {
+                toml::Value::String(output) if !words.is_empty() && !output.trim().is_empty() => {
+                    custom.push((words, output.trim().to_string()));
+                }
+                other => warn!("Skipping acronym entry {:?} = {} in {}", spoken, other, path.display()),
+            }
+        }
+        custom.sort_by(|a, b| b.0.len().cmp(&a.0.len()));
+
+        Ok(Self { custom: Arc::new(custom), ..Self::new() })
+    }
+
+    /// Create a processor from `~/.config/voice-dictation/acronyms.toml` when
+    /// it exists, falling back to the curated dictionary alone.
+    pub fn from_user_config() -> Self {
+        let Some(path) = dirs::config_dir().map(|dir| dir.join("voice-dictation").join("acronyms.toml")) else {
+            return Self::new();
+        };
+        if !path.exists() {
+            return Self::new();
+        }
+        Self::from_file(&path).unwrap_or_else(|e| {
+            warn!("{:#}, using built-in acronyms only", e);
+            Self::new()
+        })
     }
 }
 
@@ -95,7 +148,8 @@ impl TextProcessor for AcronymProcessor {
 
         while i < words.len() {
             // Try to match an acronym pattern starting at position i
-            if let Some((acronym, consumed)) = self.try_match_acronym(&words[i..]) {
+            let matched = self.try_match_custom(&words[i..]).or_else(|| self.try_match_acronym(&words[i..]));
+            if let Some((acronym, consumed)) = matched {
                 result.push(acronym);
                 i += consumed;
             } else {
@@ -109,6 +163,21 @@ impl TextProcessor for AcronymProcessor {
 }
 
 impl AcronymProcessor {
+    /// Try to match a user mapping starting from the beginning of the slice,
+    /// keeping punctuation attached to the last spoken word ("sequel." → "SQL.").
+    ///
+    /// Returns (output, number_of_words_consumed) if successful.
+    fn try_match_custom(&self, words: &[&str]) -> Option<(String, usize)> {
+        self.custom.iter().find_map(|(phrase, output)| {
+            if words.len() < phrase.len() || !words.iter().zip(phrase).all(|(w, p)| normalize(w) == *p) {
+                return None;
+            }
+            let last = words[phrase.len() - 1];
+            let trailing = &last[last.trim_end_matches(|c: char| !c.is_alphanumeric()).len()..];
+            Some((format!("{}{}", output, trailing), phrase.len()))
+        })
+    }
+
     /// Try to match an acronym pattern starting from the beginning of the slice.
     ///
     /// Returns (acronym_string, number_of_words_consumed) if successful.
@@ -152,6 +221,11 @@ impl Default for AcronymProcessor {
     }
 }
 
+/// Lowercase and strip surrounding punctuation for matching spoken forms
+fn normalize(word: &str) -> String {
+    word.trim_matches(|c: char| !c.is_alphanumeric()).to_lowercase()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -227,6 +301,27 @@ mod tests {
         assert_eq!(result, "AI model");
     }
 
+    #[test]
+    fn test_custom_mappings_from_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("acronyms.toml");
+        fs::write(&path, "\"sequel\" = \"SQL\"\n\"k eight s\" = \"K8s\"\nbroken = 5\n").unwrap();
+
+        let processor = AcronymProcessor::from_file(&path).unwrap();
+        let result = processor.process("query the sequel db on k eight s via a p i").unwrap();
+        assert_eq!(result, "query the SQL db on K8s via API");
+        assert_eq!(processor.process("Deploy to K eight s.").unwrap(), "Deploy to K8s.");
+    }
+
+    #[test]
+    fn test_malformed_file_is_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("acronyms.toml");
+        fs::write(&path, "sequel = ").unwrap();
+        assert!(AcronymProcessor::from_file(&path).is_err());
+        assert!(AcronymProcessor::from_file(&dir.path().join("missing.toml")).is_err());
+    }
+
     #[test]
     fn test_preserve_non_acronyms() {
         let processor = AcronymProcessor::new();
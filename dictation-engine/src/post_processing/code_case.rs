@@ -0,0 +1,194 @@
+//! Code identifier casing driven by spoken mode-switch commands.
+//!
+//! "snake case user profile settings normal case" → "user_profile_settings".
+//! Words between a case command and "normal case" (or the end of the
+//! utterance) are joined into a single identifier.
+
+use super::TextProcessor;
+use anyhow::Result;
+
+/// Identifier case styles
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaseStyle {
+    Snake,
+    Camel,
+    Pascal,
+    Kebab,
+}
+
+impl CaseStyle {
+    /// Join lowercase words into an identifier
+    fn join(self, words: &[String]) -> String {
+        match self {
+            CaseStyle::Snake => words.join("_"),
+            CaseStyle::Kebab => words.join("-"),
+            CaseStyle::Camel => words
+                .iter()
+                .enumerate()
+                .map(|(i, w)| if i == 0 { w.clone() } else { capitalize_first(w) })
+                .collect(),
+            CaseStyle::Pascal => words.iter().map(|w| capitalize_first(w)).collect(),
+        }
+    }
+}
+
+/// Processor that applies spoken code-case commands.
+pub struct CodeCaseProcessor;
+
+impl CodeCaseProcessor {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl TextProcessor for CodeCaseProcessor {
+    fn process(&self, text: &str) -> Result<String> {
+        let words: Vec<&str> = text.split_whitespace().collect();
+        let mut output: Vec<String> = Vec::with_capacity(words.len());
+        let mut style: Option<CaseStyle> = None;
+        let mut segment: Vec<String> = Vec::new();
+        let mut segment_suffix = String::new();
+
+        let mut i = 0;
+        while i < words.len() {
+            // Two-word commands: "<style> case"
+            if i + 1 < words.len() && normalize(words[i + 1]) == "case" {
+                let command = match normalize(words[i]).as_str() {
+                    "snake" => Some(Some(CaseStyle::Snake)),
+                    "camel" => Some(Some(CaseStyle::Camel)),
+                    "pascal" => Some(Some(CaseStyle::Pascal)),
+                    "kebab" => Some(Some(CaseStyle::Kebab)),
+                    "normal" if style.is_some() => Some(None),
+                    _ => None,
+                };
+
+                if let Some(new_style) = command {
+                    flush_segment(&mut output, style, &mut segment, &mut segment_suffix);
+                    style = new_style;
+                    i += 2;
+                    continue;
+                }
+            }
+
+            let word = words[i];
+            if style.is_some() {
+                let normalized = normalize(word);
+                if !normalized.is_empty() {
+                    segment.push(normalized);
+                }
+                // Keep sentence punctuation that followed the last word of the identifier
+                segment_suffix = trailing_punctuation(word).to_string();
+            } else {
+                output.push(word.to_string());
+            }
+            i += 1;
+        }
+
+        flush_segment(&mut output, style, &mut segment, &mut segment_suffix);
+
+        Ok(output.join(" "))
+    }
+}
+
+/// Emit the pending identifier (if any) into the output
+fn flush_segment(
+    output: &mut Vec<String>,
+    style: Option<CaseStyle>,
+    segment: &mut Vec<String>,
+    suffix: &mut String,
+) {
+    if let Some(style) = style {
+        if !segment.is_empty() {
+            output.push(format!("{}{}", style.join(segment), suffix));
+        }
+    }
+    segment.clear();
+    suffix.clear();
+}
+
+/// Lowercase and strip non-alphanumeric characters
+fn normalize(word: &str) -> String {
+    word.chars().filter(|c| c.is_alphanumeric()).collect::<String>().to_lowercase()
+}
+
+/// Trailing punctuation attached to a word (e.g. "settings." → ".")
+fn trailing_punctuation(word: &str) -> &str {
+    let trimmed = word.trim_end_matches(|c: char| !c.is_alphanumeric());
+    &word[trimmed.len()..]
+}
+
+fn capitalize_first(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        None => String::new(),
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn process(text: &str) -> String {
+        CodeCaseProcessor::new().process(text).unwrap()
+    }
+
+    #[test]
+    fn test_snake_case() {
+        assert_eq!(process("snake case user profile settings"), "user_profile_settings");
+    }
+
+    #[test]
+    fn test_camel_case() {
+        assert_eq!(process("camel case user profile settings"), "userProfileSettings");
+    }
+
+    #[test]
+    fn test_pascal_case() {
+        assert_eq!(process("pascal case user profile settings"), "UserProfileSettings");
+    }
+
+    #[test]
+    fn test_kebab_case() {
+        assert_eq!(process("kebab case user profile settings"), "user-profile-settings");
+    }
+
+    #[test]
+    fn test_normal_case_ends_segment() {
+        assert_eq!(
+            process("set snake case max retries normal case to five"),
+            "set max_retries to five"
+        );
+    }
+
+    #[test]
+    fn test_switch_between_styles() {
+        assert_eq!(process("camel case get user snake case user id"), "getUser user_id");
+    }
+
+    #[test]
+    fn test_capitalized_commands_and_punctuation() {
+        assert_eq!(process("Snake case user, profile settings."), "user_profile_settings.");
+    }
+
+    #[test]
+    fn test_no_commands_unchanged() {
+        assert_eq!(process("this is a normal sentence"), "this is a normal sentence");
+    }
+
+    #[test]
+    fn test_normal_case_outside_code_mode_unchanged() {
+        assert_eq!(process("in the normal case it works"), "in the normal case it works");
+    }
+
+    #[test]
+    fn test_case_word_alone_unchanged() {
+        assert_eq!(process("in any case we ship"), "in any case we ship");
+    }
+
+    #[test]
+    fn test_empty_input() {
+        assert_eq!(process(""), "");
+        assert_eq!(process("snake case"), "");
+    }
+}
@@ -0,0 +1,164 @@
+//! Spoken command detection in transcribed text.
+//!
+//! Commands are recognized only at the edges of an utterance so that ordinary
+//! words like "send" mid-sentence are typed as-is.
+
+use super::ProcessorKind;
+
+/// Trailing phrases that submit the text (inject Enter) after typing.
+/// Longer phrases first so "send it" wins over "send".
+const SUBMIT_PHRASES: &[&[&str]] = &[&["send", "it"], &["submit", "it"], &["submit"], &["send"]];
+
+/// Strip a trailing submit command from `text`.
+///
+/// Returns the remaining text and whether a submit command was found.
+/// Matching is case-insensitive and ignores punctuation attached to the
+/// command words (e.g. "Send it." matches).
+pub fn strip_submit_command(text: &str) -> (String, bool) {
+    let words: Vec<&str> = text.split_whitespace().collect();
+
+    for phrase in SUBMIT_PHRASES {
+        if words.len() < phrase.len() {
+            continue;
+        }
+
+        let tail = &words[words.len() - phrase.len()..];
+        let matches =
+            tail.iter().zip(phrase.iter()).all(|(word, expected)| normalize(word) == *expected);
+
+        if matches {
+            let remaining = words[..words.len() - phrase.len()].join(" ");
+            let remaining = remaining.trim_end_matches([',', ';', ':']).to_string();
+            return (remaining, true);
+        }
+    }
+
+    (text.to_string(), false)
+}
+
+/// Strip "<processor> on|off" commands ("punctuation off", "code case on") from
+/// the start and end of `text`.
+///
+/// Returns the remaining text and the toggles in spoken order.
+pub fn strip_processor_toggles(text: &str) -> (String, Vec<(ProcessorKind, bool)>) {
+    let mut words: Vec<&str> = text.split_whitespace().collect();
+    let mut toggles = Vec::new();
+
+    while let Some((toggle, len)) = parse_toggle(&words) {
+        toggles.push(toggle);
+        words.drain(..len);
+    }
+
+    let mut trailing = Vec::new();
+    loop {
+        let found = (2..=3).rev().filter(|&len| len <= words.len()).find_map(|len| {
+            parse_toggle(&words[words.len() - len..]).filter(|&(_, parsed)| parsed == len)
+        });
+        let Some((toggle, len)) = found else {
+            break;
+        };
+        trailing.push(toggle);
+        words.truncate(words.len() - len);
+    }
+    toggles.extend(trailing.into_iter().rev());
+
+    if toggles.is_empty() {
+        return (text.to_string(), toggles);
+    }
+    let remaining = words.join(" ").trim_end_matches([',', ';', ':']).to_string();
+    (remaining, toggles)
+}
+
+/// Match "<processor> on|off" at the start of `words`, returning the toggle and
+/// the number of words it spans
+fn parse_toggle(words: &[&str]) -> Option<((ProcessorKind, bool), usize)> {
+    // Two-word names ("code case") before single words
+    for name_len in [2, 1] {
+        if words.len() <= name_len {
+            continue;
+        }
+        let name: Vec<String> = words[..name_len].iter().map(|w| normalize(w)).collect();
+        let Some(kind) = ProcessorKind::from_name(&name.join("_")) else {
+            continue;
+        };
+        let enabled = match normalize(words[name_len]).as_str() {
+            "on" => true,
+            "off" => false,
+            _ => continue,
+        };
+        return Some(((kind, enabled), name_len + 1));
+    }
+    None
+}
+
+/// Lowercase a word and strip surrounding punctuation for command matching.
+fn normalize(word: &str) -> String {
+    word.trim_matches(|c: char| !c.is_alphanumeric()).to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trailing_send_it() {
+        let (text, submit) = strip_submit_command("hello there send it");
+        assert!(submit);
+        assert_eq!(text, "hello there");
+    }
+
+    #[test]
+    fn test_trailing_submit_with_punctuation() {
+        let (text, submit) = strip_submit_command("What time is it? Submit.");
+        assert!(submit);
+        assert_eq!(text, "What time is it?");
+    }
+
+    #[test]
+    fn test_strips_dangling_comma() {
+        let (text, submit) = strip_submit_command("sounds good, send it.");
+        assert!(submit);
+        assert_eq!(text, "sounds good");
+    }
+
+    #[test]
+    fn test_send_mid_sentence_is_not_command() {
+        let (text, submit) = strip_submit_command("please send the report today");
+        assert!(!submit);
+        assert_eq!(text, "please send the report today");
+    }
+
+    #[test]
+    fn test_command_only() {
+        let (text, submit) = strip_submit_command("Send.");
+        assert!(submit);
+        assert_eq!(text, "");
+    }
+
+    #[test]
+    fn test_no_command() {
+        let (text, submit) = strip_submit_command("hello world");
+        assert!(!submit);
+        assert_eq!(text, "hello world");
+    }
+
+    #[test]
+    fn test_leading_and_trailing_toggles() {
+        let (text, toggles) = strip_processor_toggles("Punctuation off. hello there, grammar on");
+        assert_eq!(text, "hello there");
+        assert_eq!(toggles, vec![(ProcessorKind::Punctuation, false), (ProcessorKind::Grammar, true)]);
+    }
+
+    #[test]
+    fn test_two_word_processor_name() {
+        let (text, toggles) = strip_processor_toggles("code case on markdown off");
+        assert_eq!(text, "");
+        assert_eq!(toggles, vec![(ProcessorKind::CodeCase, true), (ProcessorKind::Markdown, false)]);
+    }
+
+    #[test]
+    fn test_toggle_mid_sentence_is_not_command() {
+        let text = "keep the grammar on point please";
+        assert_eq!(strip_processor_toggles(text), (text.to_string(), vec![]));
+    }
+}
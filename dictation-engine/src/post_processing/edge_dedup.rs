@@ -0,0 +1,99 @@
+//! Reconciliation of the final pass against the live preview.
+//!
+//! The final pass occasionally doubles the first or last word ("hello hello
+//! world") or prepends a stray filler ("the send the report") that the preview
+//! of the same audio didn't have. Only these edge artifacts are trimmed, and
+//! only when the preview shows a clear shared prefix/suffix without them.
+
+/// Short words the final pass tends to hallucinate at the start of an utterance
+const STRAY_LEADING: &[&str] = &["the", "a", "uh", "um", "so"];
+
+/// Tokens of shared prefix required before dropping a stray leading word
+const MIN_SHARED_PREFIX: usize = 2;
+
+/// Remove doubled or stray edge tokens from `accurate` that `preview` lacks.
+///
+/// Returns `accurate` unchanged when there is no preview to compare against
+/// or the edges don't look like an artifact.
+pub fn dedup_edge_tokens(accurate: &str, preview: &str) -> String {
+    let mut words: Vec<&str> = accurate.split_whitespace().collect();
+    let preview: Vec<String> = preview.split_whitespace().map(normalize).collect();
+    if preview.is_empty() || words.len() < 2 {
+        return accurate.to_string();
+    }
+
+    let preview_doubles_first = preview.len() >= 2 && preview[0] == preview[1];
+    let preview_doubles_last = preview.len() >= 2 && preview[preview.len() - 1] == preview[preview.len() - 2];
+    let mut changed = false;
+
+    // Leading double: keep the first copy (it carries any capitalization)
+    if normalize(words[0]) == normalize(words[1]) && normalize(words[0]) == preview[0] && !preview_doubles_first {
+        words.remove(1);
+        changed = true;
+    } else if STRAY_LEADING.contains(&normalize(words[0]).as_str())
+        && normalize(words[0]) != preview[0]
+        && words.len() > MIN_SHARED_PREFIX
+        && preview.len() >= MIN_SHARED_PREFIX
+        && (0..MIN_SHARED_PREFIX).all(|i| normalize(words[i + 1]) == preview[i])
+    {
+        words.remove(0);
+        changed = true;
+    }
+
+    // Trailing double: keep the last copy (it carries any final punctuation)
+    let n = words.len();
+    if n >= 2
+        && normalize(words[n - 1]) == normalize(words[n - 2])
+        && normalize(words[n - 1]) == preview[preview.len() - 1]
+        && !preview_doubles_last
+    {
+        words.remove(n - 2);
+        changed = true;
+    }
+
+    if changed {
+        words.join(" ")
+    } else {
+        accurate.to_string()
+    }
+}
+
+/// Lowercase a word and strip surrounding punctuation for comparison.
+fn normalize(word: &str) -> String {
+    word.trim_matches(|c: char| !c.is_alphanumeric()).to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_doubled_first_word() {
+        assert_eq!(dedup_edge_tokens("Hello hello world", "hello world"), "Hello world");
+    }
+
+    #[test]
+    fn test_doubled_last_word_keeps_punctuation() {
+        assert_eq!(dedup_edge_tokens("go home home.", "go home"), "go home.");
+    }
+
+    #[test]
+    fn test_stray_leading_filler() {
+        assert_eq!(dedup_edge_tokens("the send the report", "send the report"), "send the report");
+    }
+
+    #[test]
+    fn test_double_present_in_preview_is_kept() {
+        assert_eq!(dedup_edge_tokens("that that is fine", "that that is fine"), "that that is fine");
+    }
+
+    #[test]
+    fn test_leading_filler_without_shared_prefix_is_kept() {
+        assert_eq!(dedup_edge_tokens("the cat sat", "cat on"), "the cat sat");
+    }
+
+    #[test]
+    fn test_no_preview_is_noop() {
+        assert_eq!(dedup_edge_tokens("hello hello", ""), "hello hello");
+    }
+}
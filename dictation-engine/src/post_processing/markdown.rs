@@ -0,0 +1,209 @@
+//! Markdown structure driven by spoken formatting commands.
+//!
+//! "heading shopping list bullet point milk bullet point eggs" →
+//! "# shopping list\n- milk\n- eggs". Block commands ("heading [two]",
+//! "bullet point") start a new line with their marker; "bold" and "italic"
+//! wrap the following words until the next formatting command, "end bold" /
+//! "end italic", or the end of the utterance.
+
+use super::TextProcessor;
+use anyhow::Result;
+
+/// A recognized formatting command
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Command {
+    Heading(usize),
+    Bullet,
+    Emphasis(Emphasis),
+    EndEmphasis,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Emphasis {
+    Bold,
+    Italic,
+}
+
+impl Emphasis {
+    fn delimiter(self) -> &'static str {
+        match self {
+            Emphasis::Bold => "**",
+            Emphasis::Italic => "*",
+        }
+    }
+}
+
+/// Processor that turns spoken formatting commands into markdown syntax.
+pub struct MarkdownProcessor;
+
+impl MarkdownProcessor {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl TextProcessor for MarkdownProcessor {
+    fn process(&self, text: &str) -> Result<String> {
+        let words: Vec<&str> = text.split_whitespace().collect();
+        let mut output = String::new();
+        let mut emphasis: Option<Emphasis> = None;
+        let mut span: Vec<&str> = Vec::new();
+
+        let mut i = 0;
+        while i < words.len() {
+            let Some((command, len)) = parse_command(&words[i..]) else {
+                match emphasis {
+                    Some(_) => span.push(words[i]),
+                    None => push_word(&mut output, words[i]),
+                }
+                i += 1;
+                continue;
+            };
+
+            // Any formatting command closes an open emphasis span
+            flush_span(&mut output, emphasis.take(), &mut span);
+
+            match command {
+                Command::Heading(level) => start_block(&mut output, &format!("{} ", "#".repeat(level))),
+                Command::Bullet => start_block(&mut output, "- "),
+                Command::Emphasis(kind) => emphasis = Some(kind),
+                Command::EndEmphasis => {}
+            }
+            i += len;
+        }
+
+        flush_span(&mut output, emphasis, &mut span);
+
+        Ok(output.trim_end().to_string())
+    }
+}
+
+/// Match a formatting command at the start of `words`, returning it and the
+/// number of words it spans
+fn parse_command(words: &[&str]) -> Option<(Command, usize)> {
+    let first = normalize(words[0]);
+    let second = words.get(1).map(|w| normalize(w));
+
+    match (first.as_str(), second.as_deref()) {
+        ("heading", Some(level)) if heading_level(level).is_some() => {
+            Some((Command::Heading(heading_level(level)?), 2))
+        }
+        ("heading", _) => Some((Command::Heading(1), 1)),
+        ("bullet", Some("point")) => Some((Command::Bullet, 2)),
+        ("bullet", _) => Some((Command::Bullet, 1)),
+        ("bold", _) => Some((Command::Emphasis(Emphasis::Bold), 1)),
+        ("italic" | "italics", _) => Some((Command::Emphasis(Emphasis::Italic), 1)),
+        ("end", Some("bold" | "italic" | "italics" | "emphasis")) => Some((Command::EndEmphasis, 2)),
+        _ => None,
+    }
+}
+
+/// Spoken heading level ("heading two" → 2)
+fn heading_level(word: &str) -> Option<usize> {
+    match word {
+        "one" | "1" => Some(1),
+        "two" | "2" => Some(2),
+        "three" | "3" => Some(3),
+        "four" | "4" => Some(4),
+        "five" | "5" => Some(5),
+        "six" | "6" => Some(6),
+        _ => None,
+    }
+}
+
+/// Begin a new line with a block marker, dropping separator punctuation left
+/// at the end of the previous line
+fn start_block(output: &mut String, marker: &str) {
+    let kept = output.trim_end().trim_end_matches([',', ';', ':']).len();
+    output.truncate(kept);
+    if !output.is_empty() {
+        output.push('\n');
+    }
+    output.push_str(marker);
+}
+
+/// Emit the pending emphasis span, keeping trailing sentence punctuation outside
+/// the delimiters ("**important**.")
+fn flush_span(output: &mut String, emphasis: Option<Emphasis>, span: &mut Vec<&str>) {
+    if let Some(kind) = emphasis {
+        let joined = span.join(" ");
+        let inner = joined.trim_end_matches(|c: char| !c.is_alphanumeric());
+        if !inner.is_empty() {
+            let delimiter = kind.delimiter();
+            push_word(output, &format!("{}{}{}{}", delimiter, inner, delimiter, &joined[inner.len()..]));
+        }
+    }
+    span.clear();
+}
+
+fn push_word(output: &mut String, word: &str) {
+    if !output.is_empty() && !output.ends_with([' ', '\n']) {
+        output.push(' ');
+    }
+    output.push_str(word);
+}
+
+/// Lowercase and strip surrounding punctuation for command matching
+fn normalize(word: &str) -> String {
+    word.trim_matches(|c: char| !c.is_alphanumeric()).to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn process(text: &str) -> String {
+        MarkdownProcessor::new().process(text).unwrap()
+    }
+
+    #[test]
+    fn test_heading() {
+        assert_eq!(process("heading meeting notes"), "# meeting notes");
+    }
+
+    #[test]
+    fn test_heading_level() {
+        assert_eq!(process("Heading two, action items"), "## action items");
+        assert_eq!(process("heading 3 details"), "### details");
+    }
+
+    #[test]
+    fn test_bullet_list() {
+        assert_eq!(
+            process("heading shopping list bullet point milk, bullet point eggs"),
+            "# shopping list\n- milk\n- eggs"
+        );
+    }
+
+    #[test]
+    fn test_bold_until_end_of_utterance() {
+        assert_eq!(process("this is bold very important."), "this is **very important**.");
+    }
+
+    #[test]
+    fn test_explicit_end_of_emphasis() {
+        assert_eq!(process("this is bold very important end bold okay"), "this is **very important** okay");
+    }
+
+    #[test]
+    fn test_next_command_ends_emphasis() {
+        assert_eq!(process("italic maybe bold definitely"), "*maybe* **definitely**");
+        assert_eq!(process("bullet point bold milk bullet point eggs"), "- **milk**\n- eggs");
+    }
+
+    #[test]
+    fn test_empty_emphasis_dropped() {
+        assert_eq!(process("hello bold"), "hello");
+    }
+
+    #[test]
+    fn test_no_commands_unchanged() {
+        assert_eq!(process("plain dictated sentence."), "plain dictated sentence.");
+    }
+
+    #[test]
+    fn test_empty_input() {
+        assert_eq!(process(""), "");
+        assert_eq!(process("   "), "");
+    }
+}
@@ -1,17 +1,35 @@
 mod acronym;
+mod capitalization;
+mod code_case;
+mod commands;
+mod edge_dedup;
 mod grammar;
+mod markdown;
 mod punctuation;
+mod punctuation_commands;
 mod sanitize;
+mod spacing;
+mod unicode_form;
 
 use crate::user_dictionary::UserDictionary;
 use anyhow::Result;
+use std::collections::HashMap;
 use std::sync::Arc;
+use tracing::warn;
 
 pub use acronym::AcronymProcessor;
+pub use capitalization::CapitalizationProcessor;
+pub use code_case::CodeCaseProcessor;
+pub use commands::{strip_processor_toggles, strip_submit_command};
+pub use edge_dedup::dedup_edge_tokens;
 pub use grammar::GrammarProcessor;
-pub use punctuation::PunctuationProcessor;
+pub use markdown::MarkdownProcessor;
+pub use punctuation::{PunctuationContext, PunctuationProcessor};
+pub use punctuation_commands::{CommandProcessor, CommandVocabulary};
 pub use sanitize::SanitizationProcessor;
 pub use sanitize::SanitizationRules;
+pub use spacing::SpacingOptions;
+pub use unicode_form::UnicodeForm;
 
 /// Trait for text post-processors.
 ///
@@ -22,6 +40,95 @@ pub trait TextProcessor: Send + Sync {
     fn process(&self, text: &str) -> Result<String>;
 }
 
+/// Processors that can be ordered via `post_processing_order`.
+///
+/// Sanitization is not listed: it depends on the target window and always
+/// runs after the pipeline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ProcessorKind {
+    Acronyms,
+    PunctuationCommands,
+    Capitalization,
+    Punctuation,
+    Grammar,
+    CodeCase,
+    Markdown,
+}
+
+impl ProcessorKind {
+    /// Order used when `post_processing_order` is not configured.
+    /// Spoken punctuation runs before punctuation and capitalization so they
+    /// see the inserted symbols. Code casing and markdown run last so grammar
+    /// checking sees plain words.
+    pub const DEFAULT_ORDER: [ProcessorKind; 7] = [
+        ProcessorKind::Acronyms,
+        ProcessorKind::PunctuationCommands,
+        ProcessorKind::Punctuation,
+        ProcessorKind::Capitalization,
+        ProcessorKind::Grammar,
+        ProcessorKind::CodeCase,
+        ProcessorKind::Markdown,
+    ];
+
+    /// Parse a processor name from config ("code_case" and "code-case" both accepted)
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().replace('-', "_").as_str() {
+            "acronyms" | "acronym" => Some(ProcessorKind::Acronyms),
+            "punctuation_commands" => Some(ProcessorKind::PunctuationCommands),
+            "capitalization" | "capitalisation" => Some(ProcessorKind::Capitalization),
+            "punctuation" => Some(ProcessorKind::Punctuation),
+            "grammar" => Some(ProcessorKind::Grammar),
+            "code_case" => Some(ProcessorKind::CodeCase),
+            "markdown" => Some(ProcessorKind::Markdown),
+            _ => None,
+        }
+    }
+}
+
+/// Resolve a configured processor order.
+///
+/// Unknown and duplicate names are warned about and skipped. An empty list
+/// yields the default order.
+pub fn resolve_order(names: &[String]) -> Vec<ProcessorKind> {
+    if names.is_empty() {
+        return ProcessorKind::DEFAULT_ORDER.to_vec();
+    }
+
+    let mut order = Vec::with_capacity(names.len());
+    for name in names {
+        match ProcessorKind::from_name(name) {
+            Some(kind) if order.contains(&kind) => {
+                warn!("Duplicate post-processor '{}' in post_processing_order, ignoring", name);
+            }
+            Some(kind) => order.push(kind),
+            None => {
+                warn!("Unknown post-processor '{}' in post_processing_order (expected one of: acronyms, punctuation_commands, punctuation, capitalization, grammar, code_case, markdown)", name);
+            }
+        }
+    }
+    order
+}
+
+/// Runtime on/off overrides from spoken toggle commands.
+///
+/// Held in daemon memory only: they apply until changed again or the daemon
+/// restarts.
+#[derive(Debug, Clone, Default)]
+pub struct ProcessorOverrides {
+    overrides: HashMap<ProcessorKind, bool>,
+}
+
+impl ProcessorOverrides {
+    pub fn set(&mut self, kind: ProcessorKind, enabled: bool) {
+        self.overrides.insert(kind, enabled);
+    }
+
+    /// Whether `kind` runs, given its configured setting
+    pub fn enabled(&self, kind: ProcessorKind, configured: bool) -> bool {
+        self.overrides.get(&kind).copied().unwrap_or(configured)
+    }
+}
+
 /// Pipeline that orchestrates multiple text processors.
 ///
 /// Processors are applied in sequence, with each processor
@@ -46,45 +153,118 @@ impl Pipeline {
     /// Create a pipeline from configuration.
     ///
     /// Enables processors based on configuration flags.
-    /// Processors are applied in order: acronyms → punctuation → grammar.
+    /// Processors are applied in order: acronyms → punctuation → capitalization → grammar.
     pub fn from_config(
         enable_acronyms: bool,
         enable_punctuation: bool,
+        enable_capitalization: bool,
         enable_grammar: bool,
     ) -> Self {
-        Self::from_config_with_dict(enable_acronyms, enable_punctuation, enable_grammar, None)
+        Self::from_config_with_dict(
+            enable_acronyms,
+            enable_punctuation,
+            enable_capitalization,
+            enable_grammar,
+            None,
+        )
     }
 
     /// Create a pipeline from configuration with optional user dictionary.
     ///
     /// Enables processors based on configuration flags.
-    /// Processors are applied in order: acronyms → punctuation → grammar.
+    /// Processors are applied in order: acronyms → punctuation → capitalization → grammar.
     pub fn from_config_with_dict(
         enable_acronyms: bool,
         enable_punctuation: bool,
+        enable_capitalization: bool,
         enable_grammar: bool,
         user_dict: Option<Arc<UserDictionary>>,
     ) -> Self {
-        let mut pipeline = Self::new();
+        Self::from_order(
+            &ProcessorKind::DEFAULT_ORDER,
+            |kind| match kind {
+                ProcessorKind::Acronyms => enable_acronyms,
+                ProcessorKind::Capitalization => enable_capitalization,
+                ProcessorKind::Punctuation => enable_punctuation,
+                ProcessorKind::Grammar => enable_grammar,
+                ProcessorKind::PunctuationCommands | ProcessorKind::CodeCase | ProcessorKind::Markdown => false,
+            },
+            user_dict,
+        )
+    }
 
-        // Apply acronym detection first (a p i → API)
-        if enable_acronyms {
-            pipeline.add_processor(Box::new(AcronymProcessor::new()));
-        }
+    /// Create a pipeline with processors in the given order.
+    ///
+    /// Only processors that appear in `order` and for which `enabled` returns
+    /// true are added.
+    pub fn from_order(
+        order: &[ProcessorKind],
+        enabled: impl Fn(ProcessorKind) -> bool,
+        user_dict: Option<Arc<UserDictionary>>,
+    ) -> Self {
+        Self::from_order_with_context(order, enabled, user_dict, None, None, None)
+    }
 
-        // Then apply punctuation (capitalization)
-        if enable_punctuation {
-            pipeline.add_processor(Box::new(PunctuationProcessor::new()));
-        }
+    /// Like `from_order`, with a hint about the target used by the punctuation
+    /// processor to decide whether to append a terminal period, the spoken
+    /// punctuation vocabulary (built-in commands when None) and the acronym
+    /// processor loaded by the caller (curated dictionary only when None).
+    pub fn from_order_with_context(
+        order: &[ProcessorKind],
+        enabled: impl Fn(ProcessorKind) -> bool,
+        user_dict: Option<Arc<UserDictionary>>,
+        punctuation_context: Option<PunctuationContext>,
+        command_vocabulary: Option<Arc<CommandVocabulary>>,
+        acronyms: Option<AcronymProcessor>,
+    ) -> Self {
+        let mut pipeline = Self::new();
 
-        // Finally apply grammar checking
-        if enable_grammar {
-            if let Some(dict) = user_dict {
-                pipeline.add_processor(Box::new(GrammarProcessor::new_with_user_dictionary(
-                    dict,
-                )));
-            } else {
-                pipeline.add_processor(Box::new(GrammarProcessor::new()));
+        for &kind in order {
+            if !enabled(kind) {
+                continue;
+            }
+
+            match kind {
+                // a p i → API, plus the user's acronyms.toml
+                ProcessorKind::Acronyms => {
+                    pipeline.add_processor(Box::new(acronyms.clone().unwrap_or_default()));
+                }
+                // "period"/"comma"/"new line" → symbols
+                ProcessorKind::PunctuationCommands => {
+                    let processor = match command_vocabulary {
+                        Some(ref vocabulary) => CommandProcessor::with_vocabulary(Arc::clone(vocabulary)),
+                        None => CommandProcessor::new(),
+                    };
+                    pipeline.add_processor(Box::new(processor));
+                }
+                // Sentence-start and "I" capitalization
+                ProcessorKind::Capitalization => {
+                    pipeline.add_processor(Box::new(CapitalizationProcessor::new()));
+                }
+                // Terminal period and punctuation spacing
+                ProcessorKind::Punctuation => {
+                    let processor = match punctuation_context {
+                        Some(context) => PunctuationProcessor::with_context(context),
+                        None => PunctuationProcessor::new(),
+                    };
+                    pipeline.add_processor(Box::new(processor));
+                }
+                ProcessorKind::Grammar => {
+                    if let Some(ref dict) = user_dict {
+                        pipeline.add_processor(Box::new(GrammarProcessor::new_with_user_dictionary(
+                            Arc::clone(dict),
+                        )));
+                    } else {
+                        pipeline.add_processor(Box::new(GrammarProcessor::new()));
+                    }
+                }
+                ProcessorKind::CodeCase => {
+                    pipeline.add_processor(Box::new(CodeCaseProcessor::new()));
+                }
+                // "heading"/"bullet point"/"bold" → markdown syntax
+                ProcessorKind::Markdown => {
+                    pipeline.add_processor(Box::new(MarkdownProcessor::new()));
+                }
             }
         }
 
@@ -94,12 +274,30 @@ impl Pipeline {
     /// Process text through all processors in the pipeline.
     ///
     /// Returns the final processed result, or the original text
-    /// if no processors are enabled.
+    /// if no processors are enabled or it is empty/whitespace-only.
+    /// Lines are processed separately so line breaks (e.g. from pause
+    /// splitting or spoken "new line") survive processors that re-join words
+    /// with spaces.
     pub fn process(&self, text: &str) -> Result<String> {
+        Self::run(&self.processors, text)
+    }
+
+    fn run(processors: &[Box<dyn TextProcessor>], text: &str) -> Result<String> {
         let mut result = text.to_string();
+        if result.trim().is_empty() {
+            return Ok(result);
+        }
+        if result.contains('\n') {
+            let lines = result.split('\n').map(|line| Self::run(processors, line)).collect::<Result<Vec<_>>>()?;
+            return Ok(lines.join("\n"));
+        }
 
-        for processor in &self.processors {
+        for (i, processor) in processors.iter().enumerate() {
             result = processor.process(&result)?;
+            // A processor that broke the line hands the rest over line by line
+            if result.contains('\n') {
+                return Self::run(&processors[i + 1..], &result);
+            }
         }
 
         Ok(result)
@@ -116,3 +314,105 @@ impl Default for Pipeline {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_empty_order_uses_default() {
+        assert_eq!(resolve_order(&[]), ProcessorKind::DEFAULT_ORDER.to_vec());
+    }
+
+    #[test]
+    fn test_custom_order_preserved() {
+        let order = resolve_order(&names(&["code-case", "Punctuation", "acronyms"]));
+        assert_eq!(
+            order,
+            vec![ProcessorKind::CodeCase, ProcessorKind::Punctuation, ProcessorKind::Acronyms]
+        );
+    }
+
+    #[test]
+    fn test_unknown_and_duplicate_names_skipped() {
+        let order = resolve_order(&names(&["grammar", "numbers", "grammar", "acronyms"]));
+        assert_eq!(order, vec![ProcessorKind::Grammar, ProcessorKind::Acronyms]);
+    }
+
+    #[test]
+    fn test_from_order_respects_enabled_and_listing() {
+        // Acronyms enabled but not listed → not added
+        let pipeline = Pipeline::from_order(&[ProcessorKind::CodeCase], |_| true, None);
+        assert_eq!(pipeline.process("snake case user id").unwrap(), "user_id");
+
+        let pipeline = Pipeline::from_order(&[ProcessorKind::CodeCase], |_| false, None);
+        assert!(pipeline.is_empty());
+    }
+
+    #[test]
+    fn test_overrides_replace_configured_setting() {
+        let mut overrides = ProcessorOverrides::default();
+        overrides.set(ProcessorKind::Grammar, false);
+        assert!(!overrides.enabled(ProcessorKind::Grammar, true));
+        assert!(overrides.enabled(ProcessorKind::Punctuation, true));
+        assert!(!overrides.enabled(ProcessorKind::CodeCase, false));
+    }
+
+    #[test]
+    fn test_blank_input_skips_processors() {
+        struct Failing;
+        impl TextProcessor for Failing {
+            fn process(&self, _text: &str) -> Result<String> {
+                anyhow::bail!("processor invoked")
+            }
+        }
+
+        let mut pipeline = Pipeline::new();
+        pipeline.add_processor(Box::new(Failing));
+        assert_eq!(pipeline.process("").unwrap(), "");
+        assert_eq!(pipeline.process("  \n").unwrap(), "  \n");
+        assert!(pipeline.process("hello").is_err());
+    }
+
+    #[test]
+    fn test_line_breaks_preserved() {
+        let pipeline = Pipeline::from_config(false, true, true, false);
+        assert_eq!(pipeline.process("first point\n\nsecond point").unwrap(), "First point.\n\nSecond point.");
+    }
+
+    #[test]
+    fn test_spoken_punctuation_before_capitalization() {
+        let order = resolve_order(&[]);
+        let pipeline = Pipeline::from_order(
+            &order,
+            |kind| matches!(kind, ProcessorKind::PunctuationCommands | ProcessorKind::Capitalization),
+            None,
+        );
+        assert_eq!(
+            pipeline.process("hello world period new line thanks comma see you").unwrap(),
+            "Hello world.\nThanks, see you"
+        );
+    }
+
+    #[test]
+    fn test_capitalization_only_leaves_punctuation_untouched() {
+        let pipeline = Pipeline::from_config(false, false, true, false);
+        assert_eq!(pipeline.process("hello world. how are you").unwrap(), "Hello world. How are you");
+    }
+
+    #[test]
+    fn test_punctuation_only_leaves_case_untouched() {
+        let pipeline = Pipeline::from_config(false, true, false, false);
+        assert_eq!(pipeline.process("hello world , i think").unwrap(), "hello world, i think.");
+    }
+
+    #[test]
+    fn test_punctuation_then_capitalization() {
+        let pipeline = Pipeline::from_config(false, true, true, false);
+        assert_eq!(pipeline.process("i think so").unwrap(), "I think so.");
+    }
+}
@@ -1,91 +1,81 @@
 use super::TextProcessor;
+use crate::window_detect::AppCategory;
 use anyhow::Result;
 
-/// Simple rule-based punctuation and capitalization processor.
+/// Simple rule-based punctuation insertion processor.
 ///
 /// Applies the following transformations:
-/// - Capitalizes the first word
-/// - Capitalizes the pronoun "I" (including in contractions)
-/// - Capitalizes words following sentence endings (. ? !)
+/// - Removes stray spaces before punctuation ("hello , world" → "hello, world")
+/// - Ends the utterance with a period if it has no terminal punctuation
 ///
-/// This processor is designed to be fast (<5ms) and requires
-/// no external dependencies or model files.
-pub struct PunctuationProcessor;
+/// Letter case is left untouched; see `CapitalizationProcessor`.
+pub struct PunctuationProcessor {
+    context: Option<PunctuationContext>,
+}
+
+/// Hint about where the text is going, used to decide on the terminal period.
+///
+/// Search boxes and command lines don't want "ls -la." or "rust borrow checker.",
+/// so short utterances and some app categories get no period appended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PunctuationContext {
+    /// Category of the target window
+    pub app_category: AppCategory,
+    /// Utterances with fewer words than this get no period (0 = no length limit)
+    pub min_words_for_period: usize,
+}
+
+impl PunctuationContext {
+    /// Whether a terminal period should be appended to `word_count` words
+    fn wants_period(&self, word_count: usize) -> bool {
+        let command_like = matches!(self.app_category, AppCategory::Terminal | AppCategory::Browser);
+        !command_like && word_count >= self.min_words_for_period
+    }
+}
 
 impl PunctuationProcessor {
     pub fn new() -> Self {
-        Self
+        Self { context: None }
+    }
+
+    /// Processor that may skip the terminal period based on `context`
+    pub fn with_context(context: PunctuationContext) -> Self {
+        Self { context: Some(context) }
     }
 }
 
 impl TextProcessor for PunctuationProcessor {
     fn process(&self, text: &str) -> Result<String> {
-        if text.is_empty() {
-            return Ok(String::new());
-        }
-
-        let mut result = String::with_capacity(text.len());
-        let mut capitalize_next = true;
+        let mut result = String::with_capacity(text.len() + 1);
+        let mut word_count = 0;
 
         for word in text.split_whitespace() {
-            let processed = if capitalize_next {
-                capitalize_first(word)
-            } else {
-                capitalize_pronoun_i(word)
-            };
-
-            result.push_str(&processed);
-            result.push(' ');
-
-            // Check if this word ends with a sentence terminator
-            capitalize_next = ends_with_sentence_terminator(&processed);
+            if is_attached_punctuation(word) {
+                result.push_str(word);
+                continue;
+            }
+            if !result.is_empty() {
+                result.push(' ');
+            }
+            result.push_str(word);
+            word_count += 1;
         }
 
-        // Remove trailing space
-        Ok(result.trim_end().to_string())
-    }
-}
+        let wants_period = self.context.map_or(true, |ctx| ctx.wants_period(word_count));
 
-/// Capitalize the first character of a word.
-fn capitalize_first(word: &str) -> String {
-    let mut chars = word.chars();
-    match chars.next() {
-        None => String::new(),
-        Some(first) => {
-            let mut result = String::with_capacity(word.len());
-            result.push(first.to_uppercase().next().unwrap());
-            result.push_str(chars.as_str());
-            result
+        // Only append after a word character so trailing quotes, brackets or
+        // symbols aren't followed by a dangling period
+        if wants_period && result.chars().last().is_some_and(|c| c.is_alphanumeric()) {
+            result.push('.');
         }
-    }
-}
 
-/// Capitalize pronoun "I" if it appears standalone or in contractions.
-///
-/// Examples:
-/// - "i" → "I"
-/// - "i'm" → "I'm"
-/// - "i'll" → "I'll"
-/// - "i've" → "I've"
-fn capitalize_pronoun_i(word: &str) -> String {
-    if word.len() == 1 && word == "i" {
-        return "I".to_string();
+        Ok(result)
     }
-
-    // Handle contractions like "i'm", "i'll", "i've"
-    if word.len() > 1 && word.starts_with('i') && !word.chars().nth(1).unwrap().is_alphanumeric() {
-        let mut result = String::with_capacity(word.len());
-        result.push('I');
-        result.push_str(&word[1..]);
-        return result;
-    }
-
-    word.to_string()
 }
 
-/// Check if a word ends with a sentence terminator.
-fn ends_with_sentence_terminator(word: &str) -> bool {
-    word.ends_with('.') || word.ends_with('?') || word.ends_with('!')
+/// Check if a token is punctuation that attaches to the preceding word.
+fn is_attached_punctuation(word: &str) -> bool {
+    word.chars().all(|c| matches!(c, '.' | ',' | '?' | '!' | ';' | ':'))
 }
 
 #[cfg(test)]
@@ -95,86 +85,59 @@ mod tests {
     #[test]
     fn test_empty_string() {
         let processor = PunctuationProcessor::new();
-        let result = processor.process("").unwrap();
-        assert_eq!(result, "");
-    }
-
-    #[test]
-    fn test_capitalize_first_word() {
-        let processor = PunctuationProcessor::new();
-        let result = processor.process("hello world").unwrap();
-        assert_eq!(result, "Hello world");
-    }
-
-    #[test]
-    fn test_capitalize_pronoun_i() {
-        let processor = PunctuationProcessor::new();
-        let result = processor.process("i think i should go").unwrap();
-        assert_eq!(result, "I think I should go");
+        assert_eq!(processor.process("").unwrap(), "");
     }
 
     #[test]
-    fn test_capitalize_pronoun_i_contractions() {
+    fn test_appends_period() {
         let processor = PunctuationProcessor::new();
-
-        let result = processor.process("i'm happy").unwrap();
-        assert_eq!(result, "I'm happy");
-
-        let result = processor.process("i'll be there").unwrap();
-        assert_eq!(result, "I'll be there");
-
-        let result = processor.process("i've seen it").unwrap();
-        assert_eq!(result, "I've seen it");
+        assert_eq!(processor.process("hello world").unwrap(), "hello world.");
     }
 
     #[test]
-    fn test_capitalize_after_period() {
+    fn test_keeps_existing_terminator() {
         let processor = PunctuationProcessor::new();
-        let result = processor.process("hello world. this is a test").unwrap();
-        assert_eq!(result, "Hello world. This is a test");
+        assert_eq!(processor.process("are you sure?").unwrap(), "are you sure?");
+        assert_eq!(processor.process("wow!").unwrap(), "wow!");
     }
 
     #[test]
-    fn test_capitalize_after_question() {
+    fn test_attaches_detached_punctuation() {
         let processor = PunctuationProcessor::new();
-        let result = processor.process("are you sure? yes i am").unwrap();
-        assert_eq!(result, "Are you sure? Yes I am");
+        assert_eq!(processor.process("wait , what ? ok").unwrap(), "wait, what? ok.");
     }
 
-    #[test]
-    fn test_capitalize_after_exclamation() {
-        let processor = PunctuationProcessor::new();
-        let result = processor.process("wow! that's amazing").unwrap();
-        assert_eq!(result, "Wow! That's amazing");
+    fn short_command_context(app_category: AppCategory) -> PunctuationContext {
+        PunctuationContext { app_category, min_words_for_period: 4 }
     }
 
     #[test]
-    fn test_multiple_sentences() {
-        let processor = PunctuationProcessor::new();
-        let result = processor
-            .process("hello there. how are you? i am fine!")
-            .unwrap();
-        assert_eq!(result, "Hello there. How are you? I am fine!");
+    fn test_short_command_gets_no_period() {
+        let processor = PunctuationProcessor::with_context(short_command_context(AppCategory::General));
+        assert_eq!(processor.process("open settings").unwrap(), "open settings");
     }
 
     #[test]
-    fn test_preserve_existing_punctuation() {
-        let processor = PunctuationProcessor::new();
-        let result = processor.process("hello, world! it's nice.").unwrap();
-        assert_eq!(result, "Hello, world! It's nice.");
+    fn test_long_sentence_keeps_period() {
+        let processor = PunctuationProcessor::with_context(short_command_context(AppCategory::General));
+        assert_eq!(
+            processor.process("please send the report tomorrow").unwrap(),
+            "please send the report tomorrow."
+        );
     }
 
     #[test]
-    fn test_single_word() {
-        let processor = PunctuationProcessor::new();
-        let result = processor.process("hello").unwrap();
-        assert_eq!(result, "Hello");
+    fn test_terminal_gets_no_period() {
+        let processor = PunctuationProcessor::with_context(short_command_context(AppCategory::Terminal));
+        assert_eq!(
+            processor.process("git commit with a long message here").unwrap(),
+            "git commit with a long message here"
+        );
     }
 
     #[test]
-    fn test_word_with_period() {
+    fn test_no_period_after_symbol() {
         let processor = PunctuationProcessor::new();
-        let result = processor.process("hello.").unwrap();
-        assert_eq!(result, "Hello.");
+        assert_eq!(processor.process("call foo()").unwrap(), "call foo()");
     }
 }
@@ -0,0 +1,278 @@
+//! Spoken punctuation and line breaks.
+//!
+//! "hello world period new line thanks" → "hello world.\nthanks". Command
+//! phrases are replaced by their symbol, spaced the way the symbol implies:
+//! closing marks (`. , ? ! : ; ) ]`) attach to the previous word, opening
+//! brackets attach to the next one, and line breaks replace the spaces around
+//! them. Any other symbol is spaced like a word.
+//!
+//! Some command words are also ordinary nouns ("period", "colon", "dash").
+//! Those stay words when the word before them is a determiner or a modifier
+//! they commonly follow ("the period", "menstrual period", "trial period") and
+//! are commands everywhere else. Saying "literal" before a command types the
+//! command words themselves.
+
+use super::TextProcessor;
+use anyhow::Result;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Built-in command phrases and their symbols
+const DEFAULT_COMMANDS: &[(&str, &str)] = &[
+    ("period", "."),
+    ("full stop", "."),
+    ("comma", ","),
+    ("question mark", "?"),
+    ("exclamation mark", "!"),
+    ("exclamation point", "!"),
+    ("colon", ":"),
+    ("semicolon", ";"),
+    ("dash", "-"),
+    ("open paren", "("),
+    ("close paren", ")"),
+    ("open parenthesis", "("),
+    ("close parenthesis", ")"),
+    ("open bracket", "["),
+    ("close bracket", "]"),
+    ("new line", "\n"),
+    ("newline", "\n"),
+    ("new paragraph", "\n\n"),
+];
+
+/// Command words that are also common nouns
+const AMBIGUOUS_WORDS: &[&str] = &["period", "colon", "dash"];
+
+/// Words after which an ambiguous command word is a noun, not a command
+const NOUN_CONTEXT: &[&str] = &[
+    "a", "an", "the", "this", "that", "each", "every", "any", "no", "one", "per", "my", "your", "his",
+    "her", "its", "our", "their", "menstrual", "trial", "grace", "waiting", "notice", "billing",
+    "cooling", "probation", "probationary", "incubation", "time", "rest", "study", "reporting",
+];
+
+/// Word that makes the following command be typed as words
+const LITERAL_WORD: &str = "literal";
+
+/// How a symbol is spaced against its neighbours
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Spacing {
+    /// Attaches to the previous word
+    Closing,
+    /// Attaches to the next word
+    Opening,
+    /// Line break, no spaces around it
+    Break,
+    Word,
+}
+
+impl Spacing {
+    fn of(symbol: &str) -> Self {
+        if symbol.chars().all(|c| c == '\n') {
+            Spacing::Break
+        } else if symbol.chars().all(|c| matches!(c, '.' | ',' | '?' | '!' | ':' | ';' | ')' | ']' | '}')) {
+            Spacing::Closing
+        } else if symbol.chars().all(|c| matches!(c, '(' | '[' | '{')) {
+            Spacing::Opening
+        } else {
+            Spacing::Word
+        }
+    }
+}
+
+/// Command phrases, longest first so "new paragraph" wins over shorter matches
+#[derive(Debug, Clone)]
+pub struct CommandVocabulary {
+    commands: Vec<(Vec<String>, String)>,
+}
+
+impl Default for CommandVocabulary {
+    fn default() -> Self {
+        Self::new(&HashMap::new())
+    }
+}
+
+impl CommandVocabulary {
+    /// Built-in commands plus `extra` (phrase → symbol); an extra phrase
+    /// replaces a built-in one with the same words
+    pub fn new(extra: &HashMap<String, String>) -> Self {
+        let mut by_phrase: HashMap<Vec<String>, String> = DEFAULT_COMMANDS
+            .iter()
+            .map(|&(phrase, symbol)| (phrase_words(phrase), symbol.to_string()))
+            .collect();
+        for (phrase, symbol) in extra {
+            let words = phrase_words(phrase);
+            if !words.is_empty() && !symbol.is_empty() {
+                by_phrase.insert(words, symbol.clone());
+            }
+        }
+
+        let mut commands: Vec<_> = by_phrase.into_iter().collect();
+        commands.sort_by(|a, b| b.0.len().cmp(&a.0.len()).then_with(|| a.0.cmp(&b.0)));
+        Self { commands }
+    }
+
+    /// Match a command at the start of `words`, returning its symbol and the
+    /// number of words it spans
+    fn match_at(&self, words: &[&str]) -> Option<(&str, usize)> {
+        self.commands.iter().find_map(|(phrase, symbol)| {
+            let matches = words.len() >= phrase.len()
+                && words.iter().zip(phrase).all(|(word, expected)| normalize(word) == *expected);
+            matches.then_some((symbol.as_str(), phrase.len()))
+        })
+    }
+}
+
+/// Processor that replaces spoken punctuation commands with symbols.
+pub struct CommandProcessor {
+    vocabulary: Arc<CommandVocabulary>,
+}
+
+impl CommandProcessor {
+    /// Processor with the built-in commands only
+    pub fn new() -> Self {
+        Self::with_vocabulary(Arc::new(CommandVocabulary::default()))
+    }
+
+    pub fn with_vocabulary(vocabulary: Arc<CommandVocabulary>) -> Self {
+        Self { vocabulary }
+    }
+}
+
+impl Default for CommandProcessor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TextProcessor for CommandProcessor {
+    fn process(&self, text: &str) -> Result<String> {
+        let words: Vec<&str> = text.split_whitespace().collect();
+        let mut output = String::new();
+
+        let mut i = 0;
+        while i < words.len() {
+            if normalize(words[i]) == LITERAL_WORD {
+                if let Some((_, len)) = self.vocabulary.match_at(&words[i + 1..]) {
+                    for word in &words[i + 1..i + 1 + len] {
+                        push_word(&mut output, word);
+                    }
+                    i += 1 + len;
+                    continue;
+                }
+            }
+
+            let command = self.vocabulary.match_at(&words[i..]).filter(|&(_, len)| {
+                let is_noun = len == 1
+                    && AMBIGUOUS_WORDS.contains(&normalize(words[i]).as_str())
+                    && i > 0
+                    && NOUN_CONTEXT.contains(&normalize(words[i - 1]).as_str());
+                !is_noun
+            });
+            let Some((symbol, len)) = command else {
+                push_word(&mut output, words[i]);
+                i += 1;
+                continue;
+            };
+
+            match Spacing::of(symbol) {
+                Spacing::Closing => {
+                    // The spoken mark replaces punctuation the engine guessed
+                    let kept = if symbol.starts_with(['.', ',', '?', '!', ':', ';']) {
+                        output.trim_end().trim_end_matches(['.', ',', '?', '!', ':', ';']).len()
+                    } else {
+                        output.trim_end().len()
+                    };
+                    output.truncate(kept);
+                    output.push_str(symbol);
+                }
+                Spacing::Break => {
+                    let kept = output.trim_end_matches(' ').len();
+                    output.truncate(kept);
+                    output.push_str(symbol);
+                }
+                Spacing::Opening | Spacing::Word => push_word(&mut output, symbol),
+            }
+            i += len;
+        }
+
+        Ok(output.trim_end_matches(' ').to_string())
+    }
+}
+
+/// Append a word, with no space at the start of a line or after an opening bracket
+fn push_word(output: &mut String, word: &str) {
+    if !output.is_empty() && !output.ends_with([' ', '\n', '(', '[', '{']) {
+        output.push(' ');
+    }
+    output.push_str(word);
+}
+
+fn phrase_words(phrase: &str) -> Vec<String> {
+    phrase.split_whitespace().map(normalize).filter(|w| !w.is_empty()).collect()
+}
+
+/// Lowercase and strip surrounding punctuation for command matching
+fn normalize(word: &str) -> String {
+    word.trim_matches(|c: char| !c.is_alphanumeric()).to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn process(text: &str) -> String {
+        CommandProcessor::new().process(text).unwrap()
+    }
+
+    #[test]
+    fn test_period_and_new_line() {
+        assert_eq!(process("hello world period new line thanks"), "hello world.\nthanks");
+    }
+
+    #[test]
+    fn test_engine_punctuation_replaced() {
+        assert_eq!(process("Hello world, period. New line, thanks."), "Hello world.\nthanks.");
+        assert_eq!(process("really question mark"), "really?");
+    }
+
+    #[test]
+    fn test_parentheses() {
+        assert_eq!(
+            process("call me open paren maybe close paren tomorrow"),
+            "call me (maybe) tomorrow"
+        );
+        assert_eq!(process("see open parenthesis below close parenthesis"), "see (below)");
+    }
+
+    #[test]
+    fn test_new_paragraph() {
+        assert_eq!(process("first part new paragraph second part"), "first part\n\nsecond part");
+    }
+
+    #[test]
+    fn test_period_as_noun() {
+        // Heuristic: after a determiner or a modifier it commonly follows,
+        // "period" is the word
+        assert_eq!(process("her menstrual period started"), "her menstrual period started");
+        assert_eq!(process("the trial period ended period"), "the trial period ended.");
+        assert_eq!(process("wait for the period"), "wait for the period");
+        // "literal" forces the word anywhere
+        assert_eq!(process("type literal period here"), "type period here");
+    }
+
+    #[test]
+    fn test_custom_commands() {
+        let extra = HashMap::from([
+            ("arrow".to_string(), "->".to_string()),
+            ("Dash".to_string(), "—".to_string()),
+        ]);
+        let processor = CommandProcessor::with_vocabulary(Arc::new(CommandVocabulary::new(&extra)));
+        assert_eq!(processor.process("a arrow b").unwrap(), "a -> b");
+        assert_eq!(processor.process("wait dash what").unwrap(), "wait — what");
+    }
+
+    #[test]
+    fn test_no_commands_unchanged() {
+        assert_eq!(process("plain dictated sentence."), "plain dictated sentence.");
+        assert_eq!(process(""), "");
+    }
+}
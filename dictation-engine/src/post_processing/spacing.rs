@@ -0,0 +1,75 @@
+//! Whitespace handling for injected text.
+//!
+//! Applied right before typing so repeated dictations into the same field
+//! neither double up nor glue words together.
+
+/// Leading/trailing whitespace options for typed output
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SpacingOptions {
+    /// Strip leading/trailing whitespace from the transcription
+    pub trim: bool,
+    /// Add a single space before the text (appending to existing content)
+    pub prepend_space: bool,
+    /// Add a single space after the text (ready for the next dictation)
+    pub append_space: bool,
+}
+
+impl Default for SpacingOptions {
+    fn default() -> Self {
+        Self {
+            trim: true,
+            prepend_space: false,
+            append_space: false,
+        }
+    }
+}
+
+impl SpacingOptions {
+    /// Apply the spacing options. Text that is empty after trimming stays empty
+    /// so a lone space is never typed.
+    pub fn apply(&self, text: &str) -> String {
+        let body = if self.trim { text.trim() } else { text };
+        if body.trim().is_empty() {
+            return body.to_string();
+        }
+
+        let mut out = String::with_capacity(body.len() + 2);
+        if self.prepend_space && !body.starts_with(char::is_whitespace) {
+            out.push(' ');
+        }
+        out.push_str(body);
+        if self.append_space && !body.ends_with(char::is_whitespace) {
+            out.push(' ');
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_trims() {
+        assert_eq!(SpacingOptions::default().apply("  hello world "), "hello world");
+    }
+
+    #[test]
+    fn test_prepend_and_append() {
+        let opts = SpacingOptions { trim: true, prepend_space: true, append_space: true };
+        assert_eq!(opts.apply(" hello "), " hello ");
+    }
+
+    #[test]
+    fn test_no_trim_keeps_whitespace_without_doubling() {
+        let opts = SpacingOptions { trim: false, prepend_space: true, append_space: false };
+        assert_eq!(opts.apply(" hello"), " hello");
+        assert_eq!(opts.apply("hello "), " hello ");
+    }
+
+    #[test]
+    fn test_empty_stays_empty() {
+        let opts = SpacingOptions { trim: true, prepend_space: true, append_space: true };
+        assert_eq!(opts.apply("   "), "");
+    }
+}
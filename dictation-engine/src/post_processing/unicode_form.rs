@@ -0,0 +1,64 @@
+//! Unicode normalization of the final text.
+//!
+//! Engines may emit accented characters decomposed (e + combining acute),
+//! which some apps render or search differently from the composed form.
+//! Applied as the last step before injection.
+
+use unicode_normalization::UnicodeNormalization;
+
+/// Normalization form for typed output
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum UnicodeForm {
+    /// Composed, what most apps expect
+    #[default]
+    Nfc,
+    /// Decomposed
+    Nfd,
+    /// Leave the text as transcribed
+    None,
+}
+
+impl UnicodeForm {
+    /// Parse from config string
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "nfc" => Some(UnicodeForm::Nfc),
+            "nfd" => Some(UnicodeForm::Nfd),
+            "none" | "off" => Some(UnicodeForm::None),
+            _ => None,
+        }
+    }
+
+    pub fn apply(self, text: &str) -> String {
+        match self {
+            UnicodeForm::Nfc => text.nfc().collect(),
+            UnicodeForm::Nfd => text.nfd().collect(),
+            UnicodeForm::None => text.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!(UnicodeForm::from_str("NFC"), Some(UnicodeForm::Nfc));
+        assert_eq!(UnicodeForm::from_str(" nfd "), Some(UnicodeForm::Nfd));
+        assert_eq!(UnicodeForm::from_str("none"), Some(UnicodeForm::None));
+        assert_eq!(UnicodeForm::from_str("nfkc"), None);
+    }
+
+    #[test]
+    fn test_nfc_composes_decomposed_input() {
+        let decomposed = "cafe\u{301} nin\u{303}o";
+        assert_eq!(UnicodeForm::Nfc.apply(decomposed), "caf\u{e9} ni\u{f1}o");
+    }
+
+    #[test]
+    fn test_nfd_and_none() {
+        assert_eq!(UnicodeForm::Nfd.apply("caf\u{e9}"), "cafe\u{301}");
+        assert_eq!(UnicodeForm::None.apply("cafe\u{301}"), "cafe\u{301}");
+    }
+}
@@ -0,0 +1,133 @@
+//! Sample rate conversion between the capture device and the engine.
+//!
+//! Parakeet only accepts 16kHz audio, but capture can run at any configured rate
+//! (8kHz telephony headsets, 48kHz pro interfaces). Linear interpolation is
+//! plenty for speech recognition and keeps the audio path allocation-light.
+
+/// Streaming linear-interpolation resampler for 16-bit mono PCM.
+///
+/// Keeps the fractional read position and last input sample between calls so
+/// chunk boundaries don't introduce clicks or drift.
+pub struct StreamResampler {
+    from_rate: u32,
+    to_rate: u32,
+    /// Input samples advanced per output sample
+    step: f64,
+    /// Read position relative to the start of the next chunk (may be negative,
+    /// pointing into `last_sample`)
+    position: f64,
+    last_sample: Option<i16>,
+}
+
+impl StreamResampler {
+    pub fn new(from_rate: u32, to_rate: u32) -> Self {
+        Self {
+            from_rate,
+            to_rate,
+            step: from_rate as f64 / to_rate as f64,
+            position: 0.0,
+            last_sample: None,
+        }
+    }
+
+    /// True when input and output rates match (samples pass through unchanged)
+    pub fn is_passthrough(&self) -> bool {
+        self.from_rate == self.to_rate
+    }
+
+    /// Resample one chunk of input, returning the output produced so far.
+    pub fn process(&mut self, input: &[i16]) -> Vec<i16> {
+        if self.is_passthrough() || input.is_empty() {
+            return input.to_vec();
+        }
+
+        let sample_at = |index: isize, last: Option<i16>| -> f64 {
+            if index < 0 {
+                last.unwrap_or(input[0]) as f64
+            } else {
+                input[index as usize] as f64
+            }
+        };
+
+        let mut output = Vec::with_capacity((input.len() as f64 / self.step).ceil() as usize + 1);
+
+        // Interpolate between floor(position) and floor(position) + 1, both of which
+        // must be available (index -1 is the previous chunk's last sample)
+        while self.position + 1.0 < input.len() as f64 {
+            let base = self.position.floor();
+            let frac = self.position - base;
+            let a = sample_at(base as isize, self.last_sample);
+            let b = sample_at(base as isize + 1, self.last_sample);
+            output.push((a + (b - a) * frac).round() as i16);
+            self.position += self.step;
+        }
+
+        // Rebase position onto the next chunk
+        self.position -= input.len() as f64;
+        self.last_sample = input.last().copied();
+
+        output
+    }
+}
+
+/// Resample a complete buffer from `from_rate` to `to_rate`.
+pub fn resample(samples: &[i16], from_rate: u32, to_rate: u32) -> Vec<i16> {
+    StreamResampler::new(from_rate, to_rate).process(samples)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sine(freq: f32, rate: u32, seconds: f32) -> Vec<i16> {
+        let n = (rate as f32 * seconds) as usize;
+        (0..n)
+            .map(|i| ((2.0 * std::f32::consts::PI * freq * i as f32 / rate as f32).sin() * 10000.0) as i16)
+            .collect()
+    }
+
+    fn zero_crossings(samples: &[i16]) -> usize {
+        samples.windows(2).filter(|w| (w[0] < 0) != (w[1] < 0)).count()
+    }
+
+    #[test]
+    fn test_passthrough() {
+        let input = sine(440.0, 16000, 0.1);
+        assert_eq!(resample(&input, 16000, 16000), input);
+    }
+
+    #[test]
+    fn test_8khz_to_16khz_doubles_length_and_keeps_pitch() {
+        let input = sine(300.0, 8000, 1.0);
+        let output = resample(&input, 8000, 16000);
+
+        assert!((output.len() as i64 - 16000).abs() <= 2, "got {} samples", output.len());
+        // Same tone over the same duration → same number of zero crossings
+        let diff = zero_crossings(&input) as i64 - zero_crossings(&output) as i64;
+        assert!(diff.abs() <= 2, "crossings differ by {}", diff);
+    }
+
+    #[test]
+    fn test_48khz_to_16khz_thirds_length() {
+        let input = sine(300.0, 48000, 1.0);
+        let output = resample(&input, 48000, 16000);
+        assert!((output.len() as i64 - 16000).abs() <= 2, "got {} samples", output.len());
+    }
+
+    #[test]
+    fn test_streaming_matches_one_shot() {
+        let input = sine(300.0, 8000, 0.5);
+        let expected = resample(&input, 8000, 16000);
+
+        let mut resampler = StreamResampler::new(8000, 16000);
+        let mut streamed = Vec::new();
+        for chunk in input.chunks(97) {
+            streamed.extend(resampler.process(chunk));
+        }
+
+        assert_eq!(streamed.len(), expected.len());
+        for (a, b) in streamed.iter().zip(expected.iter()) {
+            assert!((*a as i32 - *b as i32).abs() <= 1);
+        }
+    }
+}
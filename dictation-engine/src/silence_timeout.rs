@@ -0,0 +1,141 @@
+//! Hands-free confirm after the speaker stops talking.
+//!
+//! The audio task records when it last heard speech; the Recording state
+//! confirms the session once that is older than `silence_timeout_ms`. The
+//! timer only starts with the first speech, so the quiet start of a session
+//! never confirms it.
+//!
+//! Speech is either a chunk louder than the speech threshold or, with the
+//! `vad` detector, the VAD's speaking state. Loudness alone counts a steady
+//! fan or hum as speech, which keeps the timer from ever running out.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+/// What counts as speech for the timer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SpeechDetector {
+    /// Chunk RMS above the speech threshold (vad_energy_threshold_db)
+    #[default]
+    Energy,
+    /// The VAD reports speech
+    Vad,
+}
+
+impl SpeechDetector {
+    /// Parse from config string
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "energy" | "rms" => Some(SpeechDetector::Energy),
+            "vad" => Some(SpeechDetector::Vad),
+            _ => None,
+        }
+    }
+}
+
+/// Shared speech timestamp and timeout, cloned into the audio task
+#[derive(Debug, Clone)]
+pub struct SilenceTimeout {
+    timeout_ms: u64,
+    /// Epoch ms of the last loud chunk (0 = no speech yet this session)
+    last_speech_ms: Arc<AtomicU64>,
+}
+
+impl SilenceTimeout {
+    /// `timeout_ms` of 0 disables auto-confirm
+    pub fn new(timeout_ms: u64) -> Self {
+        Self { timeout_ms, last_speech_ms: Arc::new(AtomicU64::new(0)) }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.timeout_ms > 0
+    }
+
+    /// Forget the previous session's speech
+    pub fn arm(&self) {
+        self.last_speech_ms.store(0, Ordering::Relaxed);
+    }
+
+    /// Record a chunk of audio at `now_ms`; chunks with RMS (0.0-1.0) above
+    /// `threshold` count as speech and restart the timer
+    pub fn note_audio(&self, samples: &[f32], threshold: f32, now_ms: u64) {
+        if samples.is_empty() {
+            return;
+        }
+        let rms = (samples.iter().map(|&s| s * s).sum::<f32>() / samples.len() as f32).sqrt();
+        if rms > threshold {
+            self.last_speech_ms.store(now_ms, Ordering::Relaxed);
+        }
+    }
+
+    /// Record the VAD's decision for a chunk at `now_ms`
+    pub fn note_vad(&self, speaking: bool, now_ms: u64) {
+        if speaking {
+            self.last_speech_ms.store(now_ms, Ordering::Relaxed);
+        }
+    }
+
+    /// Whether speech was heard and has been followed by `timeout_ms` of silence
+    pub fn expired(&self, now_ms: u64) -> bool {
+        let last = self.last_speech_ms.load(Ordering::Relaxed);
+        self.is_enabled() && last != 0 && now_ms.saturating_sub(last) >= self.timeout_ms
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LOUD: [f32; 4] = [0.5, -0.5, 0.5, -0.5];
+    const QUIET: [f32; 4] = [0.001, -0.001, 0.001, -0.001];
+
+    #[test]
+    fn test_never_fires_before_speech() {
+        let timeout = SilenceTimeout::new(1000);
+        timeout.note_audio(&QUIET, 0.01, 1_000);
+        assert!(!timeout.expired(60_000));
+    }
+
+    #[test]
+    fn test_fires_after_silence_and_resets_on_speech() {
+        let timeout = SilenceTimeout::new(1000);
+        timeout.note_audio(&LOUD, 0.01, 10_000);
+        timeout.note_audio(&QUIET, 0.01, 10_500);
+        assert!(!timeout.expired(10_900));
+        assert!(timeout.expired(11_000));
+
+        // Speaking again restarts the timer
+        timeout.note_audio(&LOUD, 0.01, 10_950);
+        assert!(!timeout.expired(11_500));
+
+        timeout.arm();
+        assert!(!timeout.expired(60_000));
+    }
+
+    #[test]
+    fn test_vad_ignores_loudness() {
+        let timeout = SilenceTimeout::new(1000);
+        timeout.note_vad(true, 10_000);
+        // Steady noise the VAD rejects doesn't hold the timer open
+        timeout.note_vad(false, 10_500);
+        timeout.note_vad(false, 10_900);
+        assert!(timeout.expired(11_000));
+
+        timeout.note_vad(true, 11_200);
+        assert!(!timeout.expired(12_000));
+    }
+
+    #[test]
+    fn test_detector_from_str() {
+        assert_eq!(SpeechDetector::from_str("Energy"), Some(SpeechDetector::Energy));
+        assert_eq!(SpeechDetector::from_str(" vad "), Some(SpeechDetector::Vad));
+        assert_eq!(SpeechDetector::from_str("silero"), None);
+    }
+
+    #[test]
+    fn test_disabled() {
+        let timeout = SilenceTimeout::new(0);
+        timeout.note_audio(&LOUD, 0.01, 1_000);
+        assert!(!timeout.expired(60_000));
+    }
+}
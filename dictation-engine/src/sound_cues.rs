@@ -0,0 +1,94 @@
+//! Optional audio cues for recording start and confirmation.
+//!
+//! Cues are played by an external PipeWire/PulseAudio player on the output
+//! device, so they never touch the capture stream (including the exclusive
+//! ALSA path of the cpal backend). A missing file or player is logged and the
+//! cue skipped.
+
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::time::Duration;
+use tracing::{debug, warn};
+
+/// Players tried in order
+const PLAYERS: &[&str] = &["pw-play", "paplay"];
+
+/// Longest the start cue may delay capture; longer sounds keep playing while recording
+pub const START_CUE_MAX_WAIT: Duration = Duration::from_millis(500);
+
+/// Play `path` and wait until it finishes or `max_wait` passes.
+///
+/// Used before capture starts so the cue isn't recorded.
+pub async fn play_and_wait(path: &str, max_wait: Duration) {
+    let Some(mut child) = spawn_player(path) else {
+        return;
+    };
+    if tokio::time::timeout(max_wait, child.wait()).await.is_err() {
+        debug!("Sound cue '{}' still playing after {:?}, continuing", path, max_wait);
+    }
+}
+
+/// Play `path` without waiting for it.
+pub fn play(path: &str) {
+    if let Some(mut child) = spawn_player(path) {
+        // Reap the player in the background
+        tokio::spawn(async move {
+            let _ = child.wait().await;
+        });
+    }
+}
+
+fn spawn_player(path: &str) -> Option<tokio::process::Child> {
+    let file = resolve(path)?;
+    for player in PLAYERS {
+        match tokio::process::Command::new(player)
+            .arg(&file)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+        {
+            Ok(child) => return Some(child),
+            Err(e) => debug!("Sound player {} unavailable: {}", player, e),
+        }
+    }
+    warn!("No sound player found (install pipewire-utils or pulseaudio-utils), skipping cue");
+    None
+}
+
+/// Expand `~` in a configured cue path, or None if it's unset or missing
+fn resolve(path: &str) -> Option<PathBuf> {
+    if path.trim().is_empty() {
+        return None;
+    }
+    let file = PathBuf::from(shellexpand::tilde(path.trim()).as_ref());
+    if !file.is_file() {
+        warn!("Sound cue file not found: {}", file.display());
+        return None;
+    }
+    Some(file)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unset_path_is_skipped() {
+        assert_eq!(resolve(""), None);
+        assert_eq!(resolve("   "), None);
+    }
+
+    #[test]
+    fn test_missing_file_is_skipped() {
+        assert_eq!(resolve("/nonexistent/voice-dictation/start.ogg"), None);
+    }
+
+    #[test]
+    fn test_existing_file_resolves() {
+        let file = std::env::temp_dir().join("voice-dictation-cue-test.wav");
+        std::fs::write(&file, b"RIFF").unwrap();
+        assert_eq!(resolve(file.to_str().unwrap()), Some(file.clone()));
+        let _ = std::fs::remove_file(file);
+    }
+}
@@ -157,8 +157,8 @@ impl Tray for DictationTray {
                 label: "Cancel".into(),
                 enabled: is_recording,
                 activate: Box::new(|tray: &mut Self| {
-                    if let Err(e) = tray.command_tx.try_send(DaemonCommand::StopRecording) {
-                        warn!("Tray: failed to send StopRecording: {e}");
+                    if let Err(e) = tray.command_tx.try_send(DaemonCommand::Cancel) {
+                        warn!("Tray: failed to send Cancel: {e}");
                     }
                 }),
                 ..Default::default()
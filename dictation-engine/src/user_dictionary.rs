@@ -3,20 +3,29 @@ use std::collections::HashSet;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, RwLock};
+use tracing::warn;
+
+/// Bundled default word list, loaded before any configured dictionary files
+const DEFAULT_WORDS: &str = include_str!("../data/default_words.txt");
 
 /// Manages user-defined words for spell checking.
 ///
-/// Combines application-specific words with system Hunspell personal dictionary.
+/// Combines application-specific words with system Hunspell personal dictionary,
+/// plus a layered set built from the bundled defaults and configured dictionary files.
 /// Supports hot-reload via file watching.
 pub struct UserDictionary {
     /// Application-specific words (read-write)
     app_words: Arc<RwLock<HashSet<String>>>,
     /// System Hunspell personal dictionary words (read-only, but reloadable)
     system_words: Arc<RwLock<HashSet<String>>>,
+    /// Bundled defaults merged with `extra_paths` in order (read-only, but reloadable)
+    layered_words: Arc<RwLock<HashSet<String>>>,
     /// Path to application word list
     app_words_path: PathBuf,
     /// Path to system Hunspell dictionary (if available)
     system_dict_path: Option<PathBuf>,
+    /// Additional dictionary files, later files override earlier ones
+    extra_paths: Vec<PathBuf>,
 }
 
 impl UserDictionary {
@@ -27,8 +36,10 @@ impl UserDictionary {
         Self {
             app_words: Arc::new(RwLock::new(HashSet::new())),
             system_words: Arc::new(RwLock::new(HashSet::new())),
+            layered_words: Arc::new(RwLock::new(Self::load_layered_words(&[]))),
             app_words_path: PathBuf::new(),
             system_dict_path: None,
+            extra_paths: Vec::new(),
         }
     }
 
@@ -51,11 +62,25 @@ impl UserDictionary {
         Ok(Self {
             app_words: Arc::new(RwLock::new(app_words)),
             system_words: Arc::new(RwLock::new(system_words)),
+            layered_words: Arc::new(RwLock::new(Self::load_layered_words(&[]))),
             app_words_path,
             system_dict_path,
+            extra_paths: Vec::new(),
         })
     }
 
+    /// Add dictionary files layered on top of the bundled defaults.
+    ///
+    /// Files are applied in order: each line adds a word, a line starting with
+    /// `!` removes a word added by the defaults or an earlier file. Missing
+    /// files are logged and skipped.
+    pub fn with_extra_files(mut self, paths: Vec<PathBuf>) -> Self {
+        let words = Self::load_layered_words(&paths);
+        self.layered_words = Arc::new(RwLock::new(words));
+        self.extra_paths = paths;
+        self
+    }
+
     /// Get paths to watch for changes.
     ///
     /// Returns vector of paths that should be monitored for dictionary updates.
@@ -64,6 +89,7 @@ impl UserDictionary {
         if let Some(ref system_path) = self.system_dict_path {
             paths.push(system_path.clone());
         }
+        paths.extend(self.extra_paths.iter().cloned());
         paths
     }
 
@@ -85,6 +111,13 @@ impl UserDictionary {
             }
         }
 
+        // Check bundled defaults + configured dictionary files
+        if let Ok(layered_words) = self.layered_words.read() {
+            if layered_words.contains(&word_lower) {
+                return true;
+            }
+        }
+
         false
     }
 
@@ -160,10 +193,22 @@ impl UserDictionary {
         Ok(())
     }
 
-    /// Reload both dictionaries from disk.
+    /// Reload bundled defaults and configured dictionary files from disk.
+    pub fn reload_layered_words(&self) -> Result<()> {
+        let words = Self::load_layered_words(&self.extra_paths);
+        let mut layered_words = self
+            .layered_words
+            .write()
+            .map_err(|e| anyhow::anyhow!("Lock poisoned: {}", e))?;
+        *layered_words = words;
+        Ok(())
+    }
+
+    /// Reload all dictionaries from disk.
     pub fn reload_all(&self) -> Result<()> {
         self.reload_app_words()?;
         self.reload_system_words()?;
+        self.reload_layered_words()?;
         Ok(())
     }
 
@@ -198,6 +243,20 @@ impl UserDictionary {
         Ok(words)
     }
 
+    fn load_layered_words(paths: &[PathBuf]) -> HashSet<String> {
+        let mut words = HashSet::new();
+        apply_word_list(&mut words, DEFAULT_WORDS);
+
+        for path in paths {
+            match fs::read_to_string(path) {
+                Ok(content) => apply_word_list(&mut words, &content),
+                Err(e) => warn!("Skipping dictionary file {:?}: {}", path, e),
+            }
+        }
+
+        words
+    }
+
     fn load_system_words_from_path(path: &Path) -> Result<HashSet<String>> {
         let content = fs::read_to_string(path)?;
         let words = content
@@ -258,6 +317,22 @@ impl UserDictionary {
     }
 }
 
+/// Apply a word list on top of `words`.
+///
+/// Blank lines and `#` comments are ignored; `!word` removes a word.
+fn apply_word_list(words: &mut HashSet<String>, content: &str) {
+    for line in content.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if let Some(removed) = line.strip_prefix('!') {
+            words.remove(&removed.trim().to_lowercase());
+        } else {
+            words.insert(line.to_lowercase());
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -301,6 +376,54 @@ mod tests {
         assert_eq!(words, vec!["apple", "monkey", "zebra"]);
     }
 
+    #[test]
+    fn test_bundled_defaults_loaded() {
+        let dict = UserDictionary::empty();
+        assert!(dict.contains("wayland"));
+        assert!(dict.contains("Tokio"));
+    }
+
+    #[test]
+    fn test_extra_files_later_overrides_earlier() {
+        let dir = TempDir::new().unwrap();
+        let personal = dir.path().join("personal.txt");
+        let work = dir.path().join("work.txt");
+        fs::write(&personal, "# personal\nkubernetes\nfoobar\n").unwrap();
+        fs::write(&work, "!foobar\n!wayland\nacmecorp\n").unwrap();
+
+        let dict = UserDictionary::empty().with_extra_files(vec![personal, work]);
+        assert!(dict.contains("kubernetes"));
+        assert!(dict.contains("acmecorp"));
+        assert!(!dict.contains("foobar"));
+        assert!(!dict.contains("wayland"));
+    }
+
+    #[test]
+    fn test_missing_extra_file_is_skipped() {
+        let dir = TempDir::new().unwrap();
+        let present = dir.path().join("present.txt");
+        fs::write(&present, "projectword\n").unwrap();
+
+        let dict = UserDictionary::empty()
+            .with_extra_files(vec![dir.path().join("missing.txt"), present]);
+        assert!(dict.contains("projectword"));
+    }
+
+    #[test]
+    fn test_reload_picks_up_changes() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("words.txt");
+        fs::write(&path, "firstword\n").unwrap();
+
+        let dict = UserDictionary::empty().with_extra_files(vec![path.clone()]);
+        assert!(dict.contains("firstword"));
+
+        fs::write(&path, "secondword\n").unwrap();
+        dict.reload_all().unwrap();
+        assert!(!dict.contains("firstword"));
+        assert!(dict.contains("secondword"));
+    }
+
     #[test]
     fn test_empty_word_ignored() {
         let dict = UserDictionary::new().unwrap();
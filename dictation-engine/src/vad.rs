@@ -1,11 +1,76 @@
 //! Voice Activity Detection module
 //!
 //! Provides VAD trait and implementations for detecting speech in audio.
-//! Includes both simple dB-threshold and Silero neural network detection.
+//! Includes both simple dB-threshold and Silero neural network detection
+//! (the latter behind the `silero-vad` cargo feature).
 
 use anyhow::Result;
 use tracing::debug;
 
+/// VAD backend selection (config `vad_backend`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum VadBackend {
+    /// dB-threshold energy detection (always available)
+    #[default]
+    Energy,
+    /// Silero neural VAD via ONNX Runtime (requires `silero-vad` feature)
+    Silero,
+}
+
+impl VadBackend {
+    /// Parse from config string
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "energy" | "db" | "threshold" => Some(VadBackend::Energy),
+            "silero" => Some(VadBackend::Silero),
+            _ => None,
+        }
+    }
+}
+
+/// Speech boundary events derived from per-chunk VAD decisions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VadEvent {
+    SpeechStart,
+    SpeechEnd,
+}
+
+/// Wraps any detector and turns its per-chunk decisions into `VadEvent`s,
+/// so downstream logic is independent of the backend.
+pub struct VadStream {
+    detector: Box<dyn VoiceActivityDetector>,
+    speaking: bool,
+}
+
+impl VadStream {
+    pub fn new(detector: Box<dyn VoiceActivityDetector>) -> Self {
+        Self { detector, speaking: false }
+    }
+
+    /// Feed samples; returns an event when the speaking state changes
+    pub fn push(&mut self, samples: &[i16]) -> Result<Option<VadEvent>> {
+        let speech = self.detector.process(samples)?;
+        let event = match (self.speaking, speech) {
+            (false, true) => Some(VadEvent::SpeechStart),
+            (true, false) => Some(VadEvent::SpeechEnd),
+            _ => None,
+        };
+        self.speaking = speech;
+        Ok(event)
+    }
+
+    /// Whether the last processed chunk contained speech
+    pub fn is_speaking(&self) -> bool {
+        self.speaking
+    }
+
+    /// Reset detector and speaking state (call between recordings)
+    pub fn reset(&mut self) {
+        self.detector.reset();
+        self.speaking = false;
+    }
+}
+
 /// Trait for voice activity detection implementations
 pub trait VoiceActivityDetector: Send + Sync {
     /// Process audio samples and return true if speech is detected
@@ -54,6 +119,7 @@ impl VoiceActivityDetector for DbThresholdVad {
 }
 
 /// Silero VAD implementation using ONNX model
+#[cfg(feature = "silero-vad")]
 pub mod silero {
     use super::*;
     use ort::session::{Session, builder::GraphOptimizationLevel};
@@ -254,6 +320,39 @@ pub mod silero {
     }
 }
 
+/// Trimming of leading/trailing silence from the final-pass audio
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SilenceTrim {
+    /// Frames at or below this RMS level (dB) count as silence
+    pub threshold_db: f32,
+    /// Audio kept on each side of the detected speech (ms)
+    pub margin_ms: u64,
+}
+
+impl SilenceTrim {
+    /// Frame length the buffer is scanned in (ms)
+    const FRAME_MS: u64 = 20;
+
+    /// Range of `samples` from the first to the last speech frame, widened by the
+    /// margin. The whole buffer is kept when no frame is above the threshold.
+    pub fn speech_range(&self, samples: &[i16], sample_rate: u32) -> std::ops::Range<usize> {
+        let frame_len = ((Self::FRAME_MS * sample_rate as u64 / 1000) as usize).max(1);
+        let margin = (self.margin_ms * sample_rate as u64 / 1000) as usize;
+        let mut vad = DbThresholdVad::new(self.threshold_db);
+        let mut is_speech = |frame: &[i16]| vad.process(frame).unwrap_or(true);
+
+        let frames: Vec<&[i16]> = samples.chunks(frame_len).collect();
+        let Some(first) = frames.iter().position(|frame| is_speech(frame)) else {
+            return 0..samples.len();
+        };
+        let last = frames.iter().rposition(|frame| is_speech(frame)).unwrap_or(first);
+
+        let start = (first * frame_len).saturating_sub(margin);
+        let end = ((last + 1) * frame_len + margin).min(samples.len());
+        start..end
+    }
+}
+
 /// Create the appropriate VAD based on config
 pub fn create_vad(
     vad_enabled: bool,
@@ -261,28 +360,49 @@ pub fn create_vad(
     silence_threshold_db: f32,
     sample_rate: u32,
 ) -> Box<dyn VoiceActivityDetector> {
-    if vad_enabled {
-        // Try to load Silero VAD
-        let model_dir = dirs::data_dir()
-            .unwrap_or_else(|| std::path::PathBuf::from("."))
-            .join("voice-dictation")
-            .join("models");
-
-        match silero::SileroVadDetector::ensure_model(&model_dir) {
-            Ok(model_path) => {
-                match silero::SileroVadDetector::new(&model_path, vad_threshold, sample_rate) {
-                    Ok(detector) => {
-                        debug!("Using Silero VAD with threshold {}", vad_threshold);
-                        return Box::new(detector);
-                    }
-                    Err(e) => {
-                        tracing::warn!("Failed to create Silero VAD: {}, falling back to dB threshold", e);
+    let backend = if vad_enabled { VadBackend::Silero } else { VadBackend::Energy };
+    create_vad_backend(backend, vad_threshold, silence_threshold_db, sample_rate)
+}
+
+/// Create a VAD for the selected backend, falling back to dB threshold if the
+/// ML backend is unavailable (feature disabled, download or load failure).
+pub fn create_vad_backend(
+    backend: VadBackend,
+    vad_threshold: f32,
+    silence_threshold_db: f32,
+    sample_rate: u32,
+) -> Box<dyn VoiceActivityDetector> {
+    if backend == VadBackend::Silero {
+        #[cfg(feature = "silero-vad")]
+        {
+            // Try to load Silero VAD
+            let model_dir = dirs::data_dir()
+                .unwrap_or_else(|| std::path::PathBuf::from("."))
+                .join("voice-dictation")
+                .join("models");
+
+            match silero::SileroVadDetector::ensure_model(&model_dir) {
+                Ok(model_path) => {
+                    match silero::SileroVadDetector::new(&model_path, vad_threshold, sample_rate) {
+                        Ok(detector) => {
+                            debug!("Using Silero VAD with threshold {}", vad_threshold);
+                            return Box::new(detector);
+                        }
+                        Err(e) => {
+                            tracing::warn!("Failed to create Silero VAD: {}, falling back to dB threshold", e);
+                        }
                     }
                 }
+                Err(e) => {
+                    tracing::warn!("Failed to download Silero model: {}, falling back to dB threshold", e);
+                }
             }
-            Err(e) => {
-                tracing::warn!("Failed to download Silero model: {}, falling back to dB threshold", e);
-            }
+        }
+
+        #[cfg(not(feature = "silero-vad"))]
+        {
+            let _ = (vad_threshold, sample_rate);
+            tracing::warn!("Silero VAD requested but built without the silero-vad feature, using dB threshold");
         }
     }
 
@@ -308,6 +428,30 @@ mod tests {
         assert!(vad.process(&loud).unwrap());
     }
 
+    #[test]
+    fn test_silence_trim_keeps_speech_and_margin() {
+        // 1s silence, 0.5s tone, 1s silence at 16kHz
+        let tone: Vec<i16> = (0..8000).map(|i| ((i % 100) * 300) as i16).collect();
+        let samples: Vec<i16> = [vec![0i16; 16000], tone, vec![0i16; 16000]].concat();
+        let trim = SilenceTrim { threshold_db: -45.0, margin_ms: 100 };
+
+        // Speech spans frames 50..75 (20ms frames), plus 1600 samples of margin each side
+        assert_eq!(trim.speech_range(&samples, 16000), 14400..25600);
+    }
+
+    #[test]
+    fn test_silence_trim_margin_clamped_to_buffer() {
+        let loud: Vec<i16> = (0..1000).map(|i| ((i % 100) * 300) as i16).collect();
+        let trim = SilenceTrim { threshold_db: -45.0, margin_ms: 500 };
+        assert_eq!(trim.speech_range(&loud, 16000), 0..1000);
+    }
+
+    #[test]
+    fn test_silence_trim_all_silent_keeps_buffer() {
+        let trim = SilenceTrim { threshold_db: -45.0, margin_ms: 100 };
+        assert_eq!(trim.speech_range(&[0i16; 4000], 16000), 0..4000);
+    }
+
     #[test]
     fn test_db_threshold_vad_reset() {
         let mut vad = DbThresholdVad::new(-40.0);
@@ -333,6 +477,31 @@ mod tests {
         assert!(!vad.process(&silence).unwrap());
     }
 
+    #[test]
+    fn test_vad_backend_from_str() {
+        assert_eq!(VadBackend::from_str("energy"), Some(VadBackend::Energy));
+        assert_eq!(VadBackend::from_str("DB"), Some(VadBackend::Energy));
+        assert_eq!(VadBackend::from_str("silero"), Some(VadBackend::Silero));
+        assert_eq!(VadBackend::from_str("webrtc"), None);
+    }
+
+    #[test]
+    fn test_vad_stream_events() {
+        let mut stream = VadStream::new(Box::new(DbThresholdVad::new(-40.0)));
+        let silence = vec![0i16; 512];
+        let loud = vec![10000i16; 512];
+
+        assert_eq!(stream.push(&silence).unwrap(), None);
+        assert_eq!(stream.push(&loud).unwrap(), Some(VadEvent::SpeechStart));
+        assert!(stream.is_speaking());
+        assert_eq!(stream.push(&loud).unwrap(), None);
+        assert_eq!(stream.push(&silence).unwrap(), Some(VadEvent::SpeechEnd));
+
+        stream.push(&loud).unwrap();
+        stream.reset();
+        assert!(!stream.is_speaking());
+    }
+
     #[test]
     fn test_rms_calculation() {
         // Test with known values
@@ -0,0 +1,268 @@
+//! Starting dictation by voice.
+//!
+//! With `wake_word` set, the mic stays open while the daemon is idle. The VAD
+//! cuts the audio into utterances; short ones are transcribed and compared to
+//! the wake phrase, and a match sends `StartRecording` like the start command.
+//!
+//! Only an utterance that is the phrase and nothing else counts, so saying it
+//! in the middle of a sentence doesn't start a recording. Utterances longer
+//! than the phrase could plausibly take are dropped without transcribing them,
+//! which keeps idle CPU use to the occasional short check.
+
+use crate::dbus_control::DaemonCommand;
+use crate::engine::TranscriptionEngine;
+use crate::resample::StreamResampler;
+use crate::vad::{VadEvent, VadStream};
+use std::sync::Arc;
+use tokio::sync::{mpsc, watch, Mutex};
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+
+/// Longest utterance checked for the phrase
+const MAX_UTTERANCE_SECS: f32 = 3.0;
+
+/// Audio kept from before the VAD fires so the first syllable isn't clipped
+const PRE_ROLL_SECS: f32 = 0.3;
+
+/// Silence that ends an utterance (a shorter gap between words continues it)
+const END_SILENCE_SECS: f32 = 0.4;
+
+/// Normalized wake phrase
+#[derive(Debug, Clone, PartialEq)]
+pub struct WakeWord {
+    words: Vec<String>,
+}
+
+impl WakeWord {
+    /// None when `phrase` is blank (feature disabled)
+    pub fn new(phrase: &str) -> Option<Self> {
+        let words = normalize(phrase);
+        (!words.is_empty()).then_some(Self { words })
+    }
+
+    /// Whether `transcript` is the wake phrase and nothing else, ignoring case
+    /// and punctuation
+    pub fn matches(&self, transcript: &str) -> bool {
+        normalize(transcript) == self.words
+    }
+}
+
+fn normalize(text: &str) -> Vec<String> {
+    text.split_whitespace()
+        .map(|word| word.trim_matches(|c: char| !c.is_alphanumeric()).to_lowercase())
+        .filter(|word| !word.is_empty())
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Phase {
+    Quiet,
+    Speaking,
+    /// Speech ended this many samples ago
+    Trailing(usize),
+}
+
+/// Cuts the audio stream into VAD-bounded utterances
+struct Utterances {
+    samples: Vec<i16>,
+    phase: Phase,
+    /// Current utterance grew past `max_samples` and will be dropped
+    too_long: bool,
+    pre_roll: usize,
+    max_samples: usize,
+    end_silence: usize,
+}
+
+impl Utterances {
+    fn new(sample_rate: u32) -> Self {
+        let samples = |secs: f32| (secs * sample_rate as f32) as usize;
+        Self::with_limits(samples(PRE_ROLL_SECS), samples(MAX_UTTERANCE_SECS), samples(END_SILENCE_SECS))
+    }
+
+    fn with_limits(pre_roll: usize, max_samples: usize, end_silence: usize) -> Self {
+        Self { samples: Vec::new(), phase: Phase::Quiet, too_long: false, pre_roll, max_samples, end_silence }
+    }
+
+    /// Feed a chunk and the VAD event it produced; returns a finished utterance
+    /// that was short enough to be the phrase
+    fn push(&mut self, chunk: &[i16], event: Option<VadEvent>) -> Option<Vec<i16>> {
+        match (self.phase, event) {
+            (_, Some(VadEvent::SpeechStart)) => self.phase = Phase::Speaking,
+            (Phase::Speaking, Some(VadEvent::SpeechEnd)) => self.phase = Phase::Trailing(0),
+            _ => {}
+        }
+
+        if !self.too_long {
+            self.samples.extend_from_slice(chunk);
+        }
+
+        match self.phase {
+            Phase::Quiet => {
+                let excess = self.samples.len().saturating_sub(self.pre_roll);
+                self.samples.drain(..excess);
+                None
+            }
+            Phase::Speaking | Phase::Trailing(_) => {
+                if self.samples.len() > self.pre_roll + self.max_samples {
+                    self.too_long = true;
+                    self.samples.clear();
+                }
+                let Phase::Trailing(silent) = self.phase else {
+                    return None;
+                };
+                let silent = silent + chunk.len();
+                if silent < self.end_silence {
+                    self.phase = Phase::Trailing(silent);
+                    return None;
+                }
+                self.phase = Phase::Quiet;
+                let utterance = std::mem::take(&mut self.samples);
+                (!std::mem::take(&mut self.too_long)).then_some(utterance)
+            }
+        }
+    }
+}
+
+/// Background task listening for the wake phrase while the daemon is idle
+pub struct WakeListener {
+    stop_tx: watch::Sender<bool>,
+    handle: JoinHandle<()>,
+}
+
+impl WakeListener {
+    /// Start listening on `audio_rx` (capture-rate audio, resampled to the
+    /// engine's `sample_rate`). Sends `StartRecording` and exits on a match.
+    pub fn spawn(
+        wake_word: WakeWord,
+        audio_rx: Arc<Mutex<mpsc::UnboundedReceiver<Vec<i16>>>>,
+        vad: VadStream,
+        resampler: StreamResampler,
+        engine: Arc<dyn TranscriptionEngine>,
+        sample_rate: u32,
+        command_tx: mpsc::Sender<DaemonCommand>,
+    ) -> Self {
+        let (stop_tx, stop_rx) = watch::channel(false);
+        let listener = Listener { wake_word, audio_rx, vad, resampler, engine, sample_rate, command_tx };
+        let handle = tokio::spawn(listener.run(stop_rx));
+        Self { stop_tx, handle }
+    }
+
+    /// True once the phrase was heard (or the audio channel closed)
+    pub fn is_finished(&self) -> bool {
+        self.handle.is_finished()
+    }
+
+    /// Stop listening. Waits for an in-flight check so the engine and audio
+    /// channel are free for the recording session.
+    pub async fn stop(self) {
+        let _ = self.stop_tx.send(true);
+        let _ = self.handle.await;
+    }
+}
+
+/// State owned by the listening task
+struct Listener {
+    wake_word: WakeWord,
+    audio_rx: Arc<Mutex<mpsc::UnboundedReceiver<Vec<i16>>>>,
+    vad: VadStream,
+    resampler: StreamResampler,
+    engine: Arc<dyn TranscriptionEngine>,
+    sample_rate: u32,
+    command_tx: mpsc::Sender<DaemonCommand>,
+}
+
+impl Listener {
+    async fn run(mut self, mut stop_rx: watch::Receiver<bool>) {
+        debug!("Listening for the wake word");
+        let mut utterances = Utterances::new(self.sample_rate);
+        let audio_rx = Arc::clone(&self.audio_rx);
+        let mut rx = audio_rx.lock().await;
+
+        loop {
+            let samples = tokio::select! {
+                _ = stop_rx.changed() => return,
+                samples = rx.recv() => match samples {
+                    Some(samples) => self.resampler.process(&samples),
+                    None => return,
+                },
+            };
+
+            let event = self.vad.push(&samples).unwrap_or_else(|e| {
+                debug!("Wake word VAD error: {}", e);
+                None
+            });
+            let Some(utterance) = utterances.push(&samples, event) else {
+                continue;
+            };
+
+            let engine = Arc::clone(&self.engine);
+            let transcript = tokio::task::spawn_blocking(move || {
+                engine.reset();
+                engine.process_audio(&utterance)?;
+                engine.get_final_result()
+            })
+            .await;
+
+            match transcript {
+                Ok(Ok(text)) if self.wake_word.matches(&text) => {
+                    info!("Wake word heard, starting dictation");
+                    self.engine.reset();
+                    if self.command_tx.send(DaemonCommand::StartRecording).await.is_err() {
+                        warn!("Command channel closed, can't start dictation from the wake word");
+                    }
+                    return;
+                }
+                Ok(Ok(text)) => debug!("Not the wake word: {:?}", text),
+                Ok(Err(e)) => warn!("Wake word check failed: {}", e),
+                Err(e) => warn!("Wake word check panicked: {}", e),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_phrase_must_be_whole_utterance() {
+        let wake = WakeWord::new("Hey Computer").unwrap();
+        assert!(wake.matches("hey computer"));
+        assert!(wake.matches("Hey, computer."));
+        assert!(!wake.matches("hey"));
+        assert!(!wake.matches("I said hey computer yesterday"));
+        assert!(!wake.matches("hey computer open the file"));
+    }
+
+    #[test]
+    fn test_blank_phrase_disables() {
+        assert!(WakeWord::new("").is_none());
+        assert!(WakeWord::new("  ,. ").is_none());
+    }
+
+    #[test]
+    fn test_short_utterance_with_pre_roll() {
+        let mut utterances = Utterances::with_limits(2, 10, 4);
+        assert_eq!(utterances.push(&[1, 2, 3], None), None);
+        assert_eq!(utterances.push(&[4, 5], Some(VadEvent::SpeechStart)), None);
+        // A short gap between words continues the utterance
+        assert_eq!(utterances.push(&[0, 0], Some(VadEvent::SpeechEnd)), None);
+        assert_eq!(utterances.push(&[6, 7], Some(VadEvent::SpeechStart)), None);
+        assert_eq!(utterances.push(&[0, 0], Some(VadEvent::SpeechEnd)), None);
+        assert_eq!(utterances.push(&[0, 0], None), Some(vec![2, 3, 4, 5, 0, 0, 6, 7, 0, 0, 0, 0]));
+    }
+
+    #[test]
+    fn test_long_utterance_dropped() {
+        let mut utterances = Utterances::with_limits(0, 4, 2);
+        utterances.push(&[1, 1, 1], Some(VadEvent::SpeechStart));
+        utterances.push(&[1, 1, 1], None);
+        utterances.push(&[1, 1, 1], None);
+        assert_eq!(utterances.push(&[0, 0], Some(VadEvent::SpeechEnd)), None);
+
+        // The next short utterance still gets through
+        utterances.push(&[2, 2], Some(VadEvent::SpeechStart));
+        utterances.push(&[0], Some(VadEvent::SpeechEnd));
+        assert_eq!(utterances.push(&[0], None), Some(vec![2, 2, 0, 0]));
+    }
+}
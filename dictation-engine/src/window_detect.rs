@@ -41,6 +41,75 @@ pub async fn get_focused_app_category() -> AppCategory {
     AppCategory::General
 }
 
+/// Whether keyboard focus is currently on the dictation overlay itself.
+///
+/// Synthetic keystrokes sent now would land in our own surface instead of the
+/// target app. Returns false when the focused window can't be determined.
+pub async fn focused_window_is_overlay() -> bool {
+    crate::window_target::WindowTarget::capture()
+        .await
+        .map(|wt| wt.is_own_window())
+        .unwrap_or(false)
+}
+
+/// Whether the focused window is an X11 client running under XWayland.
+///
+/// Wayland virtual-keyboard events may not reach these windows. Returns false
+/// when the focused window can't be determined.
+pub async fn focused_window_is_xwayland() -> bool {
+    crate::window_target::WindowTarget::capture()
+        .await
+        .map(|wt| wt.is_xwayland())
+        .unwrap_or(false)
+}
+
+/// Per-app enable/disable for starting dictation, by window class.
+///
+/// Classes are matched case-insensitively. A non-empty allowlist restricts
+/// dictation to the listed apps; the blocklist then still applies on top.
+#[derive(Debug, Clone, Default)]
+pub struct AppFilter {
+    blocklist: Vec<String>,
+    allowlist: Vec<String>,
+}
+
+impl AppFilter {
+    pub fn new(blocklist: &[String], allowlist: &[String]) -> Self {
+        let normalize = |list: &[String]| -> Vec<String> {
+            list.iter()
+                .map(|c| c.trim().to_lowercase())
+                .filter(|c| !c.is_empty())
+                .collect()
+        };
+        Self {
+            blocklist: normalize(blocklist),
+            allowlist: normalize(allowlist),
+        }
+    }
+
+    /// Whether any list is configured
+    pub fn is_active(&self) -> bool {
+        !self.blocklist.is_empty() || !self.allowlist.is_empty()
+    }
+
+    /// Whether dictation may start with `class` focused.
+    ///
+    /// An unknown class (no window detection) passes the blocklist but not an allowlist.
+    pub fn allows(&self, class: Option<&str>) -> bool {
+        let class = class.map(|c| c.trim().to_lowercase());
+        if !self.allowlist.is_empty() {
+            match &class {
+                Some(c) if self.allowlist.contains(c) => {}
+                _ => return false,
+            }
+        }
+        match &class {
+            Some(c) => !self.blocklist.contains(c),
+            None => true,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -55,4 +124,47 @@ mod tests {
         assert_eq!(AppCategory::from_str("chat"), AppCategory::Chat);
         assert_eq!(AppCategory::from_str("anything"), AppCategory::General);
     }
+
+    fn list(classes: &[&str]) -> Vec<String> {
+        classes.iter().map(|c| c.to_string()).collect()
+    }
+
+    #[test]
+    fn test_empty_filter_allows_everything() {
+        let filter = AppFilter::new(&[], &[]);
+        assert!(!filter.is_active());
+        assert!(filter.allows(Some("steam_app_1234")));
+        assert!(filter.allows(None));
+    }
+
+    #[test]
+    fn test_blocklist() {
+        let filter = AppFilter::new(&list(&["steam_app_1234", " MPV "]), &[]);
+        assert!(filter.is_active());
+        assert!(!filter.allows(Some("steam_app_1234")));
+        assert!(!filter.allows(Some("mpv")));
+        assert!(filter.allows(Some("kitty")));
+        assert!(filter.allows(None));
+    }
+
+    #[test]
+    fn test_allowlist() {
+        let filter = AppFilter::new(&[], &list(&["kitty", "firefox"]));
+        assert!(filter.allows(Some("Firefox")));
+        assert!(!filter.allows(Some("mpv")));
+        assert!(!filter.allows(None));
+    }
+
+    #[test]
+    fn test_blocklist_applies_within_allowlist() {
+        let filter = AppFilter::new(&list(&["firefox"]), &list(&["kitty", "firefox"]));
+        assert!(filter.allows(Some("kitty")));
+        assert!(!filter.allows(Some("firefox")));
+    }
+
+    #[test]
+    fn test_blank_entries_ignored() {
+        let filter = AppFilter::new(&list(&["", "  "]), &list(&[""]));
+        assert!(!filter.is_active());
+    }
 }
@@ -8,6 +8,8 @@ use tracing::debug;
 pub struct WindowTarget {
     address: String,
     class: String,
+    pid: Option<u32>,
+    xwayland: bool,
 }
 
 impl WindowTarget {
@@ -28,9 +30,11 @@ impl WindowTarget {
 
         let address = value["address"].as_str()?.to_string();
         let class = value["class"].as_str()?.to_string();
+        let pid = value["pid"].as_u64().map(|p| p as u32);
+        let xwayland = value["xwayland"].as_bool().unwrap_or(false);
 
-        debug!("Captured window: class={}, address={}", class, address);
-        Some(Self { address, class })
+        debug!("Captured window: class={}, address={}, xwayland={}", class, address, xwayland);
+        Some(Self { address, class, pid, xwayland })
     }
 
     /// Refocus the captured window before typing.
@@ -53,4 +57,14 @@ impl WindowTarget {
     pub fn class(&self) -> &str {
         &self.class
     }
+
+    /// Whether this is an X11 client running under XWayland
+    pub fn is_xwayland(&self) -> bool {
+        self.xwayland
+    }
+
+    /// Whether this window belongs to the daemon process (i.e. the overlay itself)
+    pub fn is_own_window(&self) -> bool {
+        self.pid == Some(std::process::id())
+    }
 }
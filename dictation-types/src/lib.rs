@@ -34,6 +34,15 @@ pub enum GuiControl {
     /// Transition to processing state (spinner animation)
     SetProcessing,
 
+    /// Show the final text in the listening layout (no live spectrum) before it's typed
+    ShowResult(String),
+
+    /// Transition to typing state while the result is injected
+    SetTyping,
+
+    /// Fraction of the result typed so far (0.0-1.0)
+    UpdateTypingProgress(f32),
+
     /// Transition to closing state and begin shutdown animation
     SetClosing,
 
@@ -42,6 +51,11 @@ pub enum GuiControl {
 }
 
 /// Status messages sent from GUI to daemon
+///
+/// `Ready` and `Error` are required: the daemon waits on them at startup and
+/// uses them for health reporting, so the GUI sends them with a blocking send.
+/// `TransitionComplete` and `ShuttingDown` are informational and may be dropped
+/// (with a log) if the channel is full.
 #[derive(Debug, Clone)]
 pub enum GuiStatus {
     /// GUI has initialized and is ready
@@ -60,6 +74,13 @@ pub enum GuiStatus {
     ShuttingDown,
 }
 
+impl GuiStatus {
+    /// Whether the daemon depends on receiving this status (must never be dropped)
+    pub fn is_required(&self) -> bool {
+        matches!(self, GuiStatus::Ready | GuiStatus::Error(_))
+    }
+}
+
 /// GUI state (shared type for status messages)
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum GuiState {
@@ -67,5 +88,7 @@ pub enum GuiState {
     PreListening,
     Listening,
     Processing,
+    Result,
+    Typing,
     Closing,
 }
@@ -0,0 +1,42 @@
+//! Color parsing for overlay appearance settings
+
+use slint::Color;
+
+/// Parse a `#rrggbb` or `#rrggbbaa` hex color (leading `#` optional)
+pub fn parse_hex_color(s: &str) -> Option<Color> {
+    let hex = s.trim().trim_start_matches('#');
+    if !hex.is_ascii() {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+
+    match hex.len() {
+        6 => Some(Color::from_rgb_u8(channel(0)?, channel(2)?, channel(4)?)),
+        8 => Some(Color::from_argb_u8(channel(6)?, channel(0)?, channel(2)?, channel(4)?)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_rgb() {
+        let color = parse_hex_color("#1e1e2e").unwrap();
+        assert_eq!((color.red(), color.green(), color.blue(), color.alpha()), (0x1e, 0x1e, 0x2e, 255));
+    }
+
+    #[test]
+    fn test_parse_rgba_without_hash() {
+        let color = parse_hex_color("ffffff80").unwrap();
+        assert_eq!(color.alpha(), 0x80);
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        assert!(parse_hex_color("white").is_none());
+        assert!(parse_hex_color("#12345").is_none());
+        assert!(parse_hex_color("#gggggg").is_none());
+    }
+}
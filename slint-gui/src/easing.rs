@@ -0,0 +1,159 @@
+//! Easing curves for overlay animations driven from the Rust side
+
+/// Easing curve applied to a normalized animation progress value (0.0-1.0)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Easing {
+    Linear,
+    EaseIn,
+    #[default]
+    EaseOut,
+    EaseInOut,
+    EaseInQuad,
+    EaseOutQuad,
+    EaseInOutQuad,
+}
+
+impl Easing {
+    /// Parse an easing name from config. Accepts "linear", "ease-in", "ease-out",
+    /// "ease-in-out" (cubic, also "ease-in-cubic" etc.) and the "-quad" variants
+    /// (underscores are treated as dashes).
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().replace('_', "-").as_str() {
+            "linear" => Some(Easing::Linear),
+            "ease-in" | "ease-in-cubic" => Some(Easing::EaseIn),
+            "ease-out" | "ease-out-cubic" => Some(Easing::EaseOut),
+            "ease-in-out" | "ease-in-out-cubic" => Some(Easing::EaseInOut),
+            "ease-in-quad" => Some(Easing::EaseInQuad),
+            "ease-out-quad" => Some(Easing::EaseOutQuad),
+            "ease-in-out-quad" => Some(Easing::EaseInOutQuad),
+            _ => None,
+        }
+    }
+
+    /// The curve function for this easing
+    pub fn curve(self) -> fn(f32) -> f32 {
+        match self {
+            Easing::Linear => linear,
+            Easing::EaseIn => ease_in_cubic,
+            Easing::EaseOut => ease_out_cubic,
+            Easing::EaseInOut => ease_in_out_cubic,
+            Easing::EaseInQuad => ease_in_quad,
+            Easing::EaseOutQuad => ease_out_quad,
+            Easing::EaseInOutQuad => ease_in_out_quad,
+        }
+    }
+
+    /// Map linear progress `t` to eased progress. Input is clamped to 0.0-1.0.
+    pub fn apply(self, t: f32) -> f32 {
+        (self.curve())(t.clamp(0.0, 1.0))
+    }
+}
+
+/// Look up an easing curve by config name, falling back to ease-out for unknown names
+pub fn easing(name: &str) -> fn(f32) -> f32 {
+    Easing::from_str(name).unwrap_or_default().curve()
+}
+
+fn linear(t: f32) -> f32 {
+    t
+}
+
+fn ease_in_quad(t: f32) -> f32 {
+    t * t
+}
+
+fn ease_out_quad(t: f32) -> f32 {
+    1.0 - (1.0 - t) * (1.0 - t)
+}
+
+fn ease_in_out_quad(t: f32) -> f32 {
+    if t < 0.5 {
+        2.0 * t * t
+    } else {
+        1.0 - (-2.0 * t + 2.0).powi(2) / 2.0
+    }
+}
+
+fn ease_in_cubic(t: f32) -> f32 {
+    t * t * t
+}
+
+fn ease_out_cubic(t: f32) -> f32 {
+    1.0 - (1.0 - t).powi(3)
+}
+
+fn ease_in_out_cubic(t: f32) -> f32 {
+    if t < 0.5 {
+        4.0 * t * t * t
+    } else {
+        1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL: [Easing; 7] = [
+        Easing::Linear,
+        Easing::EaseIn,
+        Easing::EaseOut,
+        Easing::EaseInOut,
+        Easing::EaseInQuad,
+        Easing::EaseOutQuad,
+        Easing::EaseInOutQuad,
+    ];
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!(Easing::from_str("linear"), Some(Easing::Linear));
+        assert_eq!(Easing::from_str("ease_in_out"), Some(Easing::EaseInOut));
+        assert_eq!(Easing::from_str("Ease-Out"), Some(Easing::EaseOut));
+        assert_eq!(Easing::from_str("ease_out_cubic"), Some(Easing::EaseOut));
+        assert_eq!(Easing::from_str("ease-in-quad"), Some(Easing::EaseInQuad));
+        assert_eq!(Easing::from_str("bounce"), None);
+    }
+
+    #[test]
+    fn test_endpoints() {
+        for easing in ALL {
+            assert_eq!(easing.apply(0.0), 0.0);
+            assert!((easing.apply(1.0) - 1.0).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_clamps_input() {
+        assert_eq!(Easing::Linear.apply(-1.0), 0.0);
+        assert_eq!(Easing::Linear.apply(2.0), 1.0);
+    }
+
+    #[test]
+    fn test_ease_out_leads_linear() {
+        assert!(Easing::EaseOut.apply(0.3) > Easing::Linear.apply(0.3));
+        assert!(Easing::EaseIn.apply(0.3) < Easing::Linear.apply(0.3));
+        assert!(Easing::EaseOutQuad.apply(0.3) > Easing::Linear.apply(0.3));
+        assert!(Easing::EaseInQuad.apply(0.3) < Easing::Linear.apply(0.3));
+    }
+
+    #[test]
+    fn test_quad_is_gentler_than_cubic() {
+        assert!(Easing::EaseInQuad.apply(0.3) > Easing::EaseIn.apply(0.3));
+        assert!(Easing::EaseOutQuad.apply(0.3) < Easing::EaseOut.apply(0.3));
+    }
+
+    #[test]
+    fn test_in_out_midpoint() {
+        for easing in [Easing::EaseInOut, Easing::EaseInOutQuad] {
+            assert!((easing.apply(0.5) - 0.5).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_lookup_by_name() {
+        assert_eq!(easing("ease-in-quad")(0.5), 0.25);
+        assert_eq!(easing("linear")(0.4), 0.4);
+        // Unknown names fall back to ease-out
+        assert_eq!(easing("bounce")(0.5), ease_out_cubic(0.5));
+    }
+}
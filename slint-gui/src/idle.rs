@@ -0,0 +1,84 @@
+//! "Listening but quiet" spectrum animation.
+//!
+//! When every band stays below the idle threshold for a moment, the bars blend
+//! from the (near-zero) live values into a gentle travelling pulse so the
+//! overlay doesn't look frozen during pauses.
+
+use std::time::Duration;
+
+/// How long the input must stay quiet before the idle visual starts
+pub const IDLE_DELAY: Duration = Duration::from_millis(800);
+
+/// Cross-fade time from live bars to the idle pulse
+const IDLE_FADE: Duration = Duration::from_millis(400);
+
+/// Seconds per pulse cycle
+const PULSE_PERIOD_SECS: f32 = 1.6;
+
+/// Bar height range of the pulse (normalized band values)
+const PULSE_BASE: f32 = 0.05;
+const PULSE_AMPLITUDE: f32 = 0.12;
+
+/// Phase offset between neighbouring bars, so the pulse travels across them
+const PULSE_BAR_PHASE: f32 = 0.6;
+
+/// Whether every band is below `threshold`
+pub fn is_quiet(bands: &[f32], threshold: f32) -> bool {
+    bands.iter().all(|&v| v < threshold)
+}
+
+/// Idle visual weight (0.0 = live bars, 1.0 = full pulse) after `quiet_for` of silence
+pub fn idle_blend(quiet_for: Duration) -> f32 {
+    let Some(past_delay) = quiet_for.checked_sub(IDLE_DELAY) else {
+        return 0.0;
+    };
+    (past_delay.as_secs_f32() / IDLE_FADE.as_secs_f32()).min(1.0)
+}
+
+/// Blend live band values toward the idle pulse at animation time `t` (seconds)
+pub fn apply_idle(bands: &[f32], blend: f32, t: f32) -> Vec<f32> {
+    if blend <= 0.0 {
+        return bands.to_vec();
+    }
+
+    let phase = t / PULSE_PERIOD_SECS * std::f32::consts::TAU;
+    bands
+        .iter()
+        .enumerate()
+        .map(|(i, &live)| {
+            let pulse = PULSE_BASE + PULSE_AMPLITUDE * (0.5 + 0.5 * (phase - i as f32 * PULSE_BAR_PHASE).sin());
+            live + (pulse - live) * blend
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_quiet() {
+        assert!(is_quiet(&[0.01, 0.02, 0.0], 0.05));
+        assert!(!is_quiet(&[0.01, 0.2, 0.0], 0.05));
+    }
+
+    #[test]
+    fn test_blend_waits_for_delay_then_fades_in() {
+        assert_eq!(idle_blend(Duration::from_millis(100)), 0.0);
+        assert_eq!(idle_blend(IDLE_DELAY), 0.0);
+        let halfway = idle_blend(IDLE_DELAY + IDLE_FADE / 2);
+        assert!((halfway - 0.5).abs() < 0.01);
+        assert_eq!(idle_blend(Duration::from_secs(10)), 1.0);
+    }
+
+    #[test]
+    fn test_apply_idle_stays_within_pulse_range() {
+        let bands = vec![0.0; 8];
+        assert_eq!(apply_idle(&bands, 0.0, 1.0), bands);
+
+        for step in 0..20 {
+            let values = apply_idle(&bands, 1.0, step as f32 * 0.1);
+            assert!(values.iter().all(|&v| (PULSE_BASE..=PULSE_BASE + PULSE_AMPLITUDE + 1e-6).contains(&v)));
+        }
+    }
+}
@@ -7,7 +7,9 @@ use dictation_types::{GuiControl, GuiState, GuiStatus};
 use layer_shika::calloop::TimeoutAction;
 use layer_shika::prelude::*;
 use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use slint::{ModelRc, VecModel};
 use slint_interpreter::Value;
+use std::collections::HashMap;
 use std::env;
 use std::path::PathBuf;
 use std::sync::atomic::{AtomicBool, Ordering};
@@ -16,9 +18,107 @@ use std::time::{Duration, Instant};
 use tokio::sync::{broadcast, mpsc};
 use tracing::{debug, error, info, warn};
 
+mod color;
+mod easing;
+mod idle;
 mod monitor;
+mod position;
+mod preview;
+mod props;
+mod renderer;
+mod spectrum;
+mod text_fit;
+
+pub use color::parse_hex_color;
+pub use easing::{easing, Easing};
+pub use monitor::{get_active_monitor_sync, MonitorBackend};
+pub use position::OverlayPosition;
+pub use preview::run_preview;
+pub use renderer::OverlayRenderer;
+pub use spectrum::{
+    SpectrumCurve, DEFAULT_BAND_COUNT, DEFAULT_PEAK_DECAY, DEFAULT_SPECTRUM_GAIN, MAX_BAND_COUNT, MAX_SPECTRUM_SMOOTHING,
+};
+use spectrum::{PeakHold, SpectrumAnalyzer, SPECTRUM_SAMPLE_RATE};
+
+/// Overlay appearance settings supplied by the daemon config, already range-checked
+/// by its validation
+#[derive(Debug, Clone)]
+pub struct GuiConfig {
+    /// Duration of the fade-in when the overlay appears (ms). 0 = appear instantly.
+    pub startup_fade_duration_ms: u64,
+    /// Easing curve for the fade-in
+    pub startup_fade_easing: Easing,
+    /// Easing curve for the collapse animation after confirm/cancel
+    pub closing_easing: Easing,
+    /// Transcription text color
+    pub text_color: slint::Color,
+    /// Transcription text opacity (0.0-1.0), multiplied with the fade
+    pub text_opacity: f32,
+    /// How to find the focused monitor the overlay should follow
+    pub monitor_backend: MonitorBackend,
+    /// Output name to use when the focused monitor can't be detected (empty = all monitors)
+    pub fallback_monitor: String,
+    /// Where on the focused monitor the overlay appears
+    pub position: OverlayPosition,
+    /// Replace the bars with a gentle pulse while the input stays quiet
+    pub spectrum_idle_animation: bool,
+    /// Band energy (0.0-1.0) below which the input counts as quiet
+    pub spectrum_idle_threshold: f32,
+    /// Gain and gamma mapping band energy to bar height
+    pub spectrum_curve: SpectrumCurve,
+    /// Mark each band's recent peak above its bar
+    pub spectrum_peak_hold: bool,
+    /// How fast peak markers fall (full bar heights per second)
+    pub spectrum_peak_decay: f32,
+    /// Number of spectrum bars (1 to `MAX_BAND_COUNT`)
+    pub spectrum_band_count: usize,
+    /// Weight of the previous frame when blending spectrum values (0 = off, below 1)
+    pub spectrum_smoothing: f32,
+    /// Width the transcription text wraps/fits to (logical px), at most `MAX_TEXT_WIDTH`
+    pub text_max_width: f32,
+    /// GPU or software rasterization of the overlay
+    pub renderer: OverlayRenderer,
+    /// Text shown while listening until the first word is recognized (empty = none)
+    pub listening_placeholder: String,
+}
+
+impl Default for GuiConfig {
+    fn default() -> Self {
+        Self {
+            startup_fade_duration_ms: 150,
+            startup_fade_easing: Easing::EaseOut,
+            closing_easing: Easing::EaseIn,
+            text_color: slint::Color::from_rgb_u8(255, 255, 255),
+            text_opacity: 1.0,
+            monitor_backend: MonitorBackend::Auto,
+            fallback_monitor: String::new(),
+            position: OverlayPosition::Screen,
+            spectrum_idle_animation: false,
+            spectrum_idle_threshold: 0.05,
+            spectrum_curve: SpectrumCurve::default(),
+            spectrum_peak_hold: false,
+            spectrum_peak_decay: DEFAULT_PEAK_DECAY,
+            spectrum_band_count: DEFAULT_BAND_COUNT,
+            spectrum_smoothing: 0.0,
+            text_max_width: MAX_TEXT_WIDTH,
+            renderer: OverlayRenderer::Gpu,
+            listening_placeholder: "Listening...".to_string(),
+        }
+    }
+}
+
+/// Length of the collapse animation; the daemon hides the overlay shortly after
+pub const CLOSING_DURATION: Duration = Duration::from_millis(300);
+
+/// How long without a spectrum frame before the bars start falling to zero
+/// (frames normally arrive every 32ms)
+const SPECTRUM_STALE_AFTER: Duration = Duration::from_millis(100);
 
-pub use monitor::get_active_monitor_sync;
+/// Horizontal padding inside the listening overlay (each side)
+const OVERLAY_TEXT_PADDING: u32 = 16;
+
+/// Widest the transcription text can be: the overlay minus its padding
+pub const MAX_TEXT_WIDTH: f32 = (OVERLAY_WIDTH - 2 * OVERLAY_TEXT_PADDING) as f32;
 
 /// Shared state between channel listener and GUI
 pub struct SharedState {
@@ -26,19 +126,144 @@ pub struct SharedState {
     pub transcription: String,
     pub spectrum_values: Vec<f32>,
     pub closing_progress: f32,
+    /// Fraction of the result typed so far (Typing state)
+    pub typing_progress: f32,
     pub fade: f32,
     pub pre_listening: bool,
+    /// The daemon's VAD hears speech; the bars are dimmed while it doesn't
+    pub vad_active: bool,
+    /// When the current fade-in started (None = no fade in progress)
+    pub fade_started_at: Option<Instant>,
+    /// When the collapse animation started (None = not closing)
+    pub closing_started_at: Option<Instant>,
+    /// When every spectrum band last dropped below the idle threshold (None = not quiet)
+    pub quiet_since: Option<Instant>,
+    /// Held per-band peaks for the peak markers
+    pub peak_hold: PeakHold,
+    /// When the peaks were last advanced (None = not tracking)
+    pub peaks_updated_at: Option<Instant>,
+    /// When the last spectrum frame arrived (None = none this session)
+    pub spectrum_updated_at: Option<Instant>,
 }
 
-impl Default for SharedState {
-    fn default() -> Self {
+impl SharedState {
+    fn new(band_count: usize) -> Self {
         Self {
             gui_state: GuiState::Hidden,
             transcription: String::new(),
-            spectrum_values: vec![0.0; 8],
+            spectrum_values: vec![0.0; band_count],
             closing_progress: 0.0,
+            typing_progress: 0.0,
             fade: 1.0,
             pre_listening: false,
+            vad_active: false,
+            fade_started_at: None,
+            closing_started_at: None,
+            quiet_since: None,
+            peak_hold: PeakHold::new(band_count),
+            peaks_updated_at: None,
+            spectrum_updated_at: None,
+        }
+    }
+
+    /// Drop the session's text and spectrum so they can't reappear later
+    fn clear_session_display(&mut self) {
+        self.transcription.clear();
+        self.spectrum_values.fill(0.0);
+        self.vad_active = false;
+        self.quiet_since = None;
+        self.peak_hold.reset();
+        self.peaks_updated_at = None;
+        self.spectrum_updated_at = None;
+    }
+
+    /// Blend a new spectrum frame into the displayed values
+    fn receive_spectrum(&mut self, bands: &[f32], smoothing: f32) {
+        spectrum::smooth(&mut self.spectrum_values, bands, smoothing);
+        self.spectrum_updated_at = Some(Instant::now());
+    }
+
+    /// Let the bars fall back to zero once audio stops arriving instead of
+    /// freezing at the last frame
+    fn decay_stale_spectrum(&mut self, config: &GuiConfig) {
+        let stale = self.spectrum_updated_at.is_some_and(|at| at.elapsed() >= SPECTRUM_STALE_AFTER);
+        if stale {
+            spectrum::decay(&mut self.spectrum_values, config.spectrum_smoothing);
+        }
+    }
+
+    /// Back to the idle baseline once the overlay is hidden
+    fn reset_session(&mut self) {
+        self.clear_session_display();
+        self.closing_progress = 0.0;
+        self.closing_started_at = None;
+        self.typing_progress = 0.0;
+    }
+
+    /// Advance an in-progress fade-in toward full opacity
+    fn advance_fade(&mut self, config: &GuiConfig) {
+        let Some(started_at) = self.fade_started_at else {
+            return;
+        };
+
+        let duration_ms = config.startup_fade_duration_ms;
+        let progress = if duration_ms == 0 {
+            1.0
+        } else {
+            started_at.elapsed().as_millis() as f32 / duration_ms as f32
+        };
+
+        if progress >= 1.0 {
+            self.fade = 1.0;
+            self.fade_started_at = None;
+        } else {
+            self.fade = config.startup_fade_easing.apply(progress);
+        }
+    }
+
+    /// Advance the collapse animation while closing
+    fn advance_closing(&mut self, config: &GuiConfig) {
+        let Some(started_at) = self.closing_started_at else {
+            return;
+        };
+        if self.gui_state != GuiState::Closing {
+            self.closing_started_at = None;
+            return;
+        }
+
+        let progress = started_at.elapsed().as_secs_f32() / CLOSING_DURATION.as_secs_f32();
+        self.closing_progress = config.closing_easing.apply(progress);
+    }
+
+    /// Track how long the input has been quiet for the idle spectrum animation
+    fn track_quiet(&mut self, config: &GuiConfig) {
+        if !config.spectrum_idle_animation || !idle::is_quiet(&self.spectrum_values, config.spectrum_idle_threshold) {
+            self.quiet_since = None;
+        } else if self.quiet_since.is_none() {
+            self.quiet_since = Some(Instant::now());
+        }
+    }
+
+    /// Advance the peak markers while listening
+    fn track_peaks(&mut self, config: &GuiConfig) {
+        if !config.spectrum_peak_hold || self.gui_state != GuiState::Listening {
+            self.peaks_updated_at = None;
+            return;
+        }
+        let now = Instant::now();
+        let elapsed = self.peaks_updated_at.map(|at| now - at).unwrap_or_default();
+        self.peak_hold.update(&self.spectrum_values, elapsed, config.spectrum_peak_decay);
+        self.peaks_updated_at = Some(now);
+    }
+
+    /// Spectrum values to render, blended toward the idle pulse during silence
+    fn display_spectrum(&self) -> Vec<f32> {
+        match self.quiet_since {
+            Some(since) => {
+                let quiet_for = since.elapsed();
+                idle::apply_idle(&self.spectrum_values, idle::idle_blend(quiet_for), quiet_for.as_secs_f32())
+            }
+            None => self.spectrum_values.clone(),
         }
     }
 }
@@ -125,14 +350,12 @@ pub fn run_integrated(
     spectrum_tx: broadcast::Sender<Vec<f32>>,
     gui_status_tx: mpsc::Sender<GuiStatus>,
     runtime_handle: tokio::runtime::Handle,
+    gui_config: GuiConfig,
 ) -> GuiResult<()> {
     info!("Starting slint-gui (integrated mode)");
 
-    // Don't set SLINT_BACKEND - layer-shika uses slint-interpreter which doesn't need it
-    // env::set_var("SLINT_BACKEND", "winit-femtovg");
-
     // Create shared state
-    let shared_state = Arc::new(RwLock::new(SharedState::default()));
+    let shared_state = Arc::new(RwLock::new(SharedState::new(gui_config.spectrum_band_count)));
 
     // Create reload flag for hot-reload
     let reload_flag = Arc::new(AtomicBool::new(false));
@@ -148,10 +371,13 @@ pub fn run_integrated(
         shared_state.clone(),
         gui_status_tx.clone(),
         runtime_handle.clone(),
+        gui_config.spectrum_curve,
+        gui_config.spectrum_band_count,
+        gui_config.spectrum_smoothing,
     );
 
     // Spawn active monitor listener (updates global state on monitor change)
-    monitor::spawn_active_monitor_listener(None);
+    monitor::spawn_active_monitor_listener(gui_config.monitor_backend, None);
 
     // Spawn UI file watcher for hot-reload
     spawn_ui_file_watcher(reload_flag.clone());
@@ -159,7 +385,7 @@ pub fn run_integrated(
     // Run the single persistent shell with reload support
     // Send Ready signal AFTER Shell is created but BEFORE event loop starts
     info!("Creating Wayland layer shell (this may take a few seconds)...");
-    match run_shell(shared_state, reload_flag, gui_status_tx) {
+    match run_shell(shared_state, reload_flag, gui_status_tx, gui_config) {
         Ok(_) => Ok(()),
         Err(e) => {
             error!("Failed to create/run shell: {}", e);
@@ -175,6 +401,9 @@ fn spawn_channel_listener(
     shared_state: Arc<RwLock<SharedState>>,
     gui_status_tx: mpsc::Sender<GuiStatus>,
     runtime_handle: tokio::runtime::Handle,
+    spectrum_curve: SpectrumCurve,
+    band_count: usize,
+    smoothing: f32,
 ) {
     // Control message listener
     let state_clone = shared_state.clone();
@@ -188,31 +417,64 @@ fn spawn_channel_listener(
                         match control {
                             GuiControl::Initialize => {
                                 state.gui_state = GuiState::Hidden;
+                                state.reset_session();
                             }
                             GuiControl::SetHidden => {
                                 state.gui_state = GuiState::Hidden;
+                                state.reset_session();
                             }
                             GuiControl::SetListening => {
+                                // Start fade-in only when appearing from hidden
+                                if old_state == GuiState::Hidden {
+                                    state.fade = 0.0;
+                                    state.fade_started_at = Some(Instant::now());
+                                    // Don't flash the previous session's text
+                                    state.transcription.clear();
+                                }
                                 state.gui_state = GuiState::Listening;
-                                state.fade = 1.0;
                                 state.pre_listening = false;
                             }
                             GuiControl::UpdateTranscription { text, .. } => {
                                 state.transcription = text;
                             }
                             GuiControl::UpdateSpectrum(values) => {
-                                state.spectrum_values = values;
+                                state.receive_spectrum(&values, smoothing);
                             }
-                            GuiControl::UpdateVadState { .. } => {
-                                // VAD state handled elsewhere
+                            GuiControl::UpdateVadState { is_speaking, .. } => {
+                                if is_speaking != state.vad_active {
+                                    debug!("VAD: {}", if is_speaking { "speech" } else { "silence" });
+                                }
+                                state.vad_active = is_speaking;
                             }
                             GuiControl::SetProcessing => {
+                                // The processing view doesn't show text; clearing it here
+                                // keeps it out of the next session
+                                state.clear_session_display();
                                 state.gui_state = GuiState::Processing;
                                 state.fade = 1.0;
+                                state.fade_started_at = None;
+                            }
+                            GuiControl::ShowResult(text) => {
+                                state.transcription = text;
+                                state.gui_state = GuiState::Result;
+                                // Nothing is being heard any more; dims the frozen bars
+                                state.vad_active = false;
+                                state.fade = 1.0;
+                                state.fade_started_at = None;
+                            }
+                            GuiControl::SetTyping => {
+                                state.gui_state = GuiState::Typing;
+                                state.typing_progress = 0.0;
+                                state.fade = 1.0;
+                                state.fade_started_at = None;
+                            }
+                            GuiControl::UpdateTypingProgress(progress) => {
+                                state.typing_progress = progress.clamp(0.0, 1.0);
                             }
                             GuiControl::SetClosing => {
                                 state.gui_state = GuiState::Closing;
                                 state.closing_progress = 0.0;
+                                state.closing_started_at = Some(Instant::now());
                             }
                             GuiControl::Exit => {
                                 info!("Received Exit command");
@@ -223,10 +485,10 @@ fn spawn_channel_listener(
                         let new_state = state.gui_state;
                         if old_state != new_state {
                             debug!("State transition: {:?} -> {:?}", old_state, new_state);
-                            let _ = status_tx.try_send(GuiStatus::TransitionComplete {
-                                from: old_state,
-                                to: new_state,
-                            });
+                            send_status(
+                                &status_tx,
+                                GuiStatus::TransitionComplete { from: old_state, to: new_state },
+                            );
                         }
                     }
                 }
@@ -244,12 +506,13 @@ fn spawn_channel_listener(
     // Spectrum listener
     let state_clone = shared_state.clone();
     runtime_handle.spawn(async move {
+        let mut analyzer = SpectrumAnalyzer::new(band_count, spectrum_curve, SPECTRUM_SAMPLE_RATE);
         loop {
             match spectrum_rx.recv().await {
                 Ok(raw_samples) => {
-                    let bands = compute_spectrum_bands(&raw_samples);
+                    let bands = analyzer.bands(&raw_samples);
                     if let Ok(mut state) = state_clone.write() {
-                        state.spectrum_values = bands;
+                        state.receive_spectrum(&bands, smoothing);
                     }
                 }
                 Err(broadcast::error::RecvError::Lagged(_)) => {}
@@ -259,35 +522,25 @@ fn spawn_channel_listener(
     });
 }
 
-/// Simple spectrum computation - 8 frequency bands from audio samples
-fn compute_spectrum_bands(samples: &[f32]) -> Vec<f32> {
-    let len = samples.len();
-    if len == 0 {
-        return vec![0.0; 8];
-    }
-
-    let chunk_size = len / 8;
-    if chunk_size == 0 {
-        return vec![0.0; 8];
-    }
-
-    let mut bands = Vec::with_capacity(8);
-
-    for i in 0..8 {
-        let start = i * chunk_size;
-        let end = if i == 7 { len } else { (i + 1) * chunk_size };
-        let chunk = &samples[start..end];
-
-        // RMS energy
-        let sum: f32 = chunk.iter().map(|&x| x * x).sum();
-        let rms = (sum / chunk.len() as f32).sqrt();
-
-        // Normalize to 0-1 range (15x multiplier for visible movement)
-        let normalized = (rms * 15.0).min(1.0);
-        bands.push(normalized);
+/// Send an informational status without blocking the listener. Required statuses
+/// (`Ready`/`Error`) are sent with `blocking_send` from the shell thread instead.
+fn send_status(status_tx: &mpsc::Sender<GuiStatus>, status: GuiStatus) {
+    debug_assert!(!status.is_required(), "required status must not use try_send");
+    match status_tx.try_send(status) {
+        Ok(()) => {}
+        Err(mpsc::error::TrySendError::Full(status)) => {
+            warn!("GUI status channel full, dropping {:?}", status);
+        }
+        Err(mpsc::error::TrySendError::Closed(_)) => {
+            debug!("GUI status channel closed");
+        }
     }
+}
 
-    bands
+/// Slint `[float]` model from per-band values
+fn number_model(values: &[f32]) -> Value {
+    let values: Vec<Value> = values.iter().map(|&v| Value::Number(v as f64)).collect();
+    Value::Model(ModelRc::new(VecModel::from(values)))
 }
 
 /// Convert GuiState to mode integer for Slint
@@ -296,7 +549,11 @@ fn state_to_mode(state: GuiState) -> i32 {
         GuiState::Hidden => 0,
         GuiState::PreListening => 1,
         GuiState::Listening => 1,
+        // Listening layout showing the final text
+        GuiState::Result => 1,
         GuiState::Processing => 2,
+        // Same overlay as processing, with the spinner turned into a progress ring
+        GuiState::Typing => 2,
         GuiState::Closing => 3,
     }
 }
@@ -307,14 +564,29 @@ const EXIT_CODE_RELOAD: i32 = 64;
 /// Exit code when all layer surfaces are lost (triggers systemd restart via Restart=on-failure)
 const EXIT_CODE_SURFACES_LOST: i32 = 1;
 
-/// Number of timer ticks (~16ms each) to wait before exiting after all surfaces are lost (~3s)
-const SURFACE_LOSS_GRACE_TICKS: u32 = 188;
+/// How long to wait before exiting after all surfaces are lost
+const SURFACE_LOSS_GRACE: Duration = Duration::from_secs(3);
+
+/// Timer cadence while the overlay is visible (~60fps)
+const ACTIVE_UPDATE_INTERVAL: Duration = Duration::from_millis(16);
+
+/// Timer cadence while the overlay is hidden. Short enough that the next
+/// SetListening shows up promptly, long enough to avoid 60fps idle wakeups.
+const HIDDEN_UPDATE_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Overlay surface size (listening mode is the widest and tallest)
+const OVERLAY_WIDTH: u32 = 380;
+const OVERLAY_HEIGHT: u32 = 90;
+
+/// Gap between the overlay and the bottom screen edge in screen placement
+const OVERLAY_BOTTOM_MARGIN: i32 = 50;
 
 /// Run the single persistent shell with dynamic property updates
 fn run_shell(
     shared_state: Arc<RwLock<SharedState>>,
     reload_flag: Arc<AtomicBool>,
     gui_status_tx: mpsc::Sender<GuiStatus>,
+    gui_config: GuiConfig,
 ) -> GuiResult<()> {
     let ui_file = resolve_ui_path("dictation");
     info!("Loading UI from: {}", ui_file);
@@ -325,10 +597,10 @@ fn run_shell(
     info!("Creating Shell from UI file...");
     let mut runtime = Shell::from_file(&ui_file)
         .surface("Dictation")
-        .width(380)  // Listening mode is widest
-        .height(90)  // Listening mode is tallest
+        .width(OVERLAY_WIDTH)
+        .height(OVERLAY_HEIGHT)
         .anchor(AnchorEdges::empty().with_bottom())
-        .margin((0, 0, 50, 0))
+        .margin((0, 0, OVERLAY_BOTTOM_MARGIN, 0))
         .layer(Layer::Overlay)
         .keyboard_interactivity(KeyboardInteractivity::None)
         .output_policy(OutputPolicy::AllOutputs)  // Surfaces on all monitors
@@ -358,13 +630,20 @@ fn run_shell(
 
     // Set up periodic timer to sync shared state to component properties
     // This runs inside the event loop and can safely access the component
-    let update_interval = Duration::from_millis(16); // ~60fps
-
-    let mut empty_surface_ticks: u32 = 0;
+    // Runs at ACTIVE_UPDATE_INTERVAL while visible, drops to HIDDEN_UPDATE_INTERVAL once
+    // hidden state has been pushed to every surface.
+    let mut surfaces_lost_since: Option<Instant> = None;
     let mut gui_initialized = false;
+    let mut hidden_applied = false;
+    // Cursor/window placement is computed once each time the overlay appears
+    let mut placed = false;
+    // Warn once when the configured fallback monitor isn't connected
+    let mut fallback_missing_warned = false;
+    // What each surface was last given, so unchanged properties aren't set again
+    let mut pushed: HashMap<_, props::SurfaceProps> = HashMap::new();
 
     event_loop
-        .add_timer(update_interval, move |_deadline: Instant, app_state| {
+        .add_timer(ACTIVE_UPDATE_INTERVAL, move |_deadline: Instant, app_state| {
             // Check for UI file reload request (dev workflow)
             if reload_flag.load(Ordering::SeqCst) {
                 info!("UI file changed, reloading shell...");
@@ -376,19 +655,57 @@ fn run_shell(
             let surface_count = app_state.surfaces_with_keys().count();
             if surface_count > 0 {
                 gui_initialized = true;
-                empty_surface_ticks = 0;
+                surfaces_lost_since = None;
             } else if gui_initialized {
-                empty_surface_ticks += 1;
-                if empty_surface_ticks >= SURFACE_LOSS_GRACE_TICKS {
+                let lost_since = *surfaces_lost_since.get_or_insert_with(Instant::now);
+                if lost_since.elapsed() >= SURFACE_LOSS_GRACE {
                     error!(
                         "All layer surfaces lost for ~{}s after init, exiting for systemd restart",
-                        (empty_surface_ticks as u64 * 16) / 1000
+                        lost_since.elapsed().as_secs()
                     );
                     std::process::exit(EXIT_CODE_SURFACES_LOST);
                 }
             }
 
-            // Get active monitor from Hyprland
+            // Advance startup fade-in, collapse animation, spectrum decay, quiet-input and peak tracking
+            if let Ok(mut state) = shared_state.write() {
+                state.advance_fade(&gui_config);
+                state.advance_closing(&gui_config);
+                state.decay_stale_spectrum(&gui_config);
+                state.track_quiet(&gui_config);
+                state.track_peaks(&gui_config);
+            }
+
+            // Idle fast path: nothing to update once every surface has been set hidden
+            let is_hidden = shared_state
+                .read()
+                .map(|state| state.gui_state == GuiState::Hidden)
+                .unwrap_or(false);
+            if is_hidden {
+                placed = false;
+                if hidden_applied {
+                    return TimeoutAction::ToDuration(HIDDEN_UPDATE_INTERVAL);
+                }
+            }
+            hidden_applied = is_hidden;
+
+            if !is_hidden && !placed && gui_config.position != OverlayPosition::Screen {
+                placed = true;
+                let (anchor, margin) = match position::overlay_margins(
+                    gui_config.position,
+                    OVERLAY_WIDTH as i32,
+                    OVERLAY_HEIGHT as i32,
+                ) {
+                    Some((top, left)) => (AnchorEdges::empty().with_top().with_left(), (top, 0, 0, left)),
+                    None => (AnchorEdges::empty().with_bottom(), (0, 0, OVERLAY_BOTTOM_MARGIN, 0)),
+                };
+                debug!("Placing overlay: {:?} margins={:?}", gui_config.position, margin);
+                for (_, surface_state) in app_state.surfaces_with_keys() {
+                    surface_state.set_anchor_and_margin(anchor, margin);
+                }
+            }
+
+            // Get active monitor from the compositor
             let active_monitor = monitor::get_active_monitor();
 
             if let Ok(state) = shared_state.read() {
@@ -400,21 +717,53 @@ fn run_shell(
                 // Graceful degradation: show on all monitors when detection unavailable
                 let use_all_monitors = active_monitor.is_none()
                     || active_monitor.as_ref().map_or(false, |s| s.is_empty());
+                // A configured fallback monitor replaces "all monitors" while it's connected
+                let fallback_output = if use_all_monitors && !gui_config.fallback_monitor.trim().is_empty() {
+                    let outputs: Vec<String> = app_state
+                        .surfaces_with_keys()
+                        .filter_map(|(key, _)| {
+                            app_state.get_output_info(key.output_handle)
+                                .and_then(|info| info.name().map(|n| n.to_string()))
+                        })
+                        .collect();
+                    let resolved = monitor::resolve_fallback_monitor(&gui_config.fallback_monitor, &outputs);
+                    if resolved.is_none() && !fallback_missing_warned {
+                        warn!(
+                            "fallback_monitor '{}' is not connected (outputs: {:?}), showing on all monitors",
+                            gui_config.fallback_monitor, outputs
+                        );
+                        fallback_missing_warned = true;
+                    }
+                    resolved
+                } else {
+                    None
+                };
                 if use_all_monitors && state.gui_state != GuiState::Hidden {
-                    debug!("Monitor detection unavailable, showing GUI on all monitors");
+                    match fallback_output {
+                        Some(ref name) => debug!("Monitor detection unavailable, showing GUI on fallback {}", name),
+                        None => debug!("Monitor detection unavailable, showing GUI on all monitors"),
+                    }
+                }
+
+                // Forget surfaces whose output went away
+                if pushed.len() > surface_count {
+                    let live: Vec<_> = app_state.surfaces_with_keys().map(|(key, _)| key.clone()).collect();
+                    pushed.retain(|key, _| live.contains(key));
                 }
 
                 // Iterate all surfaces with their output handles
                 for (key, surface_state) in app_state.surfaces_with_keys() {
                     let component = surface_state.component_instance();
+                    let props = pushed.entry(key.clone()).or_default();
 
                     // Determine if this surface is on the active monitor
                     let output_name = app_state.get_output_info(key.output_handle)
                         .and_then(|info| info.name().map(|n| n.to_string()));
 
                     let is_active = if use_all_monitors {
-                        // Show on all monitors when detection unavailable
+                        // Show on the fallback monitor, or all monitors, when detection unavailable
                         state.gui_state != GuiState::Hidden
+                            && fallback_output.as_ref().map_or(true, |fallback| output_name.as_ref() == Some(fallback))
                     } else if let Some(ref active_name) = active_monitor {
                         // Normal behavior: only show on active monitor
                         output_name.as_ref()
@@ -434,51 +783,113 @@ fn run_shell(
                         0  // Hidden
                     };
 
-                    if state.gui_state != GuiState::Hidden && mode != 0 {
-                        info!("Setting mode={} on surface output={:?} (active_monitor={:?})", mode, output_name, active_monitor);
-                    }
-
-                    if let Err(e) = component.set_property("mode", Value::Number(mode as f64)) {
-                        debug!("Failed to set mode: {}", e);
+                    if props.mode.update(&mode) {
+                        if mode != 0 {
+                            info!("Setting mode={} on surface output={:?} (active_monitor={:?})", mode, output_name, active_monitor);
+                        }
+                        if let Err(e) = component.set_property("mode", Value::Number(mode as f64)) {
+                            debug!("Failed to set mode: {}", e);
+                        }
                     }
 
                     // Only update other properties for active surface
                     if is_active {
-                        // Update spectrum for listening mode
-                        if state.gui_state == GuiState::Listening || state.gui_state == GuiState::PreListening {
-                            // Convert spectrum values to a model
-                            let spectrum_values: [Value; 8] = [
-                                Value::Number(state.spectrum_values.get(0).copied().unwrap_or(0.0) as f64),
-                                Value::Number(state.spectrum_values.get(1).copied().unwrap_or(0.0) as f64),
-                                Value::Number(state.spectrum_values.get(2).copied().unwrap_or(0.0) as f64),
-                                Value::Number(state.spectrum_values.get(3).copied().unwrap_or(0.0) as f64),
-                                Value::Number(state.spectrum_values.get(4).copied().unwrap_or(0.0) as f64),
-                                Value::Number(state.spectrum_values.get(5).copied().unwrap_or(0.0) as f64),
-                                Value::Number(state.spectrum_values.get(6).copied().unwrap_or(0.0) as f64),
-                                Value::Number(state.spectrum_values.get(7).copied().unwrap_or(0.0) as f64),
-                            ];
-                            if let Err(e) = component.set_property("spectrum", Value::Model(spectrum_values.into())) {
-                                debug!("Failed to set spectrum: {}", e);
+                        // Update spectrum and text for listening mode (the result view keeps
+                        // the last spectrum frame)
+                        if matches!(state.gui_state, GuiState::Listening | GuiState::PreListening | GuiState::Result) {
+                            if state.gui_state != GuiState::Result {
+                                // Convert spectrum values to a model
+                                let mut bands = state.display_spectrum();
+                                bands.resize(gui_config.spectrum_band_count, 0.0);
+                                if props.spectrum.update(bands.as_slice()) {
+                                    if let Err(e) = component.set_property("spectrum", number_model(&bands)) {
+                                        debug!("Failed to set spectrum: {}", e);
+                                    }
+                                }
+                                if gui_config.spectrum_peak_hold && props.peaks.update(state.peak_hold.peaks()) {
+                                    let peaks = number_model(state.peak_hold.peaks());
+                                    if let Err(e) = component.set_property("peaks", peaks) {
+                                        debug!("Failed to set peaks: {}", e);
+                                    }
+                                }
                             }
 
-                            // Update transcription text
-                            if let Err(e) = component.set_property("text", Value::String(state.transcription.clone().into())) {
-                                debug!("Failed to set text: {}", e);
+                            // Update transcription text, keeping the latest words when it's too long
+                            // for a theme that opts in with fit-text. The placeholder stands in until
+                            // the first word is recognized.
+                            let source = if state.gui_state == GuiState::Listening && state.transcription.trim().is_empty() {
+                                &gui_config.listening_placeholder
+                            } else {
+                                &state.transcription
+                            };
+                            if props.text_source.update(source.as_str()) {
+                                let fit_text = matches!(component.get_property("fit-text"), Ok(Value::Bool(true)));
+                                let text = if fit_text {
+                                    text_fit::fit_tail(
+                                        source,
+                                        text_fit::columns_for_width(gui_config.text_max_width),
+                                        text_fit::OVERLAY_TEXT_LINES,
+                                    )
+                                } else {
+                                    source.clone()
+                                };
+                                if let Err(e) = component.set_property("text", Value::String(text.into())) {
+                                    debug!("Failed to set text: {}", e);
+                                }
                             }
 
                             // Update pre-listening flag
-                            if let Err(e) = component.set_property("pre-listening", Value::Bool(state.pre_listening)) {
-                                debug!("Failed to set pre-listening: {}", e);
+                            if props.pre_listening.update(&state.pre_listening) {
+                                if let Err(e) = component.set_property("pre-listening", Value::Bool(state.pre_listening)) {
+                                    debug!("Failed to set pre-listening: {}", e);
+                                }
+                            }
+
+                            if props.speaking.update(&state.vad_active) {
+                                if let Err(e) = component.set_property("speaking", Value::Bool(state.vad_active)) {
+                                    debug!("Failed to set speaking: {}", e);
+                                }
+                            }
+
+                            // Text color/opacity from config
+                            if !props.text_style_applied {
+                                props.text_style_applied = true;
+                                if let Err(e) = component.set_property("text-color", Value::Brush(slint::Brush::SolidColor(gui_config.text_color))) {
+                                    debug!("Failed to set text-color: {}", e);
+                                }
+                                if let Err(e) = component.set_property("text-opacity", Value::Number(gui_config.text_opacity as f64)) {
+                                    debug!("Failed to set text-opacity: {}", e);
+                                }
+                                if let Err(e) = component.set_property("text-max-width", Value::Number(gui_config.text_max_width as f64)) {
+                                    debug!("Failed to set text-max-width: {}", e);
+                                }
                             }
                         }
 
                         // Update fade
-                        if let Err(e) = component.set_property("fade", Value::Number(state.fade as f64)) {
-                            debug!("Failed to set fade: {}", e);
+                        if props.fade.update(&state.fade) {
+                            if let Err(e) = component.set_property("fade", Value::Number(state.fade as f64)) {
+                                debug!("Failed to set fade: {}", e);
+                            }
+                        }
+
+                        // Update typing indicator (custom UIs may not define these)
+                        if matches!(state.gui_state, GuiState::Processing | GuiState::Typing) {
+                            let typing = state.gui_state == GuiState::Typing;
+                            if props.typing.update(&typing) {
+                                if let Err(e) = component.set_property("typing", Value::Bool(typing)) {
+                                    debug!("Failed to set typing: {}", e);
+                                }
+                            }
+                            if typing && props.typing_progress.update(&state.typing_progress) {
+                                if let Err(e) = component.set_property("typing-progress", Value::Number(state.typing_progress as f64)) {
+                                    debug!("Failed to set typing-progress: {}", e);
+                                }
+                            }
                         }
 
                         // Update closing progress
-                        if state.gui_state == GuiState::Closing {
+                        if state.gui_state == GuiState::Closing && props.closing_progress.update(&state.closing_progress) {
                             if let Err(e) = component.set_property("closing-progress", Value::Number(state.closing_progress as f64)) {
                                 debug!("Failed to set closing-progress: {}", e);
                             }
@@ -488,7 +899,11 @@ fn run_shell(
             }
 
             // Return ToDuration to reschedule the timer
-            TimeoutAction::ToDuration(update_interval)
+            if is_hidden {
+                TimeoutAction::ToDuration(HIDDEN_UPDATE_INTERVAL)
+            } else {
+                TimeoutAction::ToDuration(ACTIVE_UPDATE_INTERVAL)
+            }
         })
         .map_err(|e| format!("Failed to add timer: {}", e))?;
 
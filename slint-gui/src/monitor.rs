@@ -1,5 +1,15 @@
-//! Monitor detection and active monitor tracking for Hyprland
+//! Monitor detection and active monitor tracking.
+//!
+//! The focused monitor comes from an [`ActiveMonitorSource`]: Hyprland (IPC
+//! events), Sway (`swaymsg`) or niri (`niri msg`). The source is chosen by
+//! config or auto-detected from the compositor's environment variables. When
+//! no source is available the overlay is shown on the configured fallback
+//! monitor, or on all monitors.
+//!
+//! The compositor is detected once at startup; Hyprland IPC is never attempted
+//! when Hyprland isn't running.
 
+use std::process::Command;
 use std::sync::atomic::{AtomicU32, Ordering};
 use std::sync::{Arc, RwLock};
 use std::thread;
@@ -12,6 +22,9 @@ const MAX_CONSECUTIVE_FAILURES: u32 = 10; // 20 seconds of failures (10 * 2s ret
 /// Circuit breaker: cool-down period after circuit opens
 const CIRCUIT_BREAKER_TIMEOUT: Duration = Duration::from_secs(60);
 
+/// Poll interval for compositors without a focus event stream
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
 /// Health tracking for the monitor listener to implement circuit breaker pattern
 struct MonitorListenerHealth {
     consecutive_failures: AtomicU32,
@@ -21,6 +34,144 @@ struct MonitorListenerHealth {
 /// Global active monitor name
 static ACTIVE_MONITOR: std::sync::OnceLock<Arc<RwLock<String>>> = std::sync::OnceLock::new();
 
+/// Compositor detected at startup
+static COMPOSITOR: std::sync::OnceLock<Compositor> = std::sync::OnceLock::new();
+
+/// The running compositor, as far as the environment tells
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compositor {
+    Hyprland,
+    Sway,
+    Niri,
+    /// Any other compositor (no focused-monitor IPC)
+    Other,
+}
+
+impl Compositor {
+    /// Detect from the compositor's socket environment variables
+    fn detect(has: impl Fn(&str) -> bool) -> Self {
+        if has("HYPRLAND_INSTANCE_SIGNATURE") {
+            Compositor::Hyprland
+        } else if has("SWAYSOCK") {
+            Compositor::Sway
+        } else if has("NIRI_SOCKET") {
+            Compositor::Niri
+        } else {
+            Compositor::Other
+        }
+    }
+}
+
+/// The running compositor, detected and logged on first use
+pub fn compositor() -> Compositor {
+    *COMPOSITOR.get_or_init(|| {
+        let detected = Compositor::detect(|var| std::env::var_os(var).is_some());
+        info!("Detected compositor: {:?}", detected);
+        detected
+    })
+}
+
+/// Whether Hyprland IPC is worth attempting
+pub fn hyprland_available() -> bool {
+    compositor() == Compositor::Hyprland
+}
+
+/// Which compositor to ask for the focused monitor
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MonitorBackend {
+    /// Detect from the environment (HYPRLAND_INSTANCE_SIGNATURE, SWAYSOCK, NIRI_SOCKET)
+    #[default]
+    Auto,
+    Hyprland,
+    Sway,
+    Niri,
+    /// No detection: always show on all monitors
+    None,
+}
+
+impl MonitorBackend {
+    /// Parse from config string
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "auto" => Some(MonitorBackend::Auto),
+            "hyprland" => Some(MonitorBackend::Hyprland),
+            "sway" => Some(MonitorBackend::Sway),
+            "niri" => Some(MonitorBackend::Niri),
+            "none" | "all" => Some(MonitorBackend::None),
+            _ => None,
+        }
+    }
+
+    /// Resolve to a concrete backend for the running compositor.
+    ///
+    /// `Hyprland` outside Hyprland would fail every IPC call, so it is treated
+    /// like `Auto`.
+    fn resolve(self, compositor: Compositor) -> Self {
+        match self {
+            MonitorBackend::Hyprland if compositor != Compositor::Hyprland => {
+                warn!("monitor_backend is 'hyprland' but Hyprland isn't running, detecting instead");
+                MonitorBackend::Auto.resolve(compositor)
+            }
+            MonitorBackend::Auto => match compositor {
+                Compositor::Hyprland => MonitorBackend::Hyprland,
+                Compositor::Sway => MonitorBackend::Sway,
+                Compositor::Niri => MonitorBackend::Niri,
+                Compositor::Other => MonitorBackend::None,
+            },
+            other => other,
+        }
+    }
+
+    fn source(self) -> Option<Box<dyn ActiveMonitorSource>> {
+        match self.resolve(compositor()) {
+            MonitorBackend::Hyprland => Some(Box::new(HyprlandSource)),
+            MonitorBackend::Sway => Some(Box::new(SwaySource)),
+            MonitorBackend::Niri => Some(Box::new(NiriSource)),
+            MonitorBackend::Auto | MonitorBackend::None => None,
+        }
+    }
+}
+
+/// A compositor-specific way to find the focused monitor
+trait ActiveMonitorSource: Send {
+    fn name(&self) -> &'static str;
+
+    /// Query the focused monitor name right now
+    fn query(&self) -> Option<String>;
+
+    /// Keep `monitor` updated with the focused monitor. Never returns.
+    /// Default: poll `query()`.
+    fn watch(&self, monitor: Arc<RwLock<String>>, reload_flag: Option<Arc<std::sync::atomic::AtomicBool>>) {
+        loop {
+            if let Some(name) = self.query() {
+                update_active_monitor(&monitor, &name, reload_flag.as_ref());
+            }
+            thread::sleep(POLL_INTERVAL);
+        }
+    }
+}
+
+/// Store a new active monitor name, flagging a reload if it changed
+fn update_active_monitor(
+    monitor: &RwLock<String>,
+    name: &str,
+    reload_flag: Option<&Arc<std::sync::atomic::AtomicBool>>,
+) {
+    if let Ok(mut m) = monitor.write() {
+        if *m == name {
+            return;
+        }
+        debug!("Active monitor changed from '{}' to '{}'", m, name);
+        *m = name.to_string();
+
+        // Trigger GUI reload if flag provided
+        if let Some(flag) = reload_flag {
+            debug!("Setting reload flag for monitor switch");
+            flag.store(true, Ordering::SeqCst);
+        }
+    }
+}
+
 /// Get the currently active monitor name
 pub fn get_active_monitor() -> Option<String> {
     ACTIVE_MONITOR
@@ -33,6 +184,10 @@ pub fn get_active_monitor_sync() -> Option<String> {
     use hyprland::data::Monitors;
     use hyprland::prelude::*;
 
+    if !hyprland_available() {
+        return None;
+    }
+
     Monitors::get().ok().and_then(|monitors| {
         monitors
             .iter()
@@ -41,6 +196,74 @@ pub fn get_active_monitor_sync() -> Option<String> {
     })
 }
 
+/// Hyprland: initial query over IPC, then the event socket for changes
+struct HyprlandSource;
+
+impl ActiveMonitorSource for HyprlandSource {
+    fn name(&self) -> &'static str {
+        "Hyprland"
+    }
+
+    fn query(&self) -> Option<String> {
+        get_active_monitor_sync()
+    }
+
+    fn watch(&self, monitor: Arc<RwLock<String>>, reload_flag: Option<Arc<std::sync::atomic::AtomicBool>>) {
+        watch_hyprland_events(monitor, reload_flag);
+    }
+}
+
+/// Sway: focused output from `swaymsg -t get_outputs`
+struct SwaySource;
+
+impl ActiveMonitorSource for SwaySource {
+    fn name(&self) -> &'static str {
+        "Sway"
+    }
+
+    fn query(&self) -> Option<String> {
+        let output = Command::new("swaymsg").args(["-t", "get_outputs", "-r"]).output().ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        parse_sway_focused_output(&String::from_utf8_lossy(&output.stdout))
+    }
+}
+
+/// niri: `niri msg --json focused-output`
+struct NiriSource;
+
+impl ActiveMonitorSource for NiriSource {
+    fn name(&self) -> &'static str {
+        "niri"
+    }
+
+    fn query(&self) -> Option<String> {
+        let output = Command::new("niri").args(["msg", "--json", "focused-output"]).output().ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        parse_niri_focused_output(&String::from_utf8_lossy(&output.stdout))
+    }
+}
+
+/// Name of the output with `"focused": true` in `swaymsg -t get_outputs -r` JSON
+fn parse_sway_focused_output(json: &str) -> Option<String> {
+    let value: serde_json::Value = serde_json::from_str(json).ok()?;
+    value
+        .as_array()?
+        .iter()
+        .find(|o| o["focused"].as_bool() == Some(true))
+        .and_then(|o| o["name"].as_str())
+        .map(str::to_string)
+}
+
+/// Output name from `niri msg --json focused-output` (null when nothing is focused)
+fn parse_niri_focused_output(json: &str) -> Option<String> {
+    let value: serde_json::Value = serde_json::from_str(json).ok()?;
+    value["name"].as_str().map(str::to_string)
+}
+
 /// Refresh Hyprland environment variables and verify socket accessibility
 /// This helps handle Hyprland restarts or session switches gracefully
 fn refresh_hyprland_environment() -> bool {
@@ -78,95 +301,163 @@ fn refresh_hyprland_environment() -> bool {
     }
 }
 
-/// Spawn a background thread to track active monitor changes
-pub fn spawn_active_monitor_listener(reload_flag: Option<Arc<std::sync::atomic::AtomicBool>>) {
-    use hyprland::event_listener::{EventListener, MonitorEventData};
+/// Spawn a background thread to track active monitor changes.
+///
+/// Without a usable backend the active monitor stays unknown and the overlay
+/// is shown on all monitors.
+pub fn spawn_active_monitor_listener(
+    backend: MonitorBackend,
+    reload_flag: Option<Arc<std::sync::atomic::AtomicBool>>,
+) {
+    let Some(source) = backend.source() else {
+        info!("No active monitor detection for this compositor, overlay will show on all monitors");
+        return;
+    };
 
     // Initialize global state
-    let initial = get_active_monitor_sync();
-    info!("Initial active monitor from Hyprland IPC: {:?}", initial);
-    let monitor = Arc::new(RwLock::new(
-        initial.unwrap_or_default(),
-    ));
+    let initial = source.query();
+    info!("Initial active monitor from {}: {:?}", source.name(), initial);
+    let monitor = Arc::new(RwLock::new(initial.unwrap_or_default()));
     let _ = ACTIVE_MONITOR.set(monitor.clone());
 
+    thread::spawn(move || source.watch(monitor, reload_flag));
+}
+
+/// Follow Hyprland's active monitor events, reconnecting with a circuit breaker
+fn watch_hyprland_events(
+    monitor: Arc<RwLock<String>>,
+    reload_flag: Option<Arc<std::sync::atomic::AtomicBool>>,
+) {
+    use hyprland::event_listener::{EventListener, MonitorEventData};
+
     // Create health tracker for circuit breaker
     let health = Arc::new(MonitorListenerHealth {
         consecutive_failures: AtomicU32::new(0),
         circuit_open_until: Arc::new(RwLock::new(None)),
     });
 
-    thread::spawn(move || {
-        loop {
-            // Check circuit breaker state
-            if let Ok(circuit) = health.circuit_open_until.read() {
-                if let Some(open_until) = *circuit {
-                    if Instant::now() < open_until {
-                        // Circuit is open, wait before retrying
-                        debug!("Circuit breaker open, waiting before retry");
-                        thread::sleep(Duration::from_secs(10));
-                        continue;
-                    }
+    loop {
+        // Check circuit breaker state
+        if let Ok(circuit) = health.circuit_open_until.read() {
+            if let Some(open_until) = *circuit {
+                if Instant::now() < open_until {
+                    // Circuit is open, wait before retrying
+                    debug!("Circuit breaker open, waiting before retry");
+                    thread::sleep(Duration::from_secs(10));
+                    continue;
                 }
             }
+        }
+
+        // Refresh environment before reconnect attempt
+        refresh_hyprland_environment();
+
+        let monitor_clone = monitor.clone();
+        let reload_flag_clone = reload_flag.clone();
+        let mut listener = EventListener::new();
+
+        listener.add_active_monitor_changed_handler(move |data: MonitorEventData| {
+            update_active_monitor(&monitor_clone, &data.monitor_name, reload_flag_clone.as_ref());
+        });
+
+        match listener.start_listener() {
+            Ok(_) => {
+                // Success - reset failure counter
+                health.consecutive_failures.store(0, Ordering::SeqCst);
+                debug!("Hyprland monitor listener connected successfully");
+            }
+            Err(e) => {
+                // Failure - increment counter and check circuit breaker
+                let failures = health.consecutive_failures.fetch_add(1, Ordering::SeqCst) + 1;
 
-            // Refresh environment before reconnect attempt
-            refresh_hyprland_environment();
-
-            let monitor_clone = monitor.clone();
-            let reload_flag_clone = reload_flag.clone();
-            let mut listener = EventListener::new();
-
-            listener.add_active_monitor_changed_handler(move |data: MonitorEventData| {
-                if let Ok(mut m) = monitor_clone.write() {
-                    let old_monitor = m.clone();
-                    debug!("Active monitor changed from '{}' to '{}'", old_monitor, data.monitor_name);
-                    *m = data.monitor_name.clone();
-
-                    // Trigger GUI reload if flag provided and monitor actually changed
-                    if let Some(ref flag) = reload_flag_clone {
-                        if old_monitor != data.monitor_name {
-                            debug!("Setting reload flag for monitor switch");
-                            flag.store(true, Ordering::SeqCst);
-                        }
+                if failures >= MAX_CONSECUTIVE_FAILURES {
+                    // Open circuit breaker
+                    warn!(
+                        "Hyprland monitor listener failed {} times, opening circuit breaker for {}s: {}",
+                        failures,
+                        CIRCUIT_BREAKER_TIMEOUT.as_secs(),
+                        e
+                    );
+
+                    if let Ok(mut circuit) = health.circuit_open_until.write() {
+                        *circuit = Some(Instant::now() + CIRCUIT_BREAKER_TIMEOUT);
                     }
-                }
-            });
 
-            match listener.start_listener() {
-                Ok(_) => {
-                    // Success - reset failure counter
+                    // Reset failure counter for next circuit attempt
                     health.consecutive_failures.store(0, Ordering::SeqCst);
-                    debug!("Hyprland monitor listener connected successfully");
-                }
-                Err(e) => {
-                    // Failure - increment counter and check circuit breaker
-                    let failures = health.consecutive_failures.fetch_add(1, Ordering::SeqCst) + 1;
-
-                    if failures >= MAX_CONSECUTIVE_FAILURES {
-                        // Open circuit breaker
-                        warn!(
-                            "Hyprland monitor listener failed {} times, opening circuit breaker for {}s: {}",
-                            failures,
-                            CIRCUIT_BREAKER_TIMEOUT.as_secs(),
-                            e
-                        );
-
-                        if let Ok(mut circuit) = health.circuit_open_until.write() {
-                            *circuit = Some(Instant::now() + CIRCUIT_BREAKER_TIMEOUT);
-                        }
-
-                        // Reset failure counter for next circuit attempt
-                        health.consecutive_failures.store(0, Ordering::SeqCst);
-                    } else {
-                        warn!(
-                            "Hyprland event listener error (attempt {}/{}): {}",
-                            failures, MAX_CONSECUTIVE_FAILURES, e
-                        );
-                        thread::sleep(Duration::from_secs(2));
-                    }
+                } else {
+                    warn!(
+                        "Hyprland event listener error (attempt {}/{}): {}",
+                        failures, MAX_CONSECUTIVE_FAILURES, e
+                    );
+                    thread::sleep(Duration::from_secs(2));
                 }
             }
         }
-    });
+    }
+}
+
+/// Output to show the overlay on when the focused monitor can't be determined.
+///
+/// Matches `configured` case-insensitively against the connected `outputs` and
+/// returns the output's own name, or None when it isn't connected.
+pub fn resolve_fallback_monitor(configured: &str, outputs: &[String]) -> Option<String> {
+    let configured = configured.trim();
+    outputs.iter().find(|name| name.eq_ignore_ascii_case(configured)).cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backend_from_str() {
+        assert_eq!(MonitorBackend::from_str("auto"), Some(MonitorBackend::Auto));
+        assert_eq!(MonitorBackend::from_str("Sway"), Some(MonitorBackend::Sway));
+        assert_eq!(MonitorBackend::from_str("none"), Some(MonitorBackend::None));
+        assert_eq!(MonitorBackend::from_str("kwin"), None);
+    }
+
+    #[test]
+    fn test_detect_compositor() {
+        let env = |vars: &'static [&'static str]| move |var: &str| vars.iter().any(|v| *v == var);
+        assert_eq!(Compositor::detect(env(&["HYPRLAND_INSTANCE_SIGNATURE"])), Compositor::Hyprland);
+        assert_eq!(Compositor::detect(env(&["SWAYSOCK"])), Compositor::Sway);
+        assert_eq!(Compositor::detect(env(&[])), Compositor::Other);
+    }
+
+    #[test]
+    fn test_hyprland_backend_needs_hyprland() {
+        assert_eq!(MonitorBackend::Hyprland.resolve(Compositor::Hyprland), MonitorBackend::Hyprland);
+        assert_eq!(MonitorBackend::Hyprland.resolve(Compositor::Niri), MonitorBackend::Niri);
+        assert_eq!(MonitorBackend::Hyprland.resolve(Compositor::Other), MonitorBackend::None);
+        assert_eq!(MonitorBackend::Auto.resolve(Compositor::Sway), MonitorBackend::Sway);
+        assert_eq!(MonitorBackend::Sway.resolve(Compositor::Other), MonitorBackend::Sway);
+    }
+
+    #[test]
+    fn test_parse_sway_outputs() {
+        let json = r#"[
+            {"name": "DP-1", "focused": false},
+            {"name": "HDMI-A-1", "focused": true}
+        ]"#;
+        assert_eq!(parse_sway_focused_output(json), Some("HDMI-A-1".to_string()));
+        assert_eq!(parse_sway_focused_output(r#"[{"name": "DP-1", "focused": false}]"#), None);
+    }
+
+    #[test]
+    fn test_parse_niri_output() {
+        assert_eq!(
+            parse_niri_focused_output(r#"{"name": "eDP-1", "make": "BOE"}"#),
+            Some("eDP-1".to_string())
+        );
+        assert_eq!(parse_niri_focused_output("null"), None);
+    }
+
+    #[test]
+    fn test_resolve_fallback_monitor() {
+        let outputs = vec!["eDP-1".to_string(), "DP-2".to_string()];
+        assert_eq!(resolve_fallback_monitor("dp-2 ", &outputs), Some("DP-2".to_string()));
+        assert_eq!(resolve_fallback_monitor("HDMI-A-1", &outputs), None);
+    }
 }
@@ -0,0 +1,154 @@
+//! Overlay placement near the cursor or focused window.
+//!
+//! Layer-shell surfaces can only be anchored to screen edges, so "near the
+//! cursor" is approximated by anchoring top-left and computing margins from
+//! the target point. Geometry comes from Hyprland IPC; when it's unavailable
+//! (or another compositor is running) the overlay falls back to its normal
+//! bottom-center placement.
+
+use crate::monitor;
+use tracing::{debug, warn};
+
+/// Gap between the target (cursor or window edge) and the overlay
+const TARGET_GAP: i32 = 24;
+
+/// Where the overlay appears on the focused monitor
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OverlayPosition {
+    /// Bottom-center of the screen
+    #[default]
+    Screen,
+    /// Just below the mouse cursor
+    Cursor,
+    /// Bottom-center inside the focused window
+    Window,
+}
+
+impl OverlayPosition {
+    /// Parse from config string
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "screen" | "bottom" => Some(OverlayPosition::Screen),
+            "cursor" | "pointer" => Some(OverlayPosition::Cursor),
+            "window" => Some(OverlayPosition::Window),
+            _ => None,
+        }
+    }
+}
+
+/// Monitor-local logical geometry used to place the overlay
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Placement {
+    monitor_width: i32,
+    monitor_height: i32,
+    /// Point the overlay's top-center should sit at
+    target_x: i32,
+    target_y: i32,
+}
+
+/// Top and left margins for a top-left anchored overlay of the given size,
+/// or None when the overlay should use its default placement.
+pub fn overlay_margins(position: OverlayPosition, width: i32, height: i32) -> Option<(i32, i32)> {
+    if position != OverlayPosition::Screen && !monitor::hyprland_available() {
+        static WARNED: std::sync::Once = std::sync::Once::new();
+        WARNED.call_once(|| warn!("overlay_position {:?} needs Hyprland, using bottom-center", position));
+        return None;
+    }
+
+    let placement = match position {
+        OverlayPosition::Screen => return None,
+        OverlayPosition::Cursor => query_cursor_placement(),
+        OverlayPosition::Window => query_window_placement(height),
+    };
+
+    match placement {
+        Some(placement) => Some(clamp_to_monitor(placement, width, height)),
+        None => {
+            debug!("No geometry for {:?} overlay position, using screen placement", position);
+            None
+        }
+    }
+}
+
+/// Center the overlay horizontally on the target and keep it fully on screen
+fn clamp_to_monitor(placement: Placement, width: i32, height: i32) -> (i32, i32) {
+    let max_left = (placement.monitor_width - width).max(0);
+    let max_top = (placement.monitor_height - height).max(0);
+    let left = (placement.target_x - width / 2).clamp(0, max_left);
+    let top = placement.target_y.clamp(0, max_top);
+    (top, left)
+}
+
+/// Focused monitor origin and logical size
+fn focused_monitor() -> Option<(i32, i32, i32, i32)> {
+    use hyprland::data::Monitors;
+    use hyprland::prelude::*;
+
+    let monitors = Monitors::get().ok()?;
+    let monitor = monitors.iter().find(|m| m.focused)?;
+    let scale = if monitor.scale > 0.0 { monitor.scale } else { 1.0 };
+    Some((
+        monitor.x,
+        monitor.y,
+        (monitor.width as f32 / scale) as i32,
+        (monitor.height as f32 / scale) as i32,
+    ))
+}
+
+fn query_cursor_placement() -> Option<Placement> {
+    use hyprland::data::CursorPosition;
+    use hyprland::prelude::*;
+
+    let cursor = CursorPosition::get().ok()?;
+    let (x, y, monitor_width, monitor_height) = focused_monitor()?;
+    Some(Placement {
+        monitor_width,
+        monitor_height,
+        target_x: cursor.x as i32 - x,
+        target_y: cursor.y as i32 - y + TARGET_GAP,
+    })
+}
+
+fn query_window_placement(overlay_height: i32) -> Option<Placement> {
+    use hyprland::data::Client;
+    use hyprland::prelude::*;
+
+    let client = Client::get_active().ok()??;
+    let (x, y, monitor_width, monitor_height) = focused_monitor()?;
+    let (win_x, win_y) = (client.at.0 as i32 - x, client.at.1 as i32 - y);
+    let (win_w, win_h) = (client.size.0 as i32, client.size.1 as i32);
+    Some(Placement {
+        monitor_width,
+        monitor_height,
+        target_x: win_x + win_w / 2,
+        target_y: win_y + win_h - overlay_height - TARGET_GAP,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn placement(target_x: i32, target_y: i32) -> Placement {
+        Placement { monitor_width: 1920, monitor_height: 1080, target_x, target_y }
+    }
+
+    #[test]
+    fn test_position_from_str() {
+        assert_eq!(OverlayPosition::from_str("Cursor"), Some(OverlayPosition::Cursor));
+        assert_eq!(OverlayPosition::from_str("window"), Some(OverlayPosition::Window));
+        assert_eq!(OverlayPosition::from_str("screen"), Some(OverlayPosition::Screen));
+        assert_eq!(OverlayPosition::from_str("middle"), None);
+    }
+
+    #[test]
+    fn test_centers_on_target() {
+        assert_eq!(clamp_to_monitor(placement(960, 500), 380, 90), (500, 770));
+    }
+
+    #[test]
+    fn test_clamps_to_monitor_edges() {
+        assert_eq!(clamp_to_monitor(placement(10, -40), 380, 90), (0, 0));
+        assert_eq!(clamp_to_monitor(placement(1910, 1070), 380, 90), (990, 1540));
+    }
+}
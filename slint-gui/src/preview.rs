@@ -0,0 +1,119 @@
+//! Standalone preview mode for UI authors.
+//!
+//! Runs the overlay without the daemon and cycles it through its states
+//! (Listening with synthetic spectrum and text → Processing → Typing → Closing → Hidden)
+//! on a loop. Combined with UI hot-reload this gives a fast iteration loop for
+//! custom `.slint` files and animation tuning.
+
+use dictation_types::{GuiControl, GuiStatus};
+use std::thread;
+use std::time::Duration;
+use tokio::sync::{broadcast, mpsc};
+use tracing::{debug, info};
+
+use crate::{run_integrated, GuiConfig, GuiResult};
+
+/// Text revealed word by word while "listening" when none is supplied
+pub const DEFAULT_PREVIEW_TEXT: &str =
+    "The quick brown fox jumps over the lazy dog while the overlay shows live transcription";
+
+/// Samples per synthetic spectrum chunk (matches the daemon's chunk size)
+const SPECTRUM_CHUNK: usize = 512;
+
+/// Run the overlay standalone, driven by synthetic data.
+///
+/// Blocks until the GUI exits. `sample_text` replaces the default preview text
+/// to test sizing and wrapping.
+pub fn run_preview(
+    runtime_handle: tokio::runtime::Handle,
+    sample_text: Option<String>,
+    gui_config: GuiConfig,
+) -> GuiResult<()> {
+    let text = sample_text.unwrap_or_else(|| DEFAULT_PREVIEW_TEXT.to_string());
+    info!("Starting GUI preview mode (Ctrl+C to exit)");
+
+    let (gui_control_tx, _) = broadcast::channel::<GuiControl>(32);
+    let (spectrum_tx, _) = broadcast::channel::<Vec<f32>>(50);
+    let (gui_status_tx, mut gui_status_rx) = mpsc::channel::<GuiStatus>(32);
+
+    // Nobody else consumes statuses in preview mode; log them so the channel never fills
+    runtime_handle.spawn(async move {
+        while let Some(status) = gui_status_rx.recv().await {
+            debug!("GUI status: {:?}", status);
+        }
+    });
+
+    let control_tx = gui_control_tx.clone();
+    let spectrum_driver_tx = spectrum_tx.clone();
+    thread::spawn(move || drive_states(control_tx, spectrum_driver_tx, text));
+
+    run_integrated(gui_control_tx, spectrum_tx, gui_status_tx, runtime_handle, gui_config)
+}
+
+/// Cycle the overlay through every state forever
+fn drive_states(
+    control_tx: broadcast::Sender<GuiControl>,
+    spectrum_tx: broadcast::Sender<Vec<f32>>,
+    text: String,
+) {
+    // Give the layer shell time to come up before the first transition
+    thread::sleep(Duration::from_secs(2));
+
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let mut tick: u64 = 0;
+
+    loop {
+        info!("Preview: Listening");
+        let _ = control_tx.send(GuiControl::UpdateTranscription { text: String::new(), is_final: false });
+        let _ = control_tx.send(GuiControl::SetListening);
+
+        // Reveal one word every 250ms while streaming spectrum data at ~30fps
+        for shown in 0..=words.len() {
+            let partial = words[..shown].join(" ");
+            let _ = control_tx.send(GuiControl::UpdateTranscription { text: partial, is_final: false });
+            // Speech ends after the last word, so the bars dim before processing
+            let is_speaking = shown < words.len();
+            let _ = control_tx.send(GuiControl::UpdateVadState { is_speaking, text_settled: !is_speaking });
+
+            for _ in 0..8 {
+                let _ = spectrum_tx.send(synthetic_audio(tick));
+                tick += 1;
+                thread::sleep(Duration::from_millis(31));
+            }
+        }
+
+        info!("Preview: Processing");
+        let _ = control_tx.send(GuiControl::SetProcessing);
+        thread::sleep(Duration::from_millis(1500));
+
+        info!("Preview: Typing");
+        let _ = control_tx.send(GuiControl::SetTyping);
+        for step in 1..=8 {
+            thread::sleep(Duration::from_millis(150));
+            let _ = control_tx.send(GuiControl::UpdateTypingProgress(step as f32 / 8.0));
+        }
+
+        info!("Preview: Closing");
+        let _ = control_tx.send(GuiControl::SetClosing);
+        thread::sleep(Duration::from_millis(350));
+
+        info!("Preview: Hidden");
+        let _ = control_tx.send(GuiControl::SetHidden);
+        thread::sleep(Duration::from_millis(1000));
+    }
+}
+
+/// Speech-like audio chunk: a tone whose loudness swells and fades over time
+fn synthetic_audio(tick: u64) -> Vec<f32> {
+    let envelope = ((tick as f32 * 0.15).sin() * 0.5 + 0.5) * 0.06;
+    (0..SPECTRUM_CHUNK)
+        .map(|i| {
+            let t = (tick as usize * SPECTRUM_CHUNK + i) as f32 / 16000.0;
+            // A voice-like tone: 220 Hz with fading harmonics so several bars move
+            let voice: f32 = (1..=6)
+                .map(|harmonic| (2.0 * std::f32::consts::PI * 220.0 * harmonic as f32 * t).sin() / harmonic as f32)
+                .sum();
+            voice * envelope
+        })
+        .collect()
+}
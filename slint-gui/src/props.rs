@@ -0,0 +1,72 @@
+//! Change detection for overlay component properties.
+//!
+//! The update timer runs every frame while the overlay is visible. Setting a
+//! property re-evaluates its bindings even when the value is the same, which
+//! adds up for a multi-kilobyte transcription or the spectrum model, so each
+//! surface remembers what it was last given.
+
+use std::borrow::Borrow;
+
+/// Last value set on one property
+#[derive(Debug)]
+pub struct Slot<T>(Option<T>);
+
+impl<T> Default for Slot<T> {
+    fn default() -> Self {
+        Self(None)
+    }
+}
+
+impl<T> Slot<T> {
+    /// Record `value`, returning true when it differs from the last one
+    pub fn update<Q>(&mut self, value: &Q) -> bool
+    where
+        Q: ToOwned<Owned = T> + PartialEq + ?Sized,
+        T: Borrow<Q>,
+    {
+        if self.0.as_ref().map(Borrow::borrow) == Some(value) {
+            return false;
+        }
+        self.0 = Some(value.to_owned());
+        true
+    }
+}
+
+/// Property values last pushed to one surface
+#[derive(Debug, Default)]
+pub struct SurfaceProps {
+    pub mode: Slot<i32>,
+    pub spectrum: Slot<Vec<f32>>,
+    pub peaks: Slot<Vec<f32>>,
+    /// Untruncated text the displayed tail was fitted from
+    pub text_source: Slot<String>,
+    pub pre_listening: Slot<bool>,
+    pub speaking: Slot<bool>,
+    /// Text color, opacity and width come from config and are set once
+    pub text_style_applied: bool,
+    pub fade: Slot<f32>,
+    pub typing: Slot<bool>,
+    pub typing_progress: Slot<f32>,
+    pub closing_progress: Slot<f32>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slot_reports_changes_only() {
+        let mut slot = Slot::default();
+        assert!(slot.update(&3));
+        assert!(!slot.update(&3));
+        assert!(slot.update(&4));
+    }
+
+    #[test]
+    fn test_string_slot_compares_borrowed() {
+        let mut slot: Slot<String> = Slot::default();
+        assert!(slot.update("hello"));
+        assert!(!slot.update("hello"));
+        assert!(slot.update("hello world"));
+    }
+}
@@ -0,0 +1,70 @@
+//! Overlay renderer selection.
+//!
+//! layer-shika drives its own Wayland surfaces and always renders them with
+//! FemtoVG over OpenGL, so `SLINT_BACKEND` has no effect on the overlay. What
+//! can be switched is the GL implementation: Mesa's software rasterizer
+//! (llvmpipe) avoids driver-specific glitches at the cost of some CPU.
+
+use std::env;
+use tracing::{info, warn};
+
+/// Mesa switch that forces software OpenGL
+const SOFTWARE_GL_VAR: &str = "LIBGL_ALWAYS_SOFTWARE";
+
+/// How the overlay is rasterized
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OverlayRenderer {
+    /// FemtoVG on the GPU driver
+    #[default]
+    Gpu,
+    /// FemtoVG on Mesa's software OpenGL
+    Software,
+}
+
+impl OverlayRenderer {
+    /// Parse from config string. Slint backend names are accepted for the GPU path.
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "gpu" | "auto" | "femtovg" | "winit-femtovg" => Some(OverlayRenderer::Gpu),
+            "software" | "llvmpipe" | "cpu" => Some(OverlayRenderer::Software),
+            _ => None,
+        }
+    }
+
+    /// Configure the process environment before the GL context is created.
+    ///
+    /// Modifies the environment, so it must be called before any other thread
+    /// is spawned (in particular before the async runtime is built).
+    /// An externally set `LIBGL_ALWAYS_SOFTWARE` takes precedence over the config.
+    pub fn apply(self) {
+        if let Ok(backend) = env::var("SLINT_BACKEND") {
+            warn!("SLINT_BACKEND={} is ignored: the overlay always renders with FemtoVG", backend);
+        }
+
+        if let Ok(value) = env::var(SOFTWARE_GL_VAR) {
+            info!("Overlay renderer: {}={} set in the environment, ignoring config", SOFTWARE_GL_VAR, value);
+            return;
+        }
+
+        match self {
+            OverlayRenderer::Gpu => info!("Overlay renderer: GPU (FemtoVG)"),
+            OverlayRenderer::Software => {
+                env::set_var(SOFTWARE_GL_VAR, "1");
+                info!("Overlay renderer: software (FemtoVG on Mesa llvmpipe)");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!(OverlayRenderer::from_str("gpu"), Some(OverlayRenderer::Gpu));
+        assert_eq!(OverlayRenderer::from_str("winit-femtovg"), Some(OverlayRenderer::Gpu));
+        assert_eq!(OverlayRenderer::from_str(" Software "), Some(OverlayRenderer::Software));
+        assert_eq!(OverlayRenderer::from_str("skia"), None);
+    }
+}
@@ -0,0 +1,294 @@
+//! Mapping from band energy to bar height.
+//!
+//! Band RMS is multiplied by a gain, clipped to 0-1, then shaped by a gamma
+//! curve: below 1 lifts quiet input so soft speech still moves the bars,
+//! above 1 compresses the low end so only louder speech registers.
+//!
+//! The optional peak hold keeps a marker at each band's recent maximum that
+//! sinks back at a fixed rate.
+//!
+//! Bands come from an FFT of each audio chunk, spaced logarithmically in
+//! frequency; the number of bars is configurable. Optional exponential
+//! smoothing blends each frame into the previous one to reduce flicker.
+
+use rustfft::num_complex::Complex;
+use rustfft::FftPlanner;
+use std::f32::consts::PI;
+use std::time::Duration;
+
+/// Default RMS multiplier; speech peaks around 0.05-0.1 RMS
+pub const DEFAULT_SPECTRUM_GAIN: f32 = 15.0;
+
+/// Default peak marker fall rate, in full bar heights per second
+pub const DEFAULT_PEAK_DECAY: f32 = 0.5;
+
+/// Default number of spectrum bars
+pub const DEFAULT_BAND_COUNT: usize = 8;
+
+/// Most bars that fit across the overlay (6px bars with 6px gaps)
+pub const MAX_BAND_COUNT: usize = 24;
+
+/// Highest smoothing factor; at 1.0 the bars would never move
+pub const MAX_SPECTRUM_SMOOTHING: f32 = 0.95;
+
+/// Rate of the audio chunks the daemon sends for the spectrum
+pub const SPECTRUM_SAMPLE_RATE: f32 = 16000.0;
+
+/// Lowest band edge; below this is mostly hum and handling noise
+const MIN_BAND_HZ: f32 = 80.0;
+
+/// Gain and gamma applied to each band
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpectrumCurve {
+    pub gain: f32,
+    pub gamma: f32,
+}
+
+impl Default for SpectrumCurve {
+    fn default() -> Self {
+        Self { gain: DEFAULT_SPECTRUM_GAIN, gamma: 1.0 }
+    }
+}
+
+impl SpectrumCurve {
+    /// Bar height (0.0-1.0) for a band with the given RMS energy
+    pub fn apply(self, rms: f32) -> f32 {
+        (rms * self.gain).clamp(0.0, 1.0).powf(self.gamma)
+    }
+}
+
+/// Turns audio chunks into bar heights with an FFT.
+///
+/// Each chunk is Hann-windowed and transformed; the magnitudes are summed into
+/// logarithmically spaced bands from `MIN_BAND_HZ` to Nyquist, so speech
+/// spreads across the bars the way it is heard.
+pub struct SpectrumAnalyzer {
+    planner: FftPlanner<f32>,
+    band_count: usize,
+    curve: SpectrumCurve,
+    sample_rate: f32,
+    /// Window for the current chunk length (recomputed when it changes)
+    window: Vec<f32>,
+}
+
+impl SpectrumAnalyzer {
+    /// `band_count` is raised to at least one band
+    pub fn new(band_count: usize, curve: SpectrumCurve, sample_rate: f32) -> Self {
+        Self { planner: FftPlanner::new(), band_count: band_count.max(1), curve, sample_rate, window: Vec::new() }
+    }
+
+    /// Bar heights (0.0-1.0) for one chunk, always `band_count` long
+    pub fn bands(&mut self, samples: &[f32]) -> Vec<f32> {
+        let len = samples.len();
+        if len < 2 {
+            return vec![0.0; self.band_count];
+        }
+        if self.window.len() != len {
+            self.window = hann_window(len);
+        }
+
+        let mut spectrum: Vec<Complex<f32>> =
+            samples.iter().zip(&self.window).map(|(&s, &w)| Complex::new(s * w, 0.0)).collect();
+        self.planner.plan_fft_forward(len).process(&mut spectrum);
+
+        // Scale so each band's value is the RMS of the signal content in it
+        // (Parseval over the positive half, corrected for the window's energy),
+        // keeping `spectrum_gain` calibrated in RMS like the rest of the daemon
+        let window_energy: f32 = self.window.iter().map(|w| w * w).sum();
+        let scale = 2.0 / (len as f32 * window_energy);
+
+        band_bins(self.band_count, self.sample_rate, len)
+            .into_iter()
+            .map(|(lo, hi)| {
+                let power: f32 = spectrum[lo..hi].iter().map(|c| c.norm_sqr()).sum();
+                self.curve.apply((power * scale).sqrt())
+            })
+            .collect()
+    }
+}
+
+fn hann_window(len: usize) -> Vec<f32> {
+    (0..len).map(|i| 0.5 - 0.5 * (2.0 * PI * i as f32 / (len - 1) as f32).cos()).collect()
+}
+
+/// FFT bin range (start, end exclusive) of each band, logarithmically spaced
+/// between `MIN_BAND_HZ` and Nyquist. The DC bin is skipped; low bands
+/// narrower than one bin are widened to the nearest bin.
+fn band_bins(band_count: usize, sample_rate: f32, fft_len: usize) -> Vec<(usize, usize)> {
+    let nyquist = sample_rate / 2.0;
+    let bin_hz = sample_rate / fft_len as f32;
+    let last_bin = fft_len / 2;
+    let min_hz = MIN_BAND_HZ.min(nyquist / 2.0);
+    let edge_bin = |i: usize| {
+        let hz = min_hz * (nyquist / min_hz).powf(i as f32 / band_count as f32);
+        ((hz / bin_hz).round() as usize).clamp(1, last_bin)
+    };
+
+    (0..band_count)
+        .map(|i| {
+            let lo = edge_bin(i);
+            let hi = if i + 1 == band_count { last_bin + 1 } else { edge_bin(i + 1) };
+            (lo, hi.clamp(lo + 1, last_bin + 1))
+        })
+        .collect()
+}
+
+/// Blend `incoming` into `smoothed`: `factor * old + (1 - factor) * new`.
+///
+/// A factor of 0 takes the new values as-is; closer to 1 moves more slowly.
+/// `smoothed` takes the length of `incoming`.
+pub fn smooth(smoothed: &mut Vec<f32>, incoming: &[f32], factor: f32) {
+    smoothed.resize(incoming.len(), 0.0);
+    for (old, &new) in smoothed.iter_mut().zip(incoming) {
+        *old = factor * *old + (1.0 - factor) * new;
+    }
+}
+
+/// One smoothing step toward silence, used when no audio is arriving
+pub fn decay(smoothed: &mut [f32], factor: f32) {
+    for value in smoothed.iter_mut() {
+        *value *= factor;
+    }
+}
+
+/// Held peak per band
+#[derive(Debug, Clone, PartialEq)]
+pub struct PeakHold {
+    peaks: Vec<f32>,
+}
+
+impl Default for PeakHold {
+    fn default() -> Self {
+        Self::new(DEFAULT_BAND_COUNT)
+    }
+}
+
+impl PeakHold {
+    pub fn new(band_count: usize) -> Self {
+        Self { peaks: vec![0.0; band_count] }
+    }
+
+    /// Let each peak fall by `decay_per_sec` over `elapsed`, then raise it to
+    /// the current band value if that is higher
+    pub fn update(&mut self, bands: &[f32], elapsed: Duration, decay_per_sec: f32) {
+        let fall = decay_per_sec * elapsed.as_secs_f32();
+        for (i, peak) in self.peaks.iter_mut().enumerate() {
+            let band = bands.get(i).copied().unwrap_or(0.0);
+            *peak = (*peak - fall).max(band).max(0.0);
+        }
+    }
+
+    pub fn peaks(&self) -> &[f32] {
+        &self.peaks
+    }
+
+    pub fn reset(&mut self) {
+        self.peaks.fill(0.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_is_linear_gain() {
+        let curve = SpectrumCurve::default();
+        assert!((curve.apply(0.02) - 0.3).abs() < 1e-6);
+        assert_eq!(curve.apply(0.5), 1.0);
+        assert_eq!(curve.apply(0.0), 0.0);
+    }
+
+    #[test]
+    fn test_gamma_below_one_lifts_quiet_input() {
+        let linear = SpectrumCurve::default();
+        let lifted = SpectrumCurve { gamma: 0.5, ..linear };
+        assert!(lifted.apply(0.01) > linear.apply(0.01));
+        // Full scale is unchanged
+        assert_eq!(lifted.apply(1.0), 1.0);
+    }
+
+    #[test]
+    fn test_gamma_above_one_compresses_quiet_input() {
+        let linear = SpectrumCurve::default();
+        let compressed = SpectrumCurve { gamma: 2.0, ..linear };
+        assert!(compressed.apply(0.03) < linear.apply(0.03));
+    }
+
+    fn sine(hz: f32, amplitude: f32) -> Vec<f32> {
+        (0..512).map(|i| amplitude * (2.0 * PI * hz * i as f32 / SPECTRUM_SAMPLE_RATE).sin()).collect()
+    }
+
+    fn loudest(bands: &[f32]) -> usize {
+        (0..bands.len()).max_by(|&a, &b| bands[a].total_cmp(&bands[b])).unwrap()
+    }
+
+    #[test]
+    fn test_sine_lands_in_its_band() {
+        let curve = SpectrumCurve { gain: 1.0, gamma: 1.0 };
+        let mut analyzer = SpectrumAnalyzer::new(8, curve, SPECTRUM_SAMPLE_RATE);
+
+        // 8 bands from 80 Hz: edges at 80, 142, 253, 450, 800, 1423, 2530, 4499, 8000
+        let bands = analyzer.bands(&sine(1000.0, 0.5));
+        assert_eq!(loudest(&bands), 4);
+        // Band value is the tone's RMS
+        assert!((bands[4] - 0.5 / 2f32.sqrt()).abs() < 0.02, "{:?}", bands);
+        assert!(bands.iter().enumerate().all(|(i, &b)| i == 4 || b < 0.02), "{:?}", bands);
+
+        assert_eq!(loudest(&analyzer.bands(&sine(200.0, 0.5))), 1);
+        assert_eq!(loudest(&analyzer.bands(&sine(5000.0, 0.5))), 7);
+    }
+
+    #[test]
+    fn test_band_count() {
+        let curve = SpectrumCurve::default();
+        let mut analyzer = SpectrumAnalyzer::new(16, curve, SPECTRUM_SAMPLE_RATE);
+        assert_eq!(analyzer.bands(&sine(440.0, 0.1)).len(), 16);
+        assert_eq!(analyzer.bands(&[]), [0.0; 16]);
+        assert_eq!(analyzer.bands(&[0.0; 512]), [0.0; 16]);
+
+        // Zero bands still produces one bar
+        let mut analyzer = SpectrumAnalyzer::new(0, curve, SPECTRUM_SAMPLE_RATE);
+        assert_eq!(analyzer.bands(&sine(440.0, 0.1)).len(), 1);
+    }
+
+    #[test]
+    fn test_band_bins_cover_spectrum() {
+        let bins = band_bins(24, SPECTRUM_SAMPLE_RATE, 512);
+        assert_eq!(bins.len(), 24);
+        assert!(bins.iter().all(|&(lo, hi)| lo >= 1 && hi > lo && hi <= 257));
+        assert_eq!(bins.last().unwrap().1, 257);
+    }
+
+    #[test]
+    fn test_smoothing_blends_frames() {
+        let mut values = vec![0.0, 1.0];
+        smooth(&mut values, &[1.0, 0.0], 0.75);
+        assert_eq!(values, [0.25, 0.75]);
+
+        // No smoothing replaces the values, including a new band count
+        smooth(&mut values, &[0.5, 0.5, 0.5], 0.0);
+        assert_eq!(values, [0.5, 0.5, 0.5]);
+
+        decay(&mut values, 0.5);
+        assert_eq!(values, [0.25, 0.25, 0.25]);
+        decay(&mut values, 0.0);
+        assert_eq!(values, [0.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_peak_jumps_up_and_decays() {
+        let mut hold = PeakHold::default();
+        hold.update(&[0.8, 0.2], Duration::ZERO, 0.5);
+        assert_eq!(hold.peaks()[..3], [0.8, 0.2, 0.0]);
+
+        // Peaks fall at the decay rate while the bands are lower
+        hold.update(&[0.1, 0.0], Duration::from_millis(200), 0.5);
+        assert!((hold.peaks()[0] - 0.7).abs() < 1e-6);
+        assert!((hold.peaks()[1] - 0.1).abs() < 1e-6);
+
+        // A louder band takes over, and peaks never go below zero
+        hold.update(&[0.9, 0.0], Duration::from_secs(2), 0.5);
+        assert_eq!(hold.peaks()[..2], [0.9, 0.0]);
+    }
+}
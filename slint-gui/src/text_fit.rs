@@ -0,0 +1,187 @@
+//! Fitting live transcription text into the fixed-size overlay.
+//!
+//! The overlay shows a single unwrapped line, so long dictation would be
+//! clipped on both sides. Instead the tail (the words just spoken) is kept and
+//! the rest replaced by an ellipsis. All measurements are in display columns
+//! over `char`s, never bytes: accented letters count once, CJK and other wide
+//! characters count twice, and cuts always land on a character boundary.
+
+/// Approximate width of one column of 16px overlay text (logical pixels)
+const COLUMN_WIDTH_PX: f32 = 9.0;
+
+/// Lines of text the overlay has room for below the spectrum
+pub const OVERLAY_TEXT_LINES: usize = 1;
+
+const ELLIPSIS: char = '…';
+
+/// Display columns taken by `c` (2 for wide East Asian characters, 0 for combining marks)
+fn char_width(c: char) -> usize {
+    match c as u32 {
+        0x0300..=0x036F | 0x200B..=0x200F | 0xFE00..=0xFE0F => 0,
+        0x1100..=0x115F
+        | 0x2E80..=0x303E
+        | 0x3041..=0x33FF
+        | 0x3400..=0x4DBF
+        | 0x4E00..=0x9FFF
+        | 0xA000..=0xA4CF
+        | 0xAC00..=0xD7A3
+        | 0xF900..=0xFAFF
+        | 0xFE30..=0xFE4F
+        | 0xFF00..=0xFF60
+        | 0xFFE0..=0xFFE6
+        | 0x1F300..=0x1F64F
+        | 0x1F900..=0x1F9FF
+        | 0x20000..=0x3FFFD => 2,
+        _ => 1,
+    }
+}
+
+/// Columns of text that fit in `width_px` logical pixels
+pub fn columns_for_width(width_px: f32) -> usize {
+    ((width_px / COLUMN_WIDTH_PX) as usize).max(1)
+}
+
+/// Display width of `text` in columns
+pub fn display_width(text: &str) -> usize {
+    text.chars().map(char_width).sum()
+}
+
+/// Estimated number of lines `text` takes when word-wrapped at `columns`.
+///
+/// Words wider than a line are broken per character, as CJK text without
+/// spaces would be.
+pub fn estimate_lines(text: &str, columns: usize) -> usize {
+    let columns = columns.max(1);
+    let mut lines = 0;
+    let mut used = 0;
+
+    for word in text.split_whitespace() {
+        let width = display_width(word);
+        let needed = if used == 0 { width } else { used + 1 + width };
+        if needed <= columns {
+            used = needed;
+            continue;
+        }
+
+        if used > 0 {
+            lines += 1;
+            used = 0;
+        }
+        for c in word.chars() {
+            let w = char_width(c);
+            if used + w > columns && used > 0 {
+                lines += 1;
+                used = 0;
+            }
+            used += w;
+        }
+    }
+
+    if used > 0 {
+        lines += 1;
+    }
+    lines
+}
+
+/// Keep the end of `text` so it fits in `lines` lines of `columns`, prefixing
+/// an ellipsis when anything was dropped.
+pub fn fit_tail(text: &str, columns: usize, lines: usize) -> String {
+    let text = text.trim();
+    let budget = columns.max(1) * lines.max(1);
+    if display_width(text) <= budget && estimate_lines(text, columns) <= lines.max(1) {
+        return text.to_string();
+    }
+
+    // Walk back from the end on char boundaries, leaving a column for the ellipsis
+    let mut used = char_width(ELLIPSIS);
+    let mut start = text.len();
+    for (i, c) in text.char_indices().rev() {
+        let w = char_width(c);
+        if used + w > budget {
+            break;
+        }
+        used += w;
+        start = i;
+    }
+
+    let tail = &text[start..];
+    // Prefer starting at a word when the cut landed mid-word and there's a later space
+    let tail = match tail.find(' ') {
+        Some(space) if start > 0 && !text[..start].ends_with(' ') && space + 1 < tail.len() => &tail[space + 1..],
+        _ => tail,
+    };
+
+    format!("{}{}", ELLIPSIS, tail.trim_start())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_width_counts_chars_not_bytes() {
+        assert_eq!(display_width("café résumé"), 11);
+        assert_eq!(display_width("日本語"), 6);
+        assert_eq!(display_width("e\u{0301}"), 1);
+    }
+
+    #[test]
+    fn test_columns_for_width() {
+        assert_eq!(columns_for_width(348.0), 38);
+        assert_eq!(columns_for_width(180.0), 20);
+        assert_eq!(columns_for_width(0.0), 1);
+    }
+
+    #[test]
+    fn test_estimate_lines_ascii() {
+        assert_eq!(estimate_lines("", 10), 0);
+        assert_eq!(estimate_lines("hello world", 11), 1);
+        assert_eq!(estimate_lines("hello world", 10), 2);
+    }
+
+    #[test]
+    fn test_estimate_lines_accented_matches_ascii() {
+        // 11 chars but 15 bytes: byte counting would wrap this
+        assert_eq!(estimate_lines("crème brûlée", 12), 1);
+    }
+
+    #[test]
+    fn test_estimate_lines_cjk_breaks_per_character() {
+        // 10 wide chars = 20 columns
+        assert_eq!(estimate_lines("今日はいい天気ですね。", 10), 3);
+        assert_eq!(estimate_lines("今日はいい天気ですね。", 40), 1);
+    }
+
+    #[test]
+    fn test_short_text_unchanged() {
+        assert_eq!(fit_tail("  hello world ", 38, 1), "hello world");
+    }
+
+    #[test]
+    fn test_fit_keeps_tail_on_word_boundary() {
+        let fitted = fit_tail("the quick brown fox jumps over the lazy dog", 20, 1);
+        assert_eq!(fitted, "…over the lazy dog");
+        assert!(display_width(&fitted) <= 20);
+    }
+
+    #[test]
+    fn test_fit_never_splits_multibyte_chars() {
+        let texts = [
+            "él está aquí y después se fue a casa de María con Ángel",
+            "日本語のテキストはスペースがないのでそのまま切り詰められます",
+            "emoji 🎤🎤🎤🎤🎤🎤🎤🎤🎤🎤🎤🎤🎤🎤🎤🎤🎤🎤🎤🎤 end",
+        ];
+        for text in texts {
+            for columns in 1..40 {
+                let fitted = fit_tail(text, columns, 1);
+                assert!(display_width(&fitted) <= columns.max(2), "{:?} at {}", fitted, columns);
+                assert!(fitted.starts_with(ELLIPSIS));
+            }
+        }
+    }
+
+    #[test]
+    fn test_fit_cjk_tail() {
+        assert_eq!(fit_tail("今日はいい天気ですね", 11, 1), "…天気ですね");
+    }
+}
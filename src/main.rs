@@ -1,43 +1,99 @@
 use clap::{Parser, Subcommand};
 use std::fs;
+use std::future::Future;
 use std::io::{self, Write as IoWrite};
 use std::path::PathBuf;
 use std::process::Command;
+use std::sync::OnceLock;
 use std::thread;
 use std::time::Duration;
 use serde_json::Value;
 use schema_tui::SchemaTUIBuilder;
 use zbus::Connection;
 
+mod model_download;
 mod utils;
 
 const STATE_FILE: &str = "/tmp/voice-dictation-state";
+/// Held while one CLI invocation spawns the daemon, so concurrent ones wait instead
+const AUTOSTART_LOCK_FILE: &str = "/tmp/voice-dictation-autostart.lock";
+/// How long to wait for a spawned daemon to register on D-Bus
+const AUTOSTART_WAIT: Duration = Duration::from_secs(10);
+/// PID of the `start-in` process waiting to start a recording
+const SCHEDULE_PID_FILE: &str = "/tmp/voice-dictation-scheduled.pid";
 const DBUS_SERVICE_NAME: &str = "com.voicedictation.Daemon";
 const DBUS_OBJECT_PATH: &str = "/com/voicedictation/Control";
 const DBUS_INTERFACE_NAME: &str = "com.voicedictation.Control";
+const DEFAULT_DBUS_TIMEOUT_SECS: u64 = 5;
+
+/// Reply timeout for D-Bus calls to the daemon, set from --timeout
+static DBUS_TIMEOUT: OnceLock<Duration> = OnceLock::new();
 
 #[derive(Parser)]
 #[command(name = "voice-dictation")]
 #[command(about = "Voice dictation system with Parakeet speech recognition", long_about = None)]
 struct Cli {
+    /// Increase log verbosity (-v = debug, -vv = trace). RUST_LOG still takes precedence.
+    #[arg(short, long, action = clap::ArgAction::Count, global = true)]
+    verbose: u8,
+
+    /// Only log warnings and errors
+    #[arg(short, long, global = true, conflicts_with = "verbose")]
+    quiet: bool,
+
+    /// Seconds to wait for the daemon to answer before giving up
+    #[arg(long, global = true, default_value_t = DEFAULT_DBUS_TIMEOUT_SECS, value_parser = clap::value_parser!(u64).range(1..))]
+    timeout: u64,
+
+    /// Spawn the daemon in the background if it isn't running (start/toggle/start-in)
+    #[arg(long, global = true)]
+    autostart: bool,
+
     #[command(subcommand)]
     command: Commands,
 }
 
+impl Cli {
+    /// Tracing level requested via -v/-q, or None to use the default
+    fn log_level(&self) -> Option<tracing::Level> {
+        if self.quiet {
+            return Some(tracing::Level::WARN);
+        }
+        match self.verbose {
+            0 => None,
+            1 => Some(tracing::Level::DEBUG),
+            _ => Some(tracing::Level::TRACE),
+        }
+    }
+}
+
 #[derive(Subcommand)]
 enum Commands {
     #[command(about = "Start the dictation engine daemon")]
     Daemon,
     #[command(about = "Start recording session")]
     Start,
-    #[command(about = "Stop recording session")]
+    #[command(about = "Start recording after a delay (cancel with `voice-dictation cancel`)")]
+    StartIn {
+        #[arg(help = "Seconds to wait before starting")]
+        seconds: u64,
+        #[arg(long, value_name = "SECONDS", value_parser = clap::value_parser!(u64).range(1..), help = "Keep starting a recording every SECONDS after the first")]
+        every: Option<u64>,
+    },
+    #[command(about = "Stop recording session (cancels unless stop_behavior = \"confirm\")")]
     Stop,
+    #[command(about = "Cancel recording session, discarding the transcription")]
+    Cancel,
     #[command(about = "Confirm and finalize transcription")]
     Confirm,
     #[command(about = "Toggle recording (start if stopped, confirm if recording)")]
     Toggle,
+    #[command(about = "Re-type the last transcription into the focused window")]
+    Repeat,
     #[command(about = "Show current status")]
     Status,
+    #[command(about = "Clear the recording state file (e.g. stuck after a crash)")]
+    Reset,
     #[command(about = "Open configuration TUI")]
     Config,
     #[command(about = "List available models")]
@@ -52,17 +108,36 @@ enum Commands {
         #[arg(default_value = "en")]
         language: String,
     },
-    #[command(about = "List available audio input devices")]
-    ListAudioDevices,
+    #[command(about = "List audio input devices, with names to use for audio_device in config.toml")]
+    ListAudioDevices {
+        #[arg(long, default_value = "auto", help = "Audio backend to enumerate (auto, cpal, pipewire)")]
+        backend: String,
+    },
     #[command(about = "Debug recording tools (requires VOICE_DICTATION_DEBUG_AUDIO=1)")]
     Debug {
         #[command(subcommand)]
         command: DebugCommands,
     },
     #[command(about = "Show audio backend diagnostics and configuration")]
-    Diagnose,
+    Diagnose {
+        #[arg(long, help = "Write a bug-report bundle of the last session instead (audio, raw and processed text, device)")]
+        bundle: bool,
+    },
+    #[command(about = "Run the overlay standalone, cycling through all states with sample data")]
+    GuiPreview {
+        #[arg(long, help = "Sample transcription text to display (tests sizing and wrapping)")]
+        text: Option<String>,
+    },
+    #[command(about = "Transcribe a WAV file with the configured model and post-processing, printing raw and processed text")]
+    Transcribe {
+        #[arg(help = "WAV file (16-bit PCM or 32-bit float, any rate and channel count)")]
+        file: PathBuf,
+    },
     #[command(about = "Download Parakeet speech recognition model from HuggingFace")]
-    DownloadModel,
+    DownloadModel {
+        #[arg(long, help = "Download every file again, replacing a corrupted model")]
+        force: bool,
+    },
 }
 
 #[derive(Subcommand)]
@@ -84,6 +159,81 @@ fn set_state(state: &str) -> std::io::Result<()> {
     fs::write(STATE_FILE, state)
 }
 
+/// State-file value matching the daemon's reported state
+fn state_for_daemon(daemon_state: &str) -> &'static str {
+    if daemon_state == "recording" {
+        "recording"
+    } else {
+        "stopped"
+    }
+}
+
+/// Recording state, checked against the running daemon.
+///
+/// The state file survives crashes, so when it disagrees with the daemon it is
+/// corrected. Falls back to the file when the daemon can't be asked.
+fn sync_state() -> String {
+    match get_daemon_state() {
+        Ok(daemon_state) => correct_state_file(&daemon_state).to_string(),
+        Err(_) => get_state(),
+    }
+}
+
+/// Bring the state file in line with `daemon_state`, returning the file state
+fn correct_state_file(daemon_state: &str) -> &'static str {
+    let file_state = get_state();
+    let actual = state_for_daemon(daemon_state);
+    if file_state != actual {
+        eprintln!("State file said '{}' but daemon is {}, correcting", file_state, daemon_state);
+        if let Err(e) = set_state(actual) {
+            eprintln!("Failed to update {}: {}", STATE_FILE, e);
+        }
+    }
+    actual
+}
+
+/// The daemon didn't answer a D-Bus call in time (e.g. wedged while processing)
+#[derive(Debug)]
+struct DaemonNotResponding(Duration);
+
+impl std::fmt::Display for DaemonNotResponding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Daemon not responding (no reply within {}s)", self.0.as_secs())
+    }
+}
+
+impl std::error::Error for DaemonNotResponding {}
+
+fn dbus_timeout() -> Duration {
+    DBUS_TIMEOUT.get().copied().unwrap_or(Duration::from_secs(DEFAULT_DBUS_TIMEOUT_SECS))
+}
+
+/// Bound a D-Bus exchange with the daemon by the --timeout
+async fn with_timeout<T>(
+    call: impl Future<Output = Result<T, Box<dyn std::error::Error>>>,
+) -> Result<T, Box<dyn std::error::Error>> {
+    let timeout = dbus_timeout();
+    match tokio::time::timeout(timeout, call).await {
+        Ok(result) => result,
+        Err(_) => Err(Box::new(DaemonNotResponding(timeout))),
+    }
+}
+
+/// Like `with_timeout`, trying once more after a timeout.
+///
+/// Only for read-only queries: a control method that timed out may still have
+/// been acted on, so repeating it could e.g. toggle twice.
+async fn query_with_retry<T, F, Fut>(query: F) -> Result<T, Box<dyn std::error::Error>>
+where
+    F: Fn() -> Fut,
+    Fut: Future<Output = Result<T, Box<dyn std::error::Error>>>,
+{
+    match with_timeout(query()).await {
+        Err(e) if e.is::<DaemonNotResponding>() => with_timeout(query()).await,
+        result => result,
+    }
+}
+
 async fn call_dbus_method(method: &str) -> Result<(), Box<dyn std::error::Error>> {
     let connection = Connection::session().await?;
     let proxy = zbus::Proxy::new(
@@ -98,20 +248,39 @@ async fn call_dbus_method(method: &str) -> Result<(), Box<dyn std::error::Error>
 }
 
 fn send_start_recording() -> Result<(), Box<dyn std::error::Error>> {
-    tokio::runtime::Runtime::new()?.block_on(call_dbus_method("StartRecording"))
-        .map_err(dbus_error_with_hint)
+    tokio::runtime::Runtime::new()?.block_on(with_timeout(call_dbus_method("StartRecording")))
+        .map_err(|e| match e.downcast_ref::<zbus::Error>() {
+            // The daemon refused (e.g. the focused app is blocklisted): its message says why
+            Some(zbus::Error::MethodError(_, Some(msg), _)) => msg.clone().into(),
+            _ => dbus_error_with_hint(e),
+        })
 }
 
 fn send_stop_recording() -> Result<(), Box<dyn std::error::Error>> {
-    tokio::runtime::Runtime::new()?.block_on(call_dbus_method("StopRecording"))
+    tokio::runtime::Runtime::new()?.block_on(with_timeout(call_dbus_method("StopRecording")))
         .map_err(dbus_error_with_hint)
 }
 
-fn send_confirm() -> Result<(), Box<dyn std::error::Error>> {
-    tokio::runtime::Runtime::new()?.block_on(call_dbus_method("Confirm"))
+fn send_cancel() -> Result<(), Box<dyn std::error::Error>> {
+    tokio::runtime::Runtime::new()?.block_on(with_timeout(call_dbus_method("Cancel")))
         .map_err(dbus_error_with_hint)
 }
 
+fn send_confirm() -> Result<(), Box<dyn std::error::Error>> {
+    tokio::runtime::Runtime::new()?.block_on(with_timeout(call_dbus_method("Confirm")))
+        .map_err(|e| match e.downcast_ref::<zbus::Error>() {
+            // The daemon refused (e.g. nothing to confirm): its message says why
+            Some(zbus::Error::MethodError(_, Some(msg), _)) => msg.clone().into(),
+            _ => dbus_error_with_hint(e),
+        })
+}
+
+fn send_repeat_last() -> Result<(), Box<dyn std::error::Error>> {
+    // The daemon answers with a descriptive error when there's nothing to repeat,
+    // so pass it through without the connectivity hint
+    tokio::runtime::Runtime::new()?.block_on(with_timeout(call_dbus_method("RepeatLast")))
+}
+
 fn dbus_error_with_hint(e: Box<dyn std::error::Error>) -> Box<dyn std::error::Error> {
     format!(
         "Failed to communicate with daemon: {}\nTry: systemctl --user status voice-dictation",
@@ -133,27 +302,73 @@ async fn call_health_check() -> Result<(String, String, String), Box<dyn std::er
 }
 
 fn get_health_check() -> Result<(String, String, String), Box<dyn std::error::Error>> {
-    tokio::runtime::Runtime::new()?.block_on(call_health_check())
+    tokio::runtime::Runtime::new()?.block_on(query_with_retry(call_health_check))
+}
+
+async fn call_session_id() -> Result<String, Box<dyn std::error::Error>> {
+    let connection = Connection::session().await?;
+    let proxy = zbus::Proxy::new(
+        &connection,
+        DBUS_SERVICE_NAME,
+        DBUS_OBJECT_PATH,
+        DBUS_INTERFACE_NAME,
+    ).await?;
+
+    let id: String = proxy.call("SessionId", &()).await?;
+    Ok(id)
+}
+
+async fn call_get_state() -> Result<String, Box<dyn std::error::Error>> {
+    let connection = Connection::session().await?;
+    let proxy = zbus::Proxy::new(
+        &connection,
+        DBUS_SERVICE_NAME,
+        DBUS_OBJECT_PATH,
+        DBUS_INTERFACE_NAME,
+    ).await?;
+
+    let state: String = proxy.call("GetState", &()).await?;
+    Ok(state)
+}
+
+/// Daemon state ("idle", "recording" or "processing")
+fn get_daemon_state() -> Result<String, Box<dyn std::error::Error>> {
+    tokio::runtime::Runtime::new()?.block_on(query_with_retry(call_get_state))
+}
+
+fn get_session_id() -> Result<String, Box<dyn std::error::Error>> {
+    tokio::runtime::Runtime::new()?.block_on(query_with_retry(call_session_id))
+}
+
+async fn call_dump_diagnostics() -> Result<String, Box<dyn std::error::Error>> {
+    let connection = Connection::session().await?;
+    let proxy = zbus::Proxy::new(
+        &connection,
+        DBUS_SERVICE_NAME,
+        DBUS_OBJECT_PATH,
+        DBUS_INTERFACE_NAME,
+    ).await?;
+
+    let dir: String = proxy.call("DumpDiagnostics", &()).await?;
+    Ok(dir)
+}
+
+async fn call_introspect() -> Result<(), Box<dyn std::error::Error>> {
+    let connection = Connection::session().await?;
+    let proxy = zbus::Proxy::new(
+        &connection,
+        DBUS_SERVICE_NAME,
+        DBUS_OBJECT_PATH,
+        DBUS_INTERFACE_NAME,
+    ).await?;
+
+    proxy.introspect().await?;
+    Ok(())
 }
 
 fn is_daemon_running() -> bool {
     if let Ok(rt) = tokio::runtime::Runtime::new() {
-        rt.block_on(async {
-            if let Ok(conn) = Connection::session().await {
-                if let Ok(proxy) = zbus::Proxy::new(
-                    &conn,
-                    DBUS_SERVICE_NAME,
-                    DBUS_OBJECT_PATH,
-                    DBUS_INTERFACE_NAME,
-                ).await {
-                    proxy.introspect().await.is_ok()
-                } else {
-                    false
-                }
-            } else {
-                false
-            }
-        })
+        rt.block_on(query_with_retry(call_introspect)).is_ok()
     } else {
         false
     }
@@ -212,7 +427,65 @@ fn check_runtime_dependencies(require_wtype: bool, require_wayland: bool) -> Res
     Ok(())
 }
 
-fn start_recording() -> Result<(), Box<dyn std::error::Error>> {
+/// Spawn `voice-dictation daemon` detached and wait for it to register on D-Bus.
+///
+/// Only the invocation that creates the lock file spawns; concurrent ones just
+/// wait for the service. A lock older than the wait is left from a crashed
+/// attempt and is taken over.
+fn autostart_daemon() -> Result<(), Box<dyn std::error::Error>> {
+    use std::os::unix::process::CommandExt;
+    use std::process::Stdio;
+
+    let stale = fs::metadata(AUTOSTART_LOCK_FILE)
+        .and_then(|m| m.modified())
+        .is_ok_and(|modified| modified.elapsed().unwrap_or_default() > AUTOSTART_WAIT);
+    if stale {
+        let _ = fs::remove_file(AUTOSTART_LOCK_FILE);
+    }
+
+    let spawner = fs::OpenOptions::new().write(true).create_new(true).open(AUTOSTART_LOCK_FILE).is_ok();
+    if spawner {
+        println!("Daemon not running, starting it...");
+        let spawned = Command::new(std::env::current_exe()?)
+            .arg("daemon")
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            // Own process group so it outlives the terminal or keybind that ran us
+            .process_group(0)
+            .spawn();
+        if let Err(e) = spawned {
+            let _ = fs::remove_file(AUTOSTART_LOCK_FILE);
+            return Err(format!("Failed to start daemon: {}", e).into());
+        }
+    } else {
+        println!("Daemon is being started by another invocation, waiting...");
+    }
+
+    let deadline = std::time::Instant::now() + AUTOSTART_WAIT;
+    let mut running = is_daemon_running();
+    while !running && std::time::Instant::now() < deadline {
+        thread::sleep(Duration::from_millis(200));
+        running = is_daemon_running();
+    }
+
+    if spawner {
+        let _ = fs::remove_file(AUTOSTART_LOCK_FILE);
+    }
+    if !running {
+        return Err(format!(
+            "Daemon did not come up within {}s\nTry running it manually: voice-dictation daemon",
+            AUTOSTART_WAIT.as_secs()
+        ).into());
+    }
+    Ok(())
+}
+
+fn start_recording(autostart: bool) -> Result<(), Box<dyn std::error::Error>> {
+    if autostart && !is_daemon_running() {
+        autostart_daemon()?;
+    }
+
     if !is_daemon_running() {
         eprintln!("Error: Daemon not running");
         eprintln!("Start the daemon with: systemctl --user start voice-dictation");
@@ -220,12 +493,13 @@ fn start_recording() -> Result<(), Box<dyn std::error::Error>> {
         return Err("Daemon not running".into());
     }
 
-    let state = get_state();
+    let state = sync_state();
     if state == "recording" {
         println!("Already recording");
         return Ok(());
     }
 
+    // Errors when the daemon refuses (e.g. blocklisted app), leaving the state file alone
     send_start_recording()?;
 
     set_state("recording")?;
@@ -234,6 +508,58 @@ fn start_recording() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+/// Wait `seconds`, then start recording; with `every`, repeat until canceled.
+///
+/// Runs in the foreground and records its PID so `cancel` can abort it.
+fn start_in(seconds: u64, every: Option<u64>, autostart: bool) -> Result<(), Box<dyn std::error::Error>> {
+    if cancel_scheduled_start() {
+        println!("Replaced the previously scheduled start");
+    }
+    let pid = std::process::id().to_string();
+    fs::write(SCHEDULE_PID_FILE, &pid)?;
+
+    let mut delay = seconds;
+    loop {
+        println!("Recording starts in {}s (voice-dictation cancel to abort)", delay);
+        thread::sleep(Duration::from_secs(delay));
+
+        let result = start_recording(autostart);
+        match every {
+            Some(interval) => {
+                if let Err(e) = result {
+                    eprintln!("Scheduled start failed: {}", e);
+                }
+                delay = interval;
+            }
+            None => {
+                if fs::read_to_string(SCHEDULE_PID_FILE).is_ok_and(|p| p.trim() == pid) {
+                    let _ = fs::remove_file(SCHEDULE_PID_FILE);
+                }
+                return result;
+            }
+        }
+    }
+}
+
+/// Kill a waiting `start-in` process, returning whether one was pending
+fn cancel_scheduled_start() -> bool {
+    let Ok(contents) = fs::read_to_string(SCHEDULE_PID_FILE) else {
+        return false;
+    };
+    let _ = fs::remove_file(SCHEDULE_PID_FILE);
+
+    let Ok(pid) = contents.trim().parse::<u32>() else {
+        return false;
+    };
+    // The PID may have been reused since the schedule ended without cleaning up
+    let is_scheduler = fs::read(format!("/proc/{}/cmdline", pid))
+        .is_ok_and(|cmdline| cmdline.split(|&b| b == 0).any(|arg| arg == b"start-in"));
+    if pid == std::process::id() || !is_scheduler {
+        return false;
+    }
+    Command::new("kill").arg(pid.to_string()).status().is_ok_and(|s| s.success())
+}
+
 fn stop_recording() -> Result<(), Box<dyn std::error::Error>> {
     let state = get_state();
     if state == "stopped" {
@@ -249,6 +575,30 @@ fn stop_recording() -> Result<(), Box<dyn std::error::Error>> {
 
     send_stop_recording()?;
 
+    set_state("stopped")?;
+    println!("Recording stopped");
+
+    Ok(())
+}
+
+fn cancel_recording() -> Result<(), Box<dyn std::error::Error>> {
+    if cancel_scheduled_start() {
+        println!("Scheduled start canceled");
+    }
+
+    if get_state() == "stopped" {
+        println!("Not recording");
+        return Ok(());
+    }
+
+    if !is_daemon_running() {
+        eprintln!("Daemon not running");
+        set_state("stopped")?;
+        return Ok(());
+    }
+
+    send_cancel()?;
+
     set_state("stopped")?;
     println!("Recording canceled");
 
@@ -256,12 +606,36 @@ fn stop_recording() -> Result<(), Box<dyn std::error::Error>> {
 }
 
 fn confirm_recording() -> Result<(), Box<dyn std::error::Error>> {
-    let state = get_state();
+    if !is_daemon_running() {
+        eprintln!("Error: Daemon not running");
+        eprintln!("Start the daemon with: systemctl --user start voice-dictation");
+        eprintln!("Or run manually: voice-dictation daemon");
+        set_state("stopped")?;
+        return Err("Daemon not running".into());
+    }
+
+    let state = sync_state();
     if state != "recording" {
         eprintln!("Not in recording state (current: {})", state);
         return Err("Invalid state".into());
     }
 
+    println!("Confirming transcription...");
+    if let Err(e) = send_confirm() {
+        // The daemon may have finished or dropped the session on its own
+        sync_state();
+        return Err(e);
+    }
+
+    thread::sleep(Duration::from_millis(500));
+
+    set_state("stopped")?;
+    println!("Transcription confirmed");
+
+    Ok(())
+}
+
+fn repeat_last() -> Result<(), Box<dyn std::error::Error>> {
     if !is_daemon_running() {
         eprintln!("Error: Daemon not running");
         eprintln!("Start the daemon with: systemctl --user start voice-dictation");
@@ -269,22 +643,53 @@ fn confirm_recording() -> Result<(), Box<dyn std::error::Error>> {
         return Err("Daemon not running".into());
     }
 
-    println!("Confirming transcription...");
-    send_confirm()?;
+    if get_state() == "recording" {
+        eprintln!("Cannot repeat while recording");
+        return Err("Invalid state".into());
+    }
 
-    thread::sleep(Duration::from_millis(500));
+    send_repeat_last()?;
+    println!("Repeating last transcription");
 
-    set_state("stopped")?;
-    println!("Transcription confirmed");
+    Ok(())
+}
+
+fn dump_diagnostics() -> Result<(), Box<dyn std::error::Error>> {
+    if !is_daemon_running() {
+        eprintln!("Error: Daemon not running");
+        eprintln!("Start the daemon with: systemctl --user start voice-dictation");
+        return Err("Daemon not running".into());
+    }
+
+    let dir = tokio::runtime::Runtime::new()?.block_on(with_timeout(call_dump_diagnostics()))?;
+    println!("Diagnostics written to {}", dir);
+    println!("Attach the directory contents (audio.wav, report.json) to your bug report");
 
     Ok(())
 }
 
-fn toggle_recording() -> Result<(), Box<dyn std::error::Error>> {
-    let state = get_state();
+fn reset_state() -> Result<(), Box<dyn std::error::Error>> {
+    // A daemon that really is recording would keep the overlay up; cancel it too
+    if is_daemon_running() && get_daemon_state().is_ok_and(|s| s == "recording") {
+        send_cancel()?;
+        println!("Canceled the daemon's active recording");
+    }
+
+    match fs::remove_file(STATE_FILE) {
+        Ok(()) => println!("Cleared {}", STATE_FILE),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => println!("No state file to clear"),
+        Err(e) => return Err(e.into()),
+    }
+    println!("State reset to stopped");
+
+    Ok(())
+}
+
+fn toggle_recording(autostart: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let state = if is_daemon_running() { sync_state() } else { get_state() };
 
     match state.as_str() {
-        "stopped" => start_recording(),
+        "stopped" => start_recording(autostart),
         "recording" => confirm_recording(),
         _ => {
             eprintln!("Unknown state: {}", state);
@@ -298,8 +703,19 @@ fn show_status() {
     println!("Daemon: {}", if daemon_running { "running" } else { "NOT running" });
 
     if daemon_running {
-        let state = get_state();
-        println!("State: {}", state);
+        // The daemon's own state machine; the state file only when it can't be asked
+        match get_daemon_state() {
+            Ok(state) => {
+                correct_state_file(&state);
+                println!("State: {}", state);
+            }
+            Err(e) => println!("State: {} (from {}, daemon unreachable: {})", get_state(), STATE_FILE, e),
+        }
+        if let Ok(id) = get_session_id() {
+            if !id.is_empty() {
+                println!("Session: {}", id);
+            }
+        }
 
         match get_health_check() {
             Ok((gui, engine, audio)) => {
@@ -508,12 +924,12 @@ fn debug_list() -> Result<(), Box<dyn std::error::Error>> {
             if let Ok(meta) = serde_json::from_str::<Value>(&content) {
                 let duration_ms = meta["duration_ms"].as_u64().unwrap_or(0);
                 let device = meta["active_device"].as_str().unwrap_or("?");
-                let device_short = if device.len() > 6 { &device[..6] } else { device };
+                let device_short: String = device.chars().take(6).collect();
                 let text = meta["final_text"].as_str()
                     .or_else(|| meta["preview_text"].as_str())
                     .unwrap_or("(no text)");
-                let text_preview = if text.len() > 35 {
-                    format!("{}...", &text[..32])
+                let text_preview = if text.chars().count() > 35 {
+                    format!("{}...", text.chars().take(32).collect::<String>())
                 } else {
                     text.to_string()
                 };
@@ -624,59 +1040,77 @@ fn diagnose() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
-fn download_model() -> Result<(), Box<dyn std::error::Error>> {
-    let home = std::env::var("HOME")?;
-    let model_dir = PathBuf::from(&home).join(".config/voice-dictation/models/parakeet");
-
-    fs::create_dir_all(&model_dir)?;
+fn gui_preview(text: Option<String>) -> Result<(), Box<dyn std::error::Error>> {
+    // The GUI exits with this code when a UI file changes (systemd restarts the daemon);
+    // in preview mode we relaunch it ourselves
+    const EXIT_CODE_RELOAD: i32 = 64;
+    const CHILD_ENV: &str = "VOICE_DICTATION_GUI_PREVIEW_CHILD";
 
-    const BASE_URL: &str = "https://huggingface.co/istupakov/parakeet-tdt-0.6b-v3-onnx/resolve/main";
-    const FILES: &[&str] = &[
-        "encoder-model.onnx",
-        "encoder-model.onnx.data",
-        "decoder_joint-model.onnx",
-    ];
-
-    println!("Model directory: {}", model_dir.display());
-    println!("Source: {}", BASE_URL);
-    println!();
-
-    let client = reqwest::blocking::Client::builder()
-        .timeout(None)
-        .build()?;
-
-    for filename in FILES {
-        let dest = model_dir.join(filename);
+    if std::env::var_os(CHILD_ENV).is_some() {
+        let runtime = tokio::runtime::Runtime::new()?;
+        slint_gui::run_preview(runtime.handle().clone(), text, slint_gui::GuiConfig::default())?;
+        return Ok(());
+    }
 
-        if dest.exists() {
-            let size = fs::metadata(&dest)?.len();
-            if size > 0 {
-                println!("  {} — already exists ({:.1} MB), skipping", filename, size as f64 / 1_048_576.0);
-                continue;
-            }
+    println!("Previewing overlay states (edit ~/.config/voice-dictation/ui/*.slint to hot-reload, Ctrl+C to exit)");
+    let exe = std::env::current_exe()?;
+    loop {
+        let mut cmd = Command::new(&exe);
+        cmd.arg("gui-preview").env(CHILD_ENV, "1");
+        if let Some(ref t) = text {
+            cmd.arg("--text").arg(t);
         }
 
-        let url = format!("{}/{}", BASE_URL, filename);
-        print!("  Downloading {}... ", filename);
-        io::stdout().flush()?;
-
-        let response = client.get(&url).send()?;
-        if !response.status().is_success() {
-            eprintln!("HTTP {}", response.status());
-            return Err(format!("Failed to download {}: HTTP {}", filename, response.status()).into());
+        let status = cmd.status()?;
+        if status.code() == Some(EXIT_CODE_RELOAD) {
+            println!("UI file changed, reloading...");
+            continue;
         }
+        if !status.success() {
+            return Err(format!("GUI preview exited with {}", status).into());
+        }
+        return Ok(());
+    }
+}
 
-        let total = response.content_length();
-        let bytes = response.bytes()?;
-        let size = bytes.len();
+/// Print device names ready to paste into `audio_device`, one per line with
+/// the description alongside
+fn list_audio_devices(backend: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let backend_type = dictation_engine::audio_backend::BackendType::from_str(backend)
+        .ok_or_else(|| format!("Unknown backend '{}' (expected auto, cpal or pipewire)", backend))?;
+    let devices = utils::list_devices_for(backend_type)?;
+    if devices.is_empty() {
+        eprintln!("No input devices found");
+        return Ok(());
+    }
 
-        fs::write(&dest, &bytes)?;
+    let names: Vec<String> = devices.iter().map(|dev| utils::quote_device_name(&dev.name)).collect();
+    let width = names.iter().map(|name| name.chars().count()).max().unwrap_or(0);
+    for (dev, name) in devices.iter().zip(&names) {
+        let marker = if dev.is_default { " (default)" } else { "" };
+        println!("{:<width$}  {}{}", name, dev.description, marker, width = width);
+    }
+    Ok(())
+}
 
-        if let Some(t) = total {
-            println!("{:.1} MB", t as f64 / 1_048_576.0);
-        } else {
-            println!("{:.1} MB", size as f64 / 1_048_576.0);
+fn download_model(force: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let home = std::env::var("HOME")?;
+    let model_dir = PathBuf::from(&home).join(".config/voice-dictation/models/parakeet");
+
+    if let Err(e) = model_download::download_model(&model_dir, force) {
+        eprintln!();
+        match &e {
+            model_download::DownloadError::Network { .. }
+            | model_download::DownloadError::Incomplete { .. }
+            | model_download::DownloadError::Corrupt { .. } => {
+                eprintln!("The download kept failing. Check your connection and run `voice-dictation download-model` again;");
+                eprintln!("files that finished are kept.");
+            }
+            model_download::DownloadError::Filesystem { .. } => {
+                eprintln!("Couldn't save the model. Check free space and permissions for {}", model_dir.display());
+            }
         }
+        return Err(e.into());
     }
 
     println!();
@@ -686,32 +1120,58 @@ fn download_model() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+fn transcribe_file(file: &std::path::Path) -> Result<(), Box<dyn std::error::Error>> {
+    eprintln!("Transcribing {}...", file.display());
+    let transcript = dictation_engine::file_transcribe::transcribe_wav(file)?;
+
+    println!("Raw:       {}", transcript.raw);
+    println!("Processed: {}", transcript.processed);
+
+    Ok(())
+}
+
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let cli = Cli::parse();
+    let log_level = cli.log_level();
+    let _ = DBUS_TIMEOUT.set(Duration::from_secs(cli.timeout));
 
     match cli.command {
         Commands::Daemon => {
             check_runtime_dependencies(true, true)?;
-            dictation_engine::run()?;
+            dictation_engine::run(log_level)?;
         }
         Commands::Start => {
             check_runtime_dependencies(true, false)?;
-            start_recording()?;
+            start_recording(cli.autostart)?;
+        }
+        Commands::StartIn { seconds, every } => {
+            check_runtime_dependencies(true, false)?;
+            start_in(seconds, every, cli.autostart)?;
         }
         Commands::Stop => {
             stop_recording()?;
         }
+        Commands::Cancel => {
+            cancel_recording()?;
+        }
         Commands::Confirm => {
             check_runtime_dependencies(true, false)?;
             confirm_recording()?;
         }
         Commands::Toggle => {
             check_runtime_dependencies(true, false)?;
-            toggle_recording()?;
+            toggle_recording(cli.autostart)?;
+        }
+        Commands::Repeat => {
+            check_runtime_dependencies(true, false)?;
+            repeat_last()?;
         }
         Commands::Status => {
             show_status();
         }
+        Commands::Reset => {
+            reset_state()?;
+        }
         Commands::Config => {
             open_config()?;
         }
@@ -730,18 +1190,16 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 println!("{}", model);
             }
         }
-        Commands::ListAudioDevices => {
-            for dev in utils::list_audio_devices() {
-                let marker = if dev.is_default { " (default)" } else { "" };
-                println!("  {}{}", dev.description, marker);
-            }
-        }
+        Commands::ListAudioDevices { backend } => list_audio_devices(&backend)?,
         Commands::Debug { command } => match command {
             DebugCommands::List => debug_list()?,
             DebugCommands::Play { filename } => debug_play(&filename)?,
         },
-        Commands::Diagnose => diagnose()?,
-        Commands::DownloadModel => download_model()?,
+        Commands::Diagnose { bundle: false } => diagnose()?,
+        Commands::Diagnose { bundle: true } => dump_diagnostics()?,
+        Commands::GuiPreview { text } => gui_preview(text)?,
+        Commands::Transcribe { file } => transcribe_file(&file)?,
+        Commands::DownloadModel { force } => download_model(force)?,
     }
 
     Ok(())
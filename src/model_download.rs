@@ -0,0 +1,444 @@
+//! Downloading the Parakeet model files from HuggingFace.
+//!
+//! Each file is streamed to a `.part` file and only renamed into place once its
+//! size and SHA-256 match the manifest, so an interrupted or corrupted download
+//! is never mistaken for a complete model. Files already on disk are checked
+//! against the same manifest and downloaded again when they don't match.
+//! Network failures and bad transfers are retried with exponential backoff;
+//! filesystem errors are not.
+//!
+//! The manifest is the repository's LFS metadata (size and SHA-256 per file).
+//! When it can't be fetched, only the size the server advertises is checked.
+
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
+
+const BASE_URL: &str = "https://huggingface.co/istupakov/parakeet-tdt-0.6b-v3-onnx/resolve/main";
+const MANIFEST_URL: &str = "https://huggingface.co/api/models/istupakov/parakeet-tdt-0.6b-v3-onnx/tree/main";
+const FILES: &[&str] = &[
+    "encoder-model.onnx",
+    "encoder-model.onnx.data",
+    "decoder_joint-model.onnx",
+];
+
+/// Retries per file after the first attempt fails
+const MAX_RETRIES: u32 = 3;
+/// Wait before the first retry, doubled for each further one (1s, 2s, 4s)
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+
+/// Expected size and content of one model file
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ManifestEntry {
+    size: u64,
+    /// Lowercase hex SHA-256 (None for files stored outside LFS, checked by size only)
+    sha256: Option<String>,
+}
+
+/// Why a model download failed
+#[derive(Debug)]
+pub enum DownloadError {
+    /// Connection failure, timeout or HTTP error status
+    Network { file: String, reason: String },
+    /// The transfer ended before the expected size was received
+    Incomplete { file: String, expected: u64, received: u64 },
+    /// The received bytes don't hash to the manifest's SHA-256
+    Corrupt { file: String, expected: String, actual: String },
+    /// Creating, writing or renaming files in the model directory failed
+    Filesystem { path: String, source: io::Error },
+}
+
+impl fmt::Display for DownloadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DownloadError::Network { file, reason } => write!(f, "network error downloading {}: {}", file, reason),
+            DownloadError::Incomplete { file, expected, received } => write!(
+                f,
+                "{} is incomplete: received {} of {} bytes",
+                file, received, expected
+            ),
+            DownloadError::Corrupt { file, expected, actual } => write!(
+                f,
+                "{} is corrupt: SHA-256 {} instead of {}",
+                file, actual, expected
+            ),
+            DownloadError::Filesystem { path, source } => write!(f, "can't write {}: {}", path, source),
+        }
+    }
+}
+
+impl std::error::Error for DownloadError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            DownloadError::Filesystem { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+impl DownloadError {
+    /// Worth trying again (the network, not the disk, was the problem)
+    fn is_transient(&self) -> bool {
+        !matches!(self, DownloadError::Filesystem { .. })
+    }
+
+    fn filesystem(path: &Path, source: io::Error) -> Self {
+        DownloadError::Filesystem { path: path.display().to_string(), source }
+    }
+}
+
+/// Download every model file into `model_dir`. Files already present are kept
+/// when they match the manifest, unless `force_redownload` is set.
+pub fn download_model(model_dir: &Path, force_redownload: bool) -> Result<(), DownloadError> {
+    fs::create_dir_all(model_dir).map_err(|e| DownloadError::filesystem(model_dir, e))?;
+
+    println!("Model directory: {}", model_dir.display());
+    println!("Source: {}", BASE_URL);
+    println!();
+
+    let client = reqwest::blocking::Client::builder()
+        .timeout(None)
+        .build()
+        .map_err(|e| DownloadError::Network { file: BASE_URL.to_string(), reason: e.to_string() })?;
+
+    let manifest = match fetch_manifest(&client) {
+        Ok(manifest) => manifest,
+        Err(e) => {
+            println!("Couldn't fetch the file manifest ({}); only file sizes will be checked", e);
+            println!();
+            Vec::new()
+        }
+    };
+
+    for filename in FILES {
+        let dest = model_dir.join(filename);
+        let entry = manifest.iter().find(|(name, _)| name == filename).map(|(_, entry)| entry);
+
+        let present = fs::metadata(&dest).map_or(false, |metadata| metadata.len() > 0);
+        if !force_redownload && present {
+            print!("  {} — already exists, verifying... ", filename);
+            let _ = io::stdout().flush();
+            match verify_existing(&dest, filename, entry) {
+                Ok(size) => {
+                    println!("ok ({:.1} MB), skipping", size as f64 / 1_048_576.0);
+                    continue;
+                }
+                Err(e) if e.is_transient() => println!("{}, downloading again", e),
+                Err(e) => {
+                    println!("failed");
+                    return Err(e);
+                }
+            }
+        }
+
+        let url = format!("{}/{}", BASE_URL, filename);
+        let size = retry_with_backoff(
+            || {
+                print!("  Downloading {}... ", filename);
+                let _ = io::stdout().flush();
+                download_file(&client, &url, filename, &dest, entry)
+            },
+            |e, backoff| println!("failed ({}), retrying in {}s", e, backoff.as_secs()),
+            thread::sleep,
+        );
+        match size {
+            Ok(size) => println!("{:.1} MB", size as f64 / 1_048_576.0),
+            Err(e) => {
+                println!("failed");
+                return Err(e);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Run `attempt` until it succeeds, retrying transient errors up to
+/// `MAX_RETRIES` times with exponential backoff
+fn retry_with_backoff<T>(
+    mut attempt: impl FnMut() -> Result<T, DownloadError>,
+    mut on_retry: impl FnMut(&DownloadError, Duration),
+    mut sleep: impl FnMut(Duration),
+) -> Result<T, DownloadError> {
+    let mut retry = 0;
+    loop {
+        match attempt() {
+            Ok(value) => return Ok(value),
+            Err(e) if e.is_transient() && retry < MAX_RETRIES => {
+                let backoff = INITIAL_BACKOFF * 2u32.pow(retry);
+                retry += 1;
+                on_retry(&e, backoff);
+                sleep(backoff);
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Fetch the size and SHA-256 of every model file from the repository's LFS metadata
+fn fetch_manifest(client: &reqwest::blocking::Client) -> Result<Vec<(String, ManifestEntry)>, String> {
+    let response = client.get(MANIFEST_URL).send().map_err(|e| e.to_string())?;
+    if !response.status().is_success() {
+        return Err(format!("HTTP {}", response.status()));
+    }
+    let body = response.text().map_err(|e| e.to_string())?;
+    parse_manifest(&body)
+}
+
+/// Parse a HuggingFace tree listing into manifest entries for `FILES`
+fn parse_manifest(json: &str) -> Result<Vec<(String, ManifestEntry)>, String> {
+    let listing: serde_json::Value = serde_json::from_str(json).map_err(|e| e.to_string())?;
+    let files = listing.as_array().ok_or("unexpected manifest format")?;
+
+    let mut manifest = Vec::new();
+    for filename in FILES {
+        let Some(file) = files.iter().find(|file| file["path"].as_str() == Some(*filename)) else {
+            return Err(format!("{} is missing from the manifest", filename));
+        };
+        // LFS files list the real content's size and SHA-256; the top-level
+        // size and oid describe the pointer
+        let entry = match file.get("lfs") {
+            Some(lfs) => ManifestEntry {
+                size: lfs["size"].as_u64().ok_or("manifest entry without a size")?,
+                sha256: lfs["oid"].as_str().map(|oid| oid.to_lowercase()),
+            },
+            None => ManifestEntry {
+                size: file["size"].as_u64().ok_or("manifest entry without a size")?,
+                sha256: None,
+            },
+        };
+        manifest.push((filename.to_string(), entry));
+    }
+    Ok(manifest)
+}
+
+/// Check a file already on disk, returning its size when it's complete
+fn verify_existing(path: &Path, filename: &str, entry: Option<&ManifestEntry>) -> Result<u64, DownloadError> {
+    let size = fs::metadata(path).map_err(|e| DownloadError::filesystem(path, e))?.len();
+    let Some(entry) = entry else {
+        return Ok(size);
+    };
+    check_size(filename, Some(entry.size), size)?;
+    if let Some(expected) = &entry.sha256 {
+        let mut file = File::open(path).map_err(|e| DownloadError::filesystem(path, e))?;
+        let mut hasher = Sha256::new();
+        io::copy(&mut file, &mut hasher).map_err(|e| DownloadError::filesystem(path, e))?;
+        check_sha256(filename, expected, &hex(&hasher.finalize()))?;
+    }
+    Ok(size)
+}
+
+/// Stream one file to `dest`, returning its size once it's complete
+fn download_file(
+    client: &reqwest::blocking::Client,
+    url: &str,
+    filename: &str,
+    dest: &Path,
+    entry: Option<&ManifestEntry>,
+) -> Result<u64, DownloadError> {
+    let network = |reason: String| DownloadError::Network { file: filename.to_string(), reason };
+
+    let mut response = client.get(url).send().map_err(|e| network(e.to_string()))?;
+    if !response.status().is_success() {
+        return Err(network(format!("HTTP {}", response.status())));
+    }
+    let expected = entry.map(|entry| entry.size).or(response.content_length());
+
+    let mut partial = dest.as_os_str().to_owned();
+    partial.push(".part");
+    let partial = PathBuf::from(partial);
+    let file = File::create(&partial).map_err(|e| DownloadError::filesystem(&partial, e))?;
+    let mut writer = HashingWriter { inner: file, hasher: Sha256::new() };
+    // Read errors mid-transfer are network failures; the size check below catches
+    // a stream that ended early without one
+    let received = match response.copy_to(&mut writer) {
+        Ok(received) => received,
+        Err(e) => {
+            drop(writer);
+            let _ = fs::remove_file(&partial);
+            return Err(network(e.to_string()));
+        }
+    };
+    let HashingWriter { inner: file, hasher } = writer;
+    file.sync_all().map_err(|e| DownloadError::filesystem(&partial, e))?;
+    drop(file);
+
+    let verified = check_size(filename, expected, received).and_then(|()| match entry.and_then(|e| e.sha256.as_ref()) {
+        Some(sha256) => check_sha256(filename, sha256, &hex(&hasher.finalize())),
+        None => Ok(()),
+    });
+    if let Err(e) = verified {
+        let _ = fs::remove_file(&partial);
+        return Err(e);
+    }
+
+    fs::rename(&partial, dest).map_err(|e| DownloadError::filesystem(dest, e))?;
+    Ok(received)
+}
+
+/// Fail when `received` bytes don't match the `expected` size (if known)
+fn check_size(filename: &str, expected: Option<u64>, received: u64) -> Result<(), DownloadError> {
+    match expected {
+        Some(expected) if expected != received => {
+            Err(DownloadError::Incomplete { file: filename.to_string(), expected, received })
+        }
+        _ => Ok(()),
+    }
+}
+
+fn check_sha256(filename: &str, expected: &str, actual: &str) -> Result<(), DownloadError> {
+    if expected.eq_ignore_ascii_case(actual) {
+        Ok(())
+    } else {
+        Err(DownloadError::Corrupt { file: filename.to_string(), expected: expected.to_string(), actual: actual.to_string() })
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Writes through to `inner` while hashing everything written
+struct HashingWriter<W> {
+    inner: W,
+    hasher: Sha256,
+}
+
+impl<W: Write> Write for HashingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.hasher.update(&buf[..written]);
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    /// SHA-256 of "hello"
+    const HELLO_SHA256: &str = "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824";
+
+    fn network_error() -> DownloadError {
+        DownloadError::Network { file: "f".to_string(), reason: "reset".to_string() }
+    }
+
+    #[test]
+    fn test_retries_transient_errors_with_backoff() {
+        let attempts = RefCell::new(0);
+        let sleeps = RefCell::new(Vec::new());
+        let result = retry_with_backoff(
+            || {
+                *attempts.borrow_mut() += 1;
+                if *attempts.borrow() < 3 {
+                    Err(network_error())
+                } else {
+                    Ok(42)
+                }
+            },
+            |_, _| {},
+            |backoff| sleeps.borrow_mut().push(backoff.as_secs()),
+        );
+        assert_eq!(result.unwrap(), 42);
+        assert_eq!(*sleeps.borrow(), vec![1, 2]);
+    }
+
+    #[test]
+    fn test_gives_up_after_max_retries() {
+        let attempts = RefCell::new(0);
+        let result: Result<(), _> = retry_with_backoff(
+            || {
+                *attempts.borrow_mut() += 1;
+                Err(DownloadError::Incomplete { file: "f".to_string(), expected: 10, received: 5 })
+            },
+            |_, _| {},
+            |_| {},
+        );
+        assert!(matches!(result, Err(DownloadError::Incomplete { .. })));
+        assert_eq!(*attempts.borrow(), MAX_RETRIES + 1);
+    }
+
+    #[test]
+    fn test_filesystem_errors_not_retried() {
+        let attempts = RefCell::new(0);
+        let result: Result<(), _> = retry_with_backoff(
+            || {
+                *attempts.borrow_mut() += 1;
+                Err(DownloadError::filesystem(Path::new("/x"), io::Error::from(io::ErrorKind::PermissionDenied)))
+            },
+            |_, _| {},
+            |_| panic!("slept before a filesystem error"),
+        );
+        assert!(matches!(result, Err(DownloadError::Filesystem { .. })));
+        assert_eq!(*attempts.borrow(), 1);
+    }
+
+    #[test]
+    fn test_check_size() {
+        assert!(check_size("f", Some(10), 10).is_ok());
+        assert!(check_size("f", None, 3).is_ok());
+        assert!(matches!(
+            check_size("f", Some(10), 4),
+            Err(DownloadError::Incomplete { expected: 10, received: 4, .. })
+        ));
+    }
+
+    #[test]
+    fn test_hashing_writer() {
+        let mut writer = HashingWriter { inner: Vec::new(), hasher: Sha256::new() };
+        writer.write_all(b"hel").unwrap();
+        writer.write_all(b"lo").unwrap();
+        assert_eq!(writer.inner, b"hello");
+        assert_eq!(hex(&writer.hasher.finalize()), HELLO_SHA256);
+    }
+
+    #[test]
+    fn test_verify_existing() {
+        let dir = std::env::temp_dir().join(format!("model-download-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("model.onnx");
+        fs::write(&path, b"hello").unwrap();
+
+        let good = ManifestEntry { size: 5, sha256: Some(HELLO_SHA256.to_string()) };
+        assert_eq!(verify_existing(&path, "model.onnx", Some(&good)).unwrap(), 5);
+
+        let truncated = ManifestEntry { size: 6, ..good.clone() };
+        assert!(matches!(verify_existing(&path, "model.onnx", Some(&truncated)), Err(DownloadError::Incomplete { .. })));
+
+        let other = ManifestEntry { sha256: Some("00".repeat(32)), ..good };
+        assert!(matches!(verify_existing(&path, "model.onnx", Some(&other)), Err(DownloadError::Corrupt { .. })));
+
+        // No manifest: the file is kept as is
+        assert_eq!(verify_existing(&path, "model.onnx", None).unwrap(), 5);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_parse_manifest() {
+        let json = r#"[
+            {"type": "file", "path": "encoder-model.onnx", "size": 41000000, "oid": "abc"},
+            {"type": "file", "path": "encoder-model.onnx.data", "size": 134,
+             "lfs": {"oid": "AA11", "size": 2440000000, "pointerSize": 134}},
+            {"type": "file", "path": "decoder_joint-model.onnx", "size": 133,
+             "lfs": {"oid": "bb22", "size": 72000000, "pointerSize": 133}},
+            {"type": "file", "path": "README.md", "size": 10, "oid": "def"}
+        ]"#;
+        let manifest = parse_manifest(json).unwrap();
+        assert_eq!(manifest.len(), FILES.len());
+        assert_eq!(manifest[0].1, ManifestEntry { size: 41_000_000, sha256: None });
+        assert_eq!(manifest[1].1, ManifestEntry { size: 2_440_000_000, sha256: Some("aa11".to_string()) });
+
+        assert!(parse_manifest(r#"[{"path": "encoder-model.onnx", "size": 1}]"#).is_err());
+        assert!(parse_manifest("{}").is_err());
+    }
+}
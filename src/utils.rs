@@ -22,7 +22,56 @@ pub fn list_audio_devices() -> Vec<DeviceInfo> {
     dictation_engine::audio_backend::list_devices(BackendType::Auto).unwrap_or_default()
 }
 
+/// Devices for `backend` sorted by name, for stable `list-audio-devices` output.
+/// `auto` uses the `pactl` listing (with backend fallback); an explicit
+/// backend is enumerated directly.
+pub fn list_devices_for(backend: BackendType) -> Result<Vec<DeviceInfo>, Box<dyn std::error::Error>> {
+    let mut devices = match backend {
+        BackendType::Auto => dictation_engine::audio_backend::list_devices(backend)?,
+        _ => dictation_engine::audio_backend::list_devices_backend(backend)?,
+    };
+    devices.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(devices)
+}
+
+/// Device name as a TOML basic string, ready to paste as the `audio_device` value
+pub fn quote_device_name(name: &str) -> String {
+    let mut quoted = String::with_capacity(name.len() + 2);
+    quoted.push('"');
+    for c in name.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\t' => quoted.push_str("\\t"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{:04X}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
 /// Get a summary of available engines for display
 pub fn get_engine_summary() -> String {
     "parakeet".to_string()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quote_device_name_is_toml_basic_string() {
+        assert_eq!(quote_device_name("default"), "\"default\"");
+        assert_eq!(
+            quote_device_name("alsa_input.usb-Blue_Yeti-00.analog-stereo"),
+            "\"alsa_input.usb-Blue_Yeti-00.analog-stereo\""
+        );
+        assert_eq!(quote_device_name("Blue Yeti"), "\"Blue Yeti\"");
+        assert_eq!(quote_device_name("Mic \"Pro\""), "\"Mic \\\"Pro\\\"\"");
+        assert_eq!(quote_device_name("hw\\0"), "\"hw\\\\0\"");
+        assert_eq!(quote_device_name("a\tb\u{1}"), "\"a\\tb\\u0001\"");
+    }
+}